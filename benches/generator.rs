@@ -0,0 +1,112 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use hora_id::{AtomicHoraGenerator, HoraGenerator, HoraId, HoraLayout, SyncHoraGenerator};
+use std::fmt::Write;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// the default layout caps generation at 65,536 IDs per 1/256 second, so a fast loop would mostly
+// measure waiting for the next tick; the layout with more sequence bits measures the generator
+const LAYOUT: HoraLayout = HoraLayout::WIDE_SEQUENCE;
+
+fn single(c: &mut Criterion) {
+    let mut generator = HoraGenerator::with_layout(LAYOUT, 1).unwrap();
+    c.bench_function("next", |b| b.iter(|| generator.next()));
+}
+
+fn batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("next_n_into");
+    for n in [100, 10_000] {
+        let mut generator = HoraGenerator::with_layout(LAYOUT, 1).unwrap();
+        let mut ids = Vec::with_capacity(n);
+        group.throughput(Throughput::Elements(n as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(n), &n, |b, &n| {
+            b.iter(|| {
+                ids.clear();
+                generator.next_n_into(n, &mut ids);
+            })
+        });
+    }
+    group.finish();
+}
+
+fn encoding(c: &mut Criterion) {
+    let id: HoraId = "00cd01daff010002".parse().unwrap();
+    let mut group = c.benchmark_group("encoding");
+    // formatting every byte on its own, as `Display` did before the lookup table
+    group.bench_function("to_string_per_byte", |b| {
+        b.iter(|| {
+            let mut s = String::new();
+            for byte in black_box(id).as_bytes() {
+                write!(s, "{:02x}", byte).unwrap();
+            }
+            s
+        })
+    });
+    group.bench_function("to_string", |b| b.iter(|| black_box(id).to_string()));
+    let mut buf = [0; 16];
+    group.bench_function("encode_hex", |b| {
+        b.iter(|| black_box(id).encode_hex(&mut buf).len())
+    });
+    // parsing every character on its own, as `FromStr` did before the lookup table
+    group.bench_function("parse_per_char", |b| {
+        b.iter(|| {
+            let mut num = 0u64;
+            for c in black_box("00cd01daff010002").chars() {
+                num = (num << 4) | c.to_digit(16).unwrap() as u64;
+            }
+            HoraId::from(num)
+        })
+    });
+    group.bench_function("parse", |b| {
+        b.iter(|| black_box("00cd01daff010002").parse::<HoraId>())
+    });
+    group.bench_function("to_base32", |b| b.iter(|| black_box(id).to_base32()));
+    group.bench_function("from_base32", |b| {
+        b.iter(|| HoraId::from_base32(black_box("01K81VBZG2002")))
+    });
+    group.bench_function("to_base62", |b| b.iter(|| black_box(id).to_base62()));
+    group.bench_function("from_base62", |b| {
+        b.iter(|| HoraId::from_base62(black_box("04GHmVCTHPu")))
+    });
+    group.finish();
+}
+
+/// Time for `threads` threads to call `next` `iters` times between them
+fn contended(iters: u64, threads: u64, next: impl Fn() -> HoraId + Sync) -> Duration {
+    let per_thread = iters.div_ceil(threads);
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                for _ in 0..per_thread {
+                    black_box(next());
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+// the mutex and the compare-and-swap loop under contention
+fn contention(c: &mut Criterion) {
+    let mut group = c.benchmark_group("contention");
+    group.throughput(Throughput::Elements(1));
+    for threads in [1, 2, 4, 8, 16] {
+        let sync = SyncHoraGenerator::from(HoraGenerator::with_layout(LAYOUT, 1).unwrap());
+        group.bench_with_input(
+            BenchmarkId::new("sync", threads),
+            &threads,
+            |b, &threads| b.iter_custom(|iters| contended(iters, threads, || sync.next())),
+        );
+        let atomic = AtomicHoraGenerator::from(HoraGenerator::with_layout(LAYOUT, 1).unwrap());
+        group.bench_with_input(
+            BenchmarkId::new("atomic", threads),
+            &threads,
+            |b, &threads| b.iter_custom(|iters| contended(iters, threads, || atomic.next())),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, single, batch, encoding, contention);
+criterion_main!(benches);
@@ -0,0 +1,25 @@
+#![no_main]
+
+use hora_id::{HoraId, HoraId128};
+use libfuzzer_sys::fuzz_target;
+
+/// Input in the form the encoder writes: uppercase, with the accepted look-alikes replaced
+fn canonical(s: &str) -> String {
+    s.to_ascii_uppercase()
+        .chars()
+        .map(|c| match c {
+            'O' => '0',
+            'I' | 'L' => '1',
+            c => c,
+        })
+        .collect()
+}
+
+fuzz_target!(|s: &str| {
+    if let Ok(id) = HoraId::from_base32(s) {
+        assert_eq!(id.to_base32(), canonical(s));
+    }
+    if let Ok(id) = HoraId128::from_base32(s) {
+        assert_eq!(id.to_base32(), canonical(s));
+    }
+});
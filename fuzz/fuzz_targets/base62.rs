@@ -0,0 +1,14 @@
+#![no_main]
+
+use hora_id::{HoraId, HoraId128};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|s: &str| {
+    // every ID has exactly one encoding, so anything accepted must re-encode to the input
+    if let Ok(id) = HoraId::from_base62(s) {
+        assert_eq!(id.to_base62(), s);
+    }
+    if let Ok(id) = HoraId128::from_base62(s) {
+        assert_eq!(id.to_base62(), s);
+    }
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use hora_id::{HoraId, HoraId128};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    match HoraId::try_from(data) {
+        Ok(id) => assert_eq!(id.as_bytes(), data),
+        Err(_) => assert_ne!(data.len(), 8),
+    }
+    match HoraId128::try_from(data) {
+        Ok(id) => assert_eq!(id.as_ref(), data),
+        Err(_) => assert_ne!(data.len(), 16),
+    }
+});
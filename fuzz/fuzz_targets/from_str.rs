@@ -0,0 +1,18 @@
+#![no_main]
+
+use hora_id::{HoraId, HoraId128};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|s: &str| {
+    // hexadecimal is case-insensitive, so the canonical form is the lowercase input
+    if let Ok(id) = s.parse::<HoraId>() {
+        assert_eq!(id.to_string(), s.to_ascii_lowercase());
+    }
+    if let Ok(id) = s.parse::<HoraId128>() {
+        assert_eq!(id.to_string(), s.to_ascii_lowercase());
+    }
+    if let Ok(id) = HoraId::parse_lenient(s) {
+        assert_eq!(id.to_string().parse::<HoraId>(), Ok(id));
+        assert_eq!(HoraId::parse_lenient(&id.to_string()), Ok(id));
+    }
+});
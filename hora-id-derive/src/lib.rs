@@ -0,0 +1,120 @@
+//! Derive macro for `hora_id::TypedHoraId`
+//!
+//! Use it through the `derive` feature of hora-id, which re-exports the macro next to the trait.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+/// Derive `TypedHoraId` for a struct with a single `HoraId` field
+///
+/// Generates `Display`, `FromStr`, conversions from and to `HoraId`, and serde support when the
+/// `serde` feature of hora-id is enabled. Set a prefix with `#[hora_id(prefix = "usr")]`.
+#[proc_macro_derive(TypedHoraId, attributes(hora_id))]
+pub fn derive_typed_hora_id(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "TypedHoraId can't be derived for generic structs",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "TypedHoraId can only be derived for structs",
+            ))
+        }
+    };
+    let (get, construct) = match fields {
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => (quote!(self.0), quote!(Self(id))),
+        Fields::Named(fields) if fields.named.len() == 1 => {
+            let field = &fields.named[0].ident;
+            (quote!(self.#field), quote!(Self { #field: id }))
+        }
+        _ => {
+            return Err(syn::Error::new_spanned(
+                fields,
+                "TypedHoraId needs a struct with exactly one HoraId field",
+            ))
+        }
+    };
+
+    let prefix = match parse_prefix(&input)? {
+        Some(prefix) => quote!(::core::option::Option::Some(#prefix)),
+        None => quote!(::core::option::Option::None),
+    };
+
+    Ok(quote! {
+        impl ::hora_id::TypedHoraId for #name {
+            const PREFIX: ::core::option::Option<&'static str> = #prefix;
+
+            fn id(&self) -> ::hora_id::HoraId {
+                #get
+            }
+        }
+
+        impl ::core::convert::From<::hora_id::HoraId> for #name {
+            fn from(id: ::hora_id::HoraId) -> Self {
+                #construct
+            }
+        }
+
+        impl ::core::convert::From<#name> for ::hora_id::HoraId {
+            fn from(id: #name) -> Self {
+                ::hora_id::TypedHoraId::id(&id)
+            }
+        }
+
+        impl ::core::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::hora_id::__private::fmt(self, f)
+            }
+        }
+
+        impl ::core::str::FromStr for #name {
+            type Err = ::hora_id::HoraIdParseError;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                ::hora_id::__private::parse(s)
+            }
+        }
+
+        ::hora_id::__typed_hora_id_serde!(#name);
+    })
+}
+
+/// Read the prefix from `#[hora_id(prefix = "...")]`
+fn parse_prefix(input: &DeriveInput) -> syn::Result<Option<LitStr>> {
+    let mut prefix = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("hora_id"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("prefix") {
+                let value: LitStr = meta.value()?.parse()?;
+                if value.value().is_empty() {
+                    return Err(meta.error("the prefix can't be empty"));
+                }
+                prefix = Some(value);
+                Ok(())
+            } else {
+                Err(meta.error("unknown hora_id attribute, expected `prefix`"))
+            }
+        })?;
+    }
+    Ok(prefix)
+}
@@ -0,0 +1,185 @@
+//! Node.js bindings for HoraID
+//!
+//! Build the addon with `npm run build` in this directory, which also generates the TypeScript
+//! declarations. IDs are passed as `BigInt`, since a JavaScript `number` loses the lowest bits,
+//! and every function taking an ID also accepts its hexadecimal string.
+//!
+//! ```js
+//! const { HoraGenerator, inspect, parse } = require("hora-id");
+//!
+//! const generator = new HoraGenerator(1);
+//! const id = generator.generate(); // 57704410318438402n
+//! console.log(parse("00cd01daff010002") === 57704410318438402n);
+//! console.log(inspect(id));
+//! // {
+//! //   id: '00cd01daff010002',
+//! //   value: 57704410318438402n,
+//! //   version: 0,
+//! //   timestamp: '2025-06-05T12:02:34.997Z',
+//! //   machineId: 1,
+//! //   sequence: 2
+//! // }
+//! ```
+
+use chrono::{DateTime, SecondsFormat};
+use core::fmt::Display;
+use hora_id::{HoraId, HoraParts, SyncHoraGenerator};
+use napi::bindgen_prelude::{BigInt, Either};
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+
+fn invalid_arg(err: impl Display) -> Error {
+    Error::new(Status::InvalidArg, err.to_string())
+}
+
+fn from_big_int(value: &BigInt) -> Result<HoraId> {
+    match value.get_u64() {
+        (false, num, true) => Ok(HoraId::from_bytes(num.to_be_bytes())),
+        _ => Err(invalid_arg("HoraId must be between 0n and 2n ** 64n - 1n")),
+    }
+}
+
+fn from_js(id: Either<String, BigInt>) -> Result<HoraId> {
+    match id {
+        Either::A(hex) => hex.parse().map_err(invalid_arg),
+        Either::B(value) => from_big_int(&value),
+    }
+}
+
+/// ISO 8601 form of the creation time, the same as `Date.prototype.toISOString()`
+fn iso_timestamp(parts: &HoraParts) -> String {
+    DateTime::from_timestamp_millis(parts.timestamp_millis as i64)
+        .expect("HoraId timestamps are within the range of chrono")
+        .to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// Parts of a HoraId, returned by `inspect`
+#[napi(object)]
+pub struct HoraIdInfo {
+    /// The 16 character hexadecimal form
+    pub id: String,
+    /// The ID as a `BigInt`
+    pub value: BigInt,
+    /// Format version the ID was decoded with
+    pub version: u8,
+    /// Creation time as an ISO 8601 string in UTC
+    pub timestamp: String,
+    pub machine_id: u16,
+    pub sequence: u32,
+}
+
+impl TryFrom<HoraId> for HoraIdInfo {
+    type Error = hora_id::HoraError;
+
+    /// Decode the ID according to its format version
+    fn try_from(id: HoraId) -> core::result::Result<Self, Self::Error> {
+        let parts = id.decode()?;
+        Ok(Self {
+            id: id.to_string(),
+            value: id.to_u64().into(),
+            version: parts.version,
+            timestamp: iso_timestamp(&parts),
+            machine_id: parts.machine_id,
+            sequence: parts.sequence,
+        })
+    }
+}
+
+/// Parse the 16 character hexadecimal form into a `BigInt`, throws on invalid input
+#[napi]
+pub fn parse(hex: String) -> Result<BigInt> {
+    let id: HoraId = hex.parse().map_err(invalid_arg)?;
+    Ok(id.to_u64().into())
+}
+
+/// The 16 character hexadecimal form of an ID
+#[napi]
+pub fn format(id: BigInt) -> Result<String> {
+    Ok(from_big_int(&id)?.to_string())
+}
+
+/// Split an ID, given as `BigInt` or hexadecimal string, into its parts, throws if its format
+/// version is unsupported
+#[napi]
+pub fn inspect(id: Either<String, BigInt>) -> Result<HoraIdInfo> {
+    HoraIdInfo::try_from(from_js(id)?).map_err(invalid_arg)
+}
+
+/// Thread-safe ID generator
+///
+/// Create one generator per process and share it, two generators with the same machine ID
+/// create duplicate IDs.
+#[napi]
+pub struct HoraGenerator {
+    generator: SyncHoraGenerator,
+}
+
+#[napi]
+impl HoraGenerator {
+    /// Create a generator for `machineId`, throws if the clock is before the HoraID epoch
+    #[napi(constructor)]
+    pub fn new(machine_id: u8) -> Result<Self> {
+        let generator = SyncHoraGenerator::new(machine_id).map_err(invalid_arg)?;
+        Ok(Self { generator })
+    }
+
+    /// Generate a new ID as a `BigInt`
+    #[napi]
+    pub fn generate(&self) -> Result<BigInt> {
+        Ok(self.next()?.to_u64().into())
+    }
+
+    /// Generate a new ID as its 16 character hexadecimal string
+    #[napi]
+    pub fn generate_string(&self) -> Result<String> {
+        Ok(self.next()?.to_string())
+    }
+}
+
+impl HoraGenerator {
+    fn next(&self) -> Result<HoraId> {
+        self.generator
+            .try_next()
+            .map_err(|err| Error::new(Status::GenericFailure, err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        let value = parse("00cd01daff010002".to_string()).unwrap();
+        assert_eq!(value, BigInt::from(57704410318438402u64));
+        assert_eq!(format(value.clone()).unwrap(), "00cd01daff010002");
+        assert!(parse("xyz".to_string()).is_err());
+        let negative = BigInt {
+            sign_bit: true,
+            words: vec![1],
+        };
+        assert!(format(negative).is_err());
+        assert!(format(BigInt::from(u128::MAX)).is_err());
+
+        let info = inspect(Either::B(value)).unwrap();
+        assert_eq!(info.id, "00cd01daff010002");
+        assert_eq!(info.timestamp, "2025-06-05T12:02:34.997Z");
+        assert_eq!((info.version, info.machine_id, info.sequence), (0, 1, 2));
+        let same = inspect(Either::A("00cd01daff010002".to_string())).unwrap();
+        assert_eq!(same.value, info.value);
+        let err = inspect(Either::A("40cd01daff010002".to_string()))
+            .err()
+            .unwrap();
+        assert_eq!(err.reason, "unsupported HoraId format version 1");
+    }
+
+    #[test]
+    fn generator() {
+        let generator = HoraGenerator::new(1).unwrap();
+        let first = generator.generate().unwrap();
+        let second = generator.generate_string().unwrap();
+        let first = from_big_int(&first).unwrap();
+        assert_eq!(first.machine_id(), 1);
+        assert!(first < second.parse().unwrap());
+    }
+}
@@ -0,0 +1,228 @@
+//! Python bindings for HoraID
+//!
+//! Build and install the `hora_id` Python module with `maturin develop` in this directory.
+//!
+//! ```python
+//! from hora_id import HoraGenerator, HoraId
+//!
+//! generator = HoraGenerator(1)
+//! id = generator.next()
+//! print(str(id), int(id), id.datetime(), id.machine_id)
+//! assert HoraId("00cd01daff010002") < HoraId(57704410318438403)
+//! ```
+
+use chrono::{DateTime, Utc};
+use hora_id::{HoraId, HoraParts, SyncHoraGenerator};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyInt;
+use std::fmt::Display;
+
+fn value_error(err: impl Display) -> PyErr {
+    PyValueError::new_err(err.to_string())
+}
+
+/// Milliseconds since the Unix epoch, times before 1970 become 0 and fail as before the epoch
+fn unix_millis(datetime: &DateTime<Utc>) -> u64 {
+    datetime.timestamp_millis().try_into().unwrap_or(0)
+}
+
+/// A time-sorted 64-bit ID, created from its hexadecimal string or its integer value
+#[pyclass(
+    name = "HoraId",
+    module = "hora_id",
+    frozen,
+    eq,
+    ord,
+    hash,
+    skip_from_py_object
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PyHoraId {
+    id: HoraId,
+}
+
+#[pymethods]
+impl PyHoraId {
+    #[new]
+    fn new(value: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let id = if value.is_instance_of::<PyInt>() {
+            HoraId::from_bytes(value.extract::<u64>()?.to_be_bytes())
+        } else {
+            value.extract::<&str>()?.parse().map_err(value_error)?
+        };
+        Ok(Self { id })
+    }
+
+    /// The smallest ID of the tick containing `datetime`, for range queries
+    ///
+    /// Like `HoraId::min_for_millis` in Rust, `datetime` is truncated to the millisecond.
+    #[staticmethod]
+    fn min_for(datetime: DateTime<Utc>) -> PyResult<Self> {
+        let id = HoraId::min_for_millis(unix_millis(&datetime)).map_err(value_error)?;
+        Ok(Self { id })
+    }
+
+    /// The largest ID of the tick containing `datetime`, for range queries
+    #[staticmethod]
+    fn max_for(datetime: DateTime<Utc>) -> PyResult<Self> {
+        let id = HoraId::max_for_millis(unix_millis(&datetime)).map_err(value_error)?;
+        Ok(Self { id })
+    }
+
+    /// Creation time as a timezone-aware UTC `datetime`
+    fn datetime(&self) -> PyResult<DateTime<Utc>> {
+        // the parts are only read to check the version, `to_utc` keeps the sub-millisecond ticks
+        self.parts()?;
+        Ok(self.id.to_utc())
+    }
+
+    /// Format version of the ID, the other parts raise `ValueError` for unsupported versions
+    #[getter]
+    fn version(&self) -> u8 {
+        self.id.version()
+    }
+
+    /// Creation time in milliseconds since the Unix epoch
+    #[getter]
+    fn timestamp_millis(&self) -> PyResult<u64> {
+        Ok(self.parts()?.timestamp_millis)
+    }
+
+    #[getter]
+    fn machine_id(&self) -> PyResult<u16> {
+        Ok(self.parts()?.machine_id)
+    }
+
+    #[getter]
+    fn sequence(&self) -> PyResult<u32> {
+        Ok(self.parts()?.sequence)
+    }
+
+    fn __str__(&self) -> String {
+        self.id.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("HoraId('{}')", self.id)
+    }
+
+    fn __int__(&self) -> u64 {
+        self.id.to_u64()
+    }
+
+    fn __bytes__(&self) -> &[u8] {
+        self.id.as_bytes()
+    }
+}
+
+impl PyHoraId {
+    fn parts(&self) -> PyResult<HoraParts> {
+        self.id.decode().map_err(value_error)
+    }
+}
+
+impl From<HoraId> for PyHoraId {
+    fn from(id: HoraId) -> Self {
+        Self { id }
+    }
+}
+
+/// Thread-safe ID generator, also an endless iterator of IDs
+#[pyclass(name = "HoraGenerator", module = "hora_id", frozen)]
+pub struct PyHoraGenerator {
+    generator: SyncHoraGenerator,
+}
+
+#[pymethods]
+impl PyHoraGenerator {
+    #[new]
+    fn new(machine_id: u8) -> PyResult<Self> {
+        let generator = SyncHoraGenerator::new(machine_id).map_err(value_error)?;
+        Ok(Self { generator })
+    }
+
+    /// Generate a new ID, raises `ValueError` if the system clock is unusable
+    fn next(&self, py: Python<'_>) -> PyResult<PyHoraId> {
+        // waiting for the next tick doesn't need the GIL
+        let id = py
+            .detach(|| self.generator.try_next())
+            .map_err(value_error)?;
+        Ok(id.into())
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&self, py: Python<'_>) -> PyResult<PyHoraId> {
+        self.next(py)
+    }
+}
+
+#[pymodule(name = "hora_id")]
+fn hora_id_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyHoraId>()?;
+    m.add_class::<PyHoraGenerator>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::ffi::c_str;
+
+    #[test]
+    fn python_api() {
+        Python::attach(|py| {
+            let module = PyModule::new(py, "hora_id").unwrap();
+            hora_id_module(&module).unwrap();
+            let globals = pyo3::types::PyDict::new(py);
+            globals.set_item("hora_id", module).unwrap();
+            py.run(
+                c_str!(
+                    r#"
+from datetime import datetime, timezone
+HoraId, HoraGenerator = hora_id.HoraId, hora_id.HoraGenerator
+
+id = HoraId("00cd01daff010002")
+assert str(id) == "00cd01daff010002"
+assert repr(id) == "HoraId('00cd01daff010002')"
+assert int(id) == 57704410318438402
+assert bytes(id) == bytes.fromhex("00cd01daff010002")
+assert HoraId(57704410318438402) == id
+assert id < HoraId(57704410318438403)
+assert len({id, HoraId(57704410318438402)}) == 1
+assert (id.machine_id, id.sequence) == (1, 2)
+assert id.datetime().tzinfo is not None
+assert id.datetime() == datetime(2025, 6, 5, 12, 2, 34, 996093, timezone.utc)
+assert id.timestamp_millis == 1749124954997
+created = datetime(2025, 6, 5, 12, 2, 34, 997000, timezone.utc)
+assert HoraId.min_for(created) <= id <= HoraId.max_for(created)
+
+try:
+    HoraId("xyz")
+    raise AssertionError("invalid ID was accepted")
+except ValueError:
+    pass
+
+future = HoraId("40cd01daff010002")
+assert (id.version, future.version) == (0, 1)
+try:
+    future.machine_id
+    raise AssertionError("unsupported version was decoded")
+except ValueError as err:
+    assert str(err) == "unsupported HoraId format version 1"
+
+generator = HoraGenerator(1)
+first, second = next(generator), generator.next()
+assert first < second and first.machine_id == 1
+"#
+                ),
+                Some(&globals),
+                None,
+            )
+            .unwrap();
+        });
+    }
+}
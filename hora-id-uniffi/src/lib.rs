@@ -0,0 +1,195 @@
+//! Kotlin and Swift bindings for HoraID, built with UniFFI
+//!
+//! Mobile apps can create IDs offline in the same format as the backend, so a record can be
+//! written optimistically on the device and keep its ID when it is synced. Give the apps their
+//! own range of machine IDs, or use `HoraId.random()` if the machine ID needn't be known.
+//!
+//! Build the library for the device targets, then generate the bindings from it:
+//!
+//! ```sh
+//! cargo build --release -p hora-id-uniffi
+//! cargo run -p hora-id-uniffi --features cli --bin uniffi-bindgen -- generate \
+//!     --library target/release/libhora_id_uniffi.so --language kotlin --out-dir out
+//! ```
+//!
+//! ```kotlin
+//! val generator = HoraGenerator(200u)
+//! val id = generator.next()
+//! println("$id ${id.value()} ${id.machineId()}")
+//! check(HoraId.parse("00cd01daff010002") < HoraId.fromValue(57704410318438403u))
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+
+uniffi::setup_scaffolding!();
+
+/// Errors thrown by the bindings
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum HoraIdError {
+    /// The string isn't a valid hexadecimal HoraID
+    InvalidId(String),
+    /// The clock of the device is before the HoraID epoch or moved backwards
+    Clock(String),
+    /// The ID has a format version this library can't decode
+    UnsupportedVersion(String),
+}
+
+impl fmt::Display for HoraIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidId(msg) => write!(f, "invalid HoraId: {}", msg),
+            Self::Clock(msg) => write!(f, "clock error: {}", msg),
+            Self::UnsupportedVersion(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for HoraIdError {}
+
+impl From<hora_id::HoraIdParseError> for HoraIdError {
+    fn from(err: hora_id::HoraIdParseError) -> Self {
+        Self::InvalidId(err.to_string())
+    }
+}
+
+impl From<hora_id::HoraError> for HoraIdError {
+    fn from(err: hora_id::HoraError) -> Self {
+        match err {
+            hora_id::HoraError::UnsupportedVersion { .. } => {
+                Self::UnsupportedVersion(err.to_string())
+            }
+            err => Self::Clock(err.to_string()),
+        }
+    }
+}
+
+/// A time-sorted 64-bit ID
+///
+/// `toString()` in Kotlin and `description` in Swift return the 16 character hexadecimal form.
+/// IDs are comparable, and sort by creation time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, uniffi::Object)]
+#[uniffi::export(Debug, Display, Eq, Hash, Ord)]
+pub struct HoraId {
+    id: hora_id::HoraId,
+}
+
+#[uniffi::export]
+impl HoraId {
+    /// Parse the 16 character hexadecimal form
+    #[uniffi::constructor]
+    pub fn parse(hex: String) -> Result<Arc<Self>, HoraIdError> {
+        Ok(Arc::new(Self { id: hex.parse()? }))
+    }
+
+    /// Create an ID from the number returned by `value()`
+    #[uniffi::constructor]
+    pub fn from_value(value: u64) -> Arc<Self> {
+        Arc::new(hora_id::HoraId::from_bytes(value.to_be_bytes()).into())
+    }
+
+    /// A new ID with random machine and sequence bits, which needs no generator
+    #[uniffi::constructor]
+    pub fn random() -> Result<Arc<Self>, HoraIdError> {
+        Ok(Arc::new(hora_id::HoraId::rand()?.into()))
+    }
+
+    /// The ID as an unsigned 64-bit number, as stored by the backend
+    pub fn value(&self) -> u64 {
+        self.id.to_u64()
+    }
+
+    /// Format version of the ID, the other parts throw for versions this library can't decode
+    pub fn version(&self) -> u8 {
+        self.id.version()
+    }
+
+    /// Creation time in milliseconds since the Unix epoch
+    pub fn timestamp_millis(&self) -> Result<u64, HoraIdError> {
+        Ok(self.id.decode()?.timestamp_millis)
+    }
+
+    pub fn machine_id(&self) -> Result<u16, HoraIdError> {
+        Ok(self.id.decode()?.machine_id)
+    }
+
+    pub fn sequence(&self) -> Result<u32, HoraIdError> {
+        Ok(self.id.decode()?.sequence)
+    }
+}
+
+impl fmt::Display for HoraId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.id.fmt(f)
+    }
+}
+
+impl From<hora_id::HoraId> for HoraId {
+    fn from(id: hora_id::HoraId) -> Self {
+        Self { id }
+    }
+}
+
+/// Thread-safe ID generator
+///
+/// Create one generator for the app and share it, two generators with the same machine ID
+/// create duplicate IDs.
+#[derive(uniffi::Object)]
+pub struct HoraGenerator {
+    generator: hora_id::SyncHoraGenerator,
+}
+
+#[uniffi::export]
+impl HoraGenerator {
+    /// Create a generator for `machine_id`
+    #[uniffi::constructor]
+    pub fn new(machine_id: u8) -> Result<Arc<Self>, HoraIdError> {
+        let generator = hora_id::SyncHoraGenerator::new(machine_id)?;
+        Ok(Arc::new(Self { generator }))
+    }
+
+    /// Generate a new ID
+    pub fn next(&self) -> Result<Arc<HoraId>, HoraIdError> {
+        Ok(Arc::new(self.generator.try_next()?.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn id() {
+        let id = HoraId::parse("00cd01daff010002".to_string()).unwrap();
+        assert_eq!(id.value(), 57704410318438402);
+        assert_eq!(id.to_string(), "00cd01daff010002");
+        assert_eq!(id.timestamp_millis(), Ok(1749124954997));
+        assert_eq!((id.machine_id(), id.sequence()), (Ok(1), Ok(2)));
+        assert_eq!(id.version(), 0);
+        assert!(id < HoraId::from_value(57704410318438403));
+        assert!(matches!(
+            HoraId::parse("xyz".to_string()),
+            Err(HoraIdError::InvalidId(_))
+        ));
+
+        let future = HoraId::parse("40cd01daff010002".to_string()).unwrap();
+        assert_eq!(future.version(), 1);
+        assert_eq!(
+            future.machine_id(),
+            Err(HoraIdError::UnsupportedVersion(
+                "unsupported HoraId format version 1".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn generator() {
+        let generator = HoraGenerator::new(200).unwrap();
+        let first = generator.next().unwrap();
+        let second = generator.next().unwrap();
+        assert!(first < second);
+        assert_eq!(first.machine_id(), Ok(200));
+        assert!(HoraId::random().is_ok());
+    }
+}
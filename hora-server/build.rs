@@ -0,0 +1,6 @@
+fn main() -> std::io::Result<()> {
+    // use the bundled protoc so building doesn't need a system install
+    let protoc = protoc_bin_vendored::protoc_bin_path().map_err(std::io::Error::other)?;
+    std::env::set_var("PROTOC", protoc);
+    tonic_prost_build::compile_protos("proto/hora/v1/hora.proto")
+}
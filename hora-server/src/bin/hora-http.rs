@@ -0,0 +1,40 @@
+//! `hora-http` binary, serving the JSON API of [hora_server::http]
+//!
+//! ```sh
+//! HORA_MACHINE_ID=1 hora-http --addr 0.0.0.0:8080
+//! curl localhost:8080/ids?count=10
+//! ```
+
+use clap::Parser;
+use hora_server::{http, GeneratorArgs};
+use std::error::Error;
+use std::net::SocketAddr;
+use tokio::net::TcpListener;
+
+/// HTTP service that generates time-sorted HoraIDs
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Args {
+    #[command(flatten)]
+    generator: GeneratorArgs,
+    /// Address to listen on
+    #[arg(long, env = "HORA_HTTP_ADDR", default_value = "0.0.0.0:8080")]
+    addr: SocketAddr,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let router = http::router(args.generator.generator()?);
+    let listener = TcpListener::bind(args.addr).await?;
+    eprintln!(
+        "hora-http listening on {} with machine ID {}",
+        args.addr, args.generator.machine_id
+    );
+    axum::serve(listener, router)
+        .with_graceful_shutdown(async {
+            tokio::signal::ctrl_c().await.ok();
+        })
+        .await?;
+    Ok(())
+}
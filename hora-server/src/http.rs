@@ -0,0 +1,314 @@
+//! JSON over HTTP API, served by the `hora-http` binary
+//!
+//! - `GET /id` returns one new ID
+//! - `GET /ids?count=n` returns `n` new IDs, from 1 to [MAX_BATCH]
+//! - `GET /inspect/{id}` splits a hexadecimal ID into its parts, reading its timestamp with the
+//!   epoch of the generator
+//! - `GET /segment?size=n` reserves a block of up to `n` consecutive IDs for a
+//!   `hora_id::SegmentGenerator`, which can fetch it with `hora_id::HttpSegmentAllocator`
+//!
+//! IDs are returned in both forms, with the number as a decimal string since JavaScript can't
+//! parse 64-bit numbers exactly: `{"id": "00cd01daff010002", "value": "57704410318438402"}`.
+//! Errors are returned as `{"error": "..."}`.
+
+use crate::MAX_BATCH;
+use axum::extract::rejection::QueryRejection;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, SecondsFormat};
+use hora_id::{HoraId, SegmentAllocator, SyncHoraGenerator};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// An ID as returned by the API
+#[derive(Debug, Clone, Serialize)]
+pub struct IdJson {
+    /// The 16 character hexadecimal form
+    pub id: HoraId,
+    /// The ID as a decimal string
+    #[serde(with = "hora_id::serde_decimal")]
+    pub value: HoraId,
+}
+
+impl From<HoraId> for IdJson {
+    fn from(id: HoraId) -> Self {
+        Self { id, value: id }
+    }
+}
+
+/// Response of `GET /ids`
+#[derive(Debug, Clone, Serialize)]
+pub struct IdsJson {
+    pub ids: Vec<IdJson>,
+}
+
+/// Response of `GET /inspect/{id}`
+#[derive(Debug, Clone, Serialize)]
+pub struct InspectJson {
+    #[serde(flatten)]
+    pub id: IdJson,
+    /// Format version the ID was decoded with
+    pub version: u8,
+    /// Creation time as an ISO 8601 string in UTC
+    pub timestamp: String,
+    /// Creation time in milliseconds since the Unix epoch
+    pub timestamp_millis: u64,
+    pub machine_id: u16,
+    pub sequence: u32,
+}
+
+impl InspectJson {
+    /// Decode an ID generated with the custom epoch `epoch_millis` according to its format
+    /// version
+    pub fn with_epoch(id: HoraId, epoch_millis: u64) -> Result<Self, hora_id::HoraError> {
+        let parts = id.decode_with_epoch(epoch_millis)?;
+        let timestamp = DateTime::from_timestamp_millis(parts.timestamp_millis as i64)
+            .expect("HoraId timestamps are within the range of chrono")
+            .to_rfc3339_opts(SecondsFormat::Millis, true);
+        Ok(Self {
+            id: id.into(),
+            version: parts.version,
+            timestamp,
+            timestamp_millis: parts.timestamp_millis,
+            machine_id: parts.machine_id,
+            sequence: parts.sequence,
+        })
+    }
+}
+
+impl TryFrom<HoraId> for InspectJson {
+    type Error = hora_id::HoraError;
+
+    /// Decode the ID according to its format version
+    fn try_from(id: HoraId) -> Result<Self, Self::Error> {
+        Self::with_epoch(id, hora_id::EPOCH)
+    }
+}
+
+/// Response of `GET /segment`
+#[derive(Debug, Clone, Serialize)]
+pub struct SegmentJson {
+    /// First ID of the block
+    pub start: HoraId,
+    /// Last ID of the block
+    pub end: HoraId,
+    /// Number of IDs in the block, which can be less than requested
+    pub size: u64,
+}
+
+/// Error response with a `{"error": "..."}` body
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, message: impl ToString) -> Self {
+        Self {
+            status,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl From<hora_id::HoraError> for ApiError {
+    fn from(err: hora_id::HoraError) -> Self {
+        Self::new(StatusCode::SERVICE_UNAVAILABLE, err)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        #[derive(Serialize)]
+        struct Body {
+            error: String,
+        }
+        let body = Body {
+            error: self.message,
+        };
+        (self.status, Json(body)).into_response()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CountQuery {
+    count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct SizeQuery {
+    size: u32,
+}
+
+fn bad_query(err: QueryRejection) -> ApiError {
+    ApiError::new(StatusCode::BAD_REQUEST, err.body_text())
+}
+
+type Generator = Arc<SyncHoraGenerator>;
+
+/// Router serving the API with IDs from `generator`
+pub fn router(generator: impl Into<Generator>) -> Router {
+    Router::new()
+        .route("/id", get(get_id))
+        .route("/ids", get(get_ids))
+        .route("/inspect/{id}", get(inspect))
+        .route("/segment", get(segment))
+        .with_state(generator.into())
+}
+
+async fn get_id(State(generator): State<Generator>) -> Result<Json<IdJson>, ApiError> {
+    Ok(Json(generator.try_next()?.into()))
+}
+
+async fn get_ids(
+    State(generator): State<Generator>,
+    query: Result<Query<CountQuery>, QueryRejection>,
+) -> Result<Json<IdsJson>, ApiError> {
+    let Query(CountQuery { count }) = query.map_err(bad_query)?;
+    if !(1..=MAX_BATCH).contains(&count) {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            format!("count must be from 1 to {}, got {}", MAX_BATCH, count),
+        ));
+    }
+    let ids = (0..count)
+        .map(|_| generator.try_next().map(IdJson::from))
+        .collect::<Result<_, _>>()?;
+    Ok(Json(IdsJson { ids }))
+}
+
+async fn inspect(
+    State(generator): State<Generator>,
+    Path(id): Path<String>,
+) -> Result<Json<InspectJson>, ApiError> {
+    let id: HoraId = id.parse().map_err(|err| {
+        ApiError::new(StatusCode::BAD_REQUEST, format!("invalid HoraId: {}", err))
+    })?;
+    let json = InspectJson::with_epoch(id, generator.epoch())
+        .map_err(|err| ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, err))?;
+    Ok(Json(json))
+}
+
+async fn segment(
+    State(generator): State<Generator>,
+    query: Result<Query<SizeQuery>, QueryRejection>,
+) -> Result<Json<SegmentJson>, ApiError> {
+    let Query(SizeQuery { size }) = query.map_err(bad_query)?;
+    if size == 0 {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "size must be at least 1",
+        ));
+    }
+    let block = generator.allocate(size)?;
+    Ok(Json(SegmentJson {
+        start: block.start(),
+        end: block.end(),
+        size: block.end().to_u64() - block.start().to_u64() + 1,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    async fn call(uri: &str) -> (StatusCode, Value) {
+        call_with(SyncHoraGenerator::new(4).unwrap(), uri).await
+    }
+
+    async fn call_with(generator: SyncHoraGenerator, uri: &str) -> (StatusCode, Value) {
+        let request = Request::get(uri).body(Body::empty()).unwrap();
+        let response = router(generator).oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn id() {
+        let (status, json) = call("/id").await;
+        assert_eq!(status, StatusCode::OK);
+        let id: HoraId = json["id"].as_str().unwrap().parse().unwrap();
+        assert_eq!(json["value"], id.to_u64().to_string());
+        assert_eq!(id.machine_id(), 4);
+    }
+
+    #[tokio::test]
+    async fn ids() {
+        let (status, json) = call("/ids?count=3").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["ids"].as_array().unwrap().len(), 3);
+
+        for uri in ["/ids", "/ids?count=0", "/ids?count=10001", "/ids?count=x"] {
+            let (status, json) = call(uri).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+            assert!(json["error"].is_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn inspect() {
+        let (status, json) = call("/inspect/00cd01daff010002").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "id": "00cd01daff010002",
+                "value": "57704410318438402",
+                "version": 0,
+                "timestamp": "2025-06-05T12:02:34.997Z",
+                "timestamp_millis": 1749124954997u64,
+                "machine_id": 1,
+                "sequence": 2,
+            })
+        );
+
+        let (status, json) = call("/inspect/xyz").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            json["error"],
+            "invalid HoraId: invalid length: expected 16, got 3"
+        );
+
+        let (status, json) = call("/inspect/40cd01daff010002").await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(json["error"], "unsupported HoraId format version 1");
+    }
+
+    #[tokio::test]
+    async fn inspect_with_epoch() {
+        let epoch = hora_id::EPOCH + 86_400_000;
+        let mut generator = hora_id::HoraGenerator::new(4)
+            .unwrap()
+            .with_epoch(epoch)
+            .unwrap();
+        let id = generator.next();
+        let (status, json) = call_with(generator.into(), &format!("/inspect/{}", id)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            json["timestamp_millis"],
+            id.timestamp_millis_with_epoch(epoch)
+        );
+    }
+
+    #[tokio::test]
+    async fn segment() {
+        let (status, json) = call("/segment?size=100").await;
+        assert_eq!(status, StatusCode::OK);
+        let start: HoraId = json["start"].as_str().unwrap().parse().unwrap();
+        let end: HoraId = json["end"].as_str().unwrap().parse().unwrap();
+        assert_eq!(json["size"], 100);
+        assert_eq!(end.to_u64() - start.to_u64(), 99);
+
+        let (status, _) = call("/segment?size=0").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
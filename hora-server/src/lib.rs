@@ -0,0 +1,148 @@
+//! gRPC and HTTP services that generate HoraIDs
+//!
+//! The gRPC protocol is defined in `proto/hora/v1/hora.proto`, so clients in any language can be
+//! generated from it. The JSON API is described in [http]. Every ID is returned as a number and
+//! as its hexadecimal string.
+
+use hora_id::{HoraError, HoraGenerator, SyncHoraGenerator};
+use proto::hora_id_service_server;
+use proto::{GetIdBatchRequest, GetIdBatchResponse, GetIdRequest, GetIdResponse};
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+
+pub mod http;
+
+/// Messages, client and server generated from `hora.proto`
+pub mod proto {
+    tonic::include_proto!("hora.v1");
+}
+
+/// Most IDs returned by one `GetIdBatch` call
+pub const MAX_BATCH: u32 = 10_000;
+
+/// Options that configure the generator of a server
+#[derive(Debug, Clone, clap::Args)]
+pub struct GeneratorArgs {
+    /// Machine ID of this server, unique among all generators of the system
+    #[arg(long, env = "HORA_MACHINE_ID")]
+    pub machine_id: u8,
+    /// Custom epoch in milliseconds since the Unix epoch
+    #[arg(long, env = "HORA_EPOCH")]
+    pub epoch: Option<u64>,
+}
+
+impl GeneratorArgs {
+    /// Create the generator
+    ///
+    /// ## Fail condition
+    /// If the system time is before the epoch
+    pub fn generator(&self) -> Result<SyncHoraGenerator, HoraError> {
+        let generator = HoraGenerator::new(self.machine_id)?;
+        let generator = match self.epoch {
+            Some(epoch_millis) => generator.with_epoch(epoch_millis)?,
+            None => generator,
+        };
+        Ok(generator.into())
+    }
+}
+
+impl From<hora_id::HoraId> for proto::HoraId {
+    fn from(id: hora_id::HoraId) -> Self {
+        Self {
+            value: id.to_u64(),
+            hex: id.to_string(),
+        }
+    }
+}
+
+/// Implementation of the `HoraIdService` RPCs
+pub struct HoraService {
+    generator: Arc<SyncHoraGenerator>,
+}
+
+impl HoraService {
+    pub fn new(generator: impl Into<Arc<SyncHoraGenerator>>) -> Self {
+        Self {
+            generator: generator.into(),
+        }
+    }
+
+    /// The service wrapped for `tonic::transport::Server::add_service`
+    pub fn into_server(self) -> hora_id_service_server::HoraIdServiceServer<Self> {
+        hora_id_service_server::HoraIdServiceServer::new(self)
+    }
+
+    fn next(&self) -> Result<proto::HoraId, Status> {
+        match self.generator.try_next() {
+            Ok(id) => Ok(id.into()),
+            Err(err) => Err(Status::unavailable(err.to_string())),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl hora_id_service_server::HoraIdService for HoraService {
+    async fn get_id(&self, _: Request<GetIdRequest>) -> Result<Response<GetIdResponse>, Status> {
+        let id = self.next()?;
+        Ok(Response::new(GetIdResponse { id: Some(id) }))
+    }
+
+    async fn get_id_batch(
+        &self,
+        request: Request<GetIdBatchRequest>,
+    ) -> Result<Response<GetIdBatchResponse>, Status> {
+        let count = request.into_inner().count;
+        if !(1..=MAX_BATCH).contains(&count) {
+            return Err(Status::invalid_argument(format!(
+                "count must be from 1 to {}, got {}",
+                MAX_BATCH, count
+            )));
+        }
+        let ids = (0..count).map(|_| self.next()).collect::<Result<_, _>>()?;
+        Ok(Response::new(GetIdBatchResponse { ids }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hora_id_service_server::HoraIdService;
+    use super::*;
+    use tonic::Code;
+
+    fn service() -> HoraService {
+        let args = GeneratorArgs {
+            machine_id: 3,
+            epoch: None,
+        };
+        HoraService::new(args.generator().unwrap())
+    }
+
+    #[tokio::test]
+    async fn get_id() {
+        let response = service().get_id(Request::new(GetIdRequest {})).await;
+        let id = response.unwrap().into_inner().id.unwrap();
+        let parsed: hora_id::HoraId = id.hex.parse().unwrap();
+        assert_eq!(parsed.to_u64(), id.value);
+        assert_eq!(parsed.machine_id(), 3);
+    }
+
+    #[tokio::test]
+    async fn get_id_batch() {
+        let service = service();
+        let request = Request::new(GetIdBatchRequest { count: 500 });
+        let ids = service
+            .get_id_batch(request)
+            .await
+            .unwrap()
+            .into_inner()
+            .ids;
+        assert_eq!(ids.len(), 500);
+        assert!(ids.windows(2).all(|pair| pair[0].value < pair[1].value));
+
+        for count in [0, MAX_BATCH + 1] {
+            let request = Request::new(GetIdBatchRequest { count });
+            let status = service.get_id_batch(request).await.unwrap_err();
+            assert_eq!(status.code(), Code::InvalidArgument);
+        }
+    }
+}
@@ -0,0 +1,41 @@
+//! `hora-server` binary, serving `HoraIdService` over gRPC
+//!
+//! ```sh
+//! HORA_MACHINE_ID=1 hora-server --addr 0.0.0.0:50051
+//! grpcurl -plaintext -import-path proto -proto hora/v1/hora.proto \
+//!     localhost:50051 hora.v1.HoraIdService/GetId
+//! ```
+
+use clap::Parser;
+use hora_server::{GeneratorArgs, HoraService};
+use std::error::Error;
+use std::net::SocketAddr;
+use tonic::transport::Server;
+
+/// gRPC service that generates time-sorted HoraIDs
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Args {
+    #[command(flatten)]
+    generator: GeneratorArgs,
+    /// Address to listen on
+    #[arg(long, env = "HORA_ADDR", default_value = "0.0.0.0:50051")]
+    addr: SocketAddr,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let service = HoraService::new(args.generator.generator()?);
+    eprintln!(
+        "hora-server listening on {} with machine ID {}",
+        args.addr, args.generator.machine_id
+    );
+    Server::builder()
+        .add_service(service.into_server())
+        .serve_with_shutdown(args.addr, async {
+            tokio::signal::ctrl_c().await.ok();
+        })
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,93 @@
+#ifndef HORA_ID_H
+#define HORA_ID_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/* Length of the hexadecimal form, without the terminating NUL */
+#define HORA_HEX_LEN 16
+
+/**
+ * Result of the fallible FFI functions
+ */
+typedef enum HoraStatus {
+  /**
+   * The result was written to the out pointer
+   */
+  HORA_STATUS_OK = 0,
+  /**
+   * A pointer argument was NULL
+   */
+  HORA_STATUS_NULL_POINTER = 1,
+  /**
+   * The string isn't a valid hexadecimal HoraID
+   */
+  HORA_STATUS_INVALID_ID = 2,
+  /**
+   * The clock is before the HoraID epoch or moved backwards too far
+   */
+  HORA_STATUS_CLOCK_ERROR = 3,
+} HoraStatus;
+
+/**
+ * Thread-safe generator, shared between threads through the same pointer
+ */
+typedef struct HoraGeneratorHandle HoraGeneratorHandle;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Create a generator for `machine_id`, returns NULL if the system clock is before the HoraID
+ * epoch
+ *
+ * Release it with [hora_generator_free].
+ */
+struct HoraGeneratorHandle *hora_generator_new(uint8_t machine_id);
+
+/**
+ * Release a generator created by [hora_generator_new], NULL is ignored
+ *
+ * # Safety
+ * `generator` must be NULL or returned by [hora_generator_new] and not freed before, and no
+ * other thread may use it anymore.
+ */
+void hora_generator_free(struct HoraGeneratorHandle *generator);
+
+/**
+ * Generate a new ID into `out`
+ *
+ * # Safety
+ * `generator` must be NULL or a live generator from [hora_generator_new], and `out` must be
+ * NULL or valid for writing a `uint64_t`.
+ */
+enum HoraStatus hora_next(const struct HoraGeneratorHandle *generator, uint64_t *out);
+
+/**
+ * Write the 16 character hexadecimal form of `id` and a terminating NUL into `out`
+ *
+ * # Safety
+ * `out` must be NULL or valid for writing `HORA_HEX_LEN + 1` bytes.
+ */
+enum HoraStatus hora_to_hex(uint64_t id, char *out);
+
+/**
+ * Parse the NUL terminated hexadecimal form in `hex` into `out`
+ *
+ * # Safety
+ * `hex` must be NULL or a NUL terminated string, and `out` must be NULL or valid for writing
+ * a `uint64_t`.
+ */
+enum HoraStatus hora_parse(const char *hex, uint64_t *out);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* HORA_ID_H */
@@ -0,0 +1,110 @@
+//! [actix_web] support for [HoraId]
+//!
+//! The feature enables `serde`, so `web::Path<HoraId>` and `web::Query` structs with [HoraId]
+//! fields work out of the box. actix answers `web::Query` errors with 400 Bad Request but
+//! `web::Path` errors with 404 Not Found. For routes with a single path parameter [HoraId] is
+//! also an extractor by itself, which answers invalid IDs with 400 Bad Request.
+
+use crate::{HoraId, HoraIdParseError};
+use actix_web::dev::Payload;
+use actix_web::error::ErrorInternalServerError;
+use actix_web::http::StatusCode;
+use actix_web::{FromRequest, HttpRequest, HttpResponse, ResponseError};
+use core::future::{ready, Ready};
+
+/// Answers with 400 Bad Request, so `?` on a parse result in a handler rejects the request
+impl ResponseError for HoraIdParseError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().body(format!("Invalid HoraId: {}", self))
+    }
+}
+
+/// Extracts the only path parameter of the route
+///
+/// ```
+/// use actix_web::{web, App};
+/// use hora_id::HoraId;
+///
+/// async fn get_user(id: HoraId) -> String {
+///     format!("user {}", id)
+/// }
+///
+/// let app = App::new().route("/users/{id}", web::get().to(get_user));
+/// ```
+impl FromRequest for HoraId {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let mut params = req.match_info().iter();
+        let result = match (params.next(), params.next()) {
+            (Some((_, id)), None) => id.parse().map_err(actix_web::Error::from),
+            _ => Err(ErrorInternalServerError(
+                "Extracting a HoraId needs a route with exactly one path parameter",
+            )),
+        };
+        ready(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::{call_and_read_body, call_service, init_service, TestRequest};
+    use actix_web::{rt, web, App};
+
+    #[derive(serde::Deserialize)]
+    struct Params {
+        id: HoraId,
+    }
+
+    #[test]
+    fn extract() {
+        rt::System::new().block_on(async {
+            let app = init_service(
+                App::new()
+                    .route(
+                        "/users/{id}",
+                        web::get().to(|id: HoraId| async move { id.to_string() }),
+                    )
+                    .route(
+                        "/orders",
+                        web::get()
+                            .to(|query: web::Query<Params>| async move { query.id.to_string() }),
+                    )
+                    .route(
+                        "/items/{shop}/{id}",
+                        web::get().to(|id: HoraId| async move { id.to_string() }),
+                    ),
+            )
+            .await;
+
+            for uri in ["/users/00cd01daff010002", "/orders?id=00cd01daff010002"] {
+                let body = call_and_read_body(&app, TestRequest::get().uri(uri).to_request()).await;
+                assert_eq!(body, "00cd01daff010002");
+            }
+
+            let request = TestRequest::get().uri("/users/123").to_request();
+            let response = call_service(&app, request).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            let body = actix_web::body::to_bytes(response.into_body())
+                .await
+                .unwrap();
+            assert_eq!(body, "Invalid HoraId: invalid length: expected 16, got 3");
+
+            let request = TestRequest::get().uri("/orders?id=123").to_request();
+            let response = call_service(&app, request).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+            let request = TestRequest::get()
+                .uri("/items/1/00cd01daff010002")
+                .to_request();
+            let response = call_service(&app, request).await;
+            assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        });
+    }
+}
@@ -0,0 +1,79 @@
+//! [arbitrary] support for [HoraId], [HoraId128] and [HoraIdRange]
+
+use crate::{HoraId, HoraId128, HoraIdRange};
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+/// Any 64-bit pattern, including format versions that [HoraId::decode] rejects
+impl<'a> Arbitrary<'a> for HoraId {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(HoraId::from_bytes(u.arbitrary()?))
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (8, Some(8))
+    }
+}
+
+/// Any 128-bit pattern
+impl<'a> Arbitrary<'a> for HoraId128 {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(HoraId128::from(u.arbitrary::<[u8; 16]>()?))
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (16, Some(16))
+    }
+}
+
+impl HoraIdRange {
+    /// Generate an arbitrary [HoraId] within the range, for example to fuzz with IDs from a
+    /// realistic time window
+    ///
+    /// ```
+    /// use arbitrary::Unstructured;
+    /// use hora_id::HoraIdRange;
+    ///
+    /// // 2025-03-01 to 2025-03-31
+    /// let march = HoraIdRange::from_millis(1740787200000, 1743465599999).unwrap();
+    /// let mut u = Unstructured::new(&[0x42; 8]);
+    /// let id = march.arbitrary_id(&mut u).unwrap();
+    /// assert!(march.contains(&id));
+    /// ```
+    ///
+    /// ## Fail condition
+    /// If the range is empty
+    pub fn arbitrary_id(&self, u: &mut Unstructured<'_>) -> Result<HoraId> {
+        if self.is_empty() {
+            return Err(arbitrary::Error::IncorrectFormat);
+        }
+        let num = u.int_in_range(self.to_u64_range())?;
+        Ok(HoraId::from_bytes(num.to_be_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arbitrary() {
+        let data = [0x00, 0xcd, 0x01, 0xda, 0xff, 0x01, 0x00, 0x02];
+        let id = HoraId::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        assert_eq!(id.to_u64(), 57704410318438402);
+        let id = HoraId128::arbitrary(&mut Unstructured::new(&[0xff; 16])).unwrap();
+        assert_eq!(id.to_u128(), u128::MAX);
+    }
+
+    #[test]
+    fn in_range() {
+        let range = HoraIdRange::from_millis(1740787200000, 1743465599999).unwrap();
+        for byte in [0, 1, 0x80, 0xff] {
+            let id = range
+                .arbitrary_id(&mut Unstructured::new(&[byte; 8]))
+                .unwrap();
+            assert!(range.contains(&id));
+        }
+        let empty = HoraIdRange::new(HoraId::MAX, HoraId::NIL);
+        assert!(empty.arbitrary_id(&mut Unstructured::new(&[0; 8])).is_err());
+    }
+}
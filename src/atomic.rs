@@ -0,0 +1,272 @@
+//! Lock-free generator with its state in one atomic integer
+
+use crate::{
+    ticks_since, Clock, HoraError, HoraGenerator, HoraId, HoraLayout, Spin, SystemClock,
+    WaitStrategy,
+};
+#[cfg(not(hora_loom))]
+use core::sync::atomic::{AtomicU64, Ordering};
+#[cfg(hora_loom)]
+use loom::sync::atomic::{AtomicU64, Ordering};
+
+/// Thread-safe ID generator that needs no lock
+///
+/// The last tick and sequence number are packed into one [AtomicU64] and advanced with
+/// compare-and-swap, so threads never block each other and a thread that is descheduled can't
+/// hold up the rest. It also works where no mutex is available, such as `no_std` targets with
+/// 64-bit atomics. All threads still update the same counter, the `contention` benchmark
+/// compares it with [SyncHoraGenerator](crate::SyncHoraGenerator) and
+/// [HoraGeneratorPool](crate::HoraGeneratorPool) spreads the load over several counters.
+///
+/// When the clock moves backwards, the generator keeps counting in the last tick as with
+/// [RollbackPolicy::BorrowSequence](crate::RollbackPolicy::BorrowSequence).
+///
+/// ## Usage
+/// ```no_run
+/// use hora_id::AtomicHoraGenerator;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let generator = Arc::new(AtomicHoraGenerator::new(1).unwrap());
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| {
+///         let generator = Arc::clone(&generator);
+///         thread::spawn(move || generator.next())
+///     })
+///     .collect();
+/// for handle in handles {
+///     println!("{}", handle.join().unwrap());
+/// }
+/// ```
+pub struct AtomicHoraGenerator<C = SystemClock, W = Spin> {
+    machine_id: u16,
+    layout: HoraLayout,
+    tenant: u16,
+    epoch: u64,
+    /// Last generated tick above the last sequence number, as in the ID without machine ID
+    state: AtomicU64,
+    clock: C,
+    wait_strategy: W,
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl AtomicHoraGenerator {
+    /// Create a generator for `machine_id`, never invalid, or [HoraError::ClockBeforeEpoch]
+    pub fn new(machine_id: u8) -> Result<Self, HoraError> {
+        Ok(Self::from(HoraGenerator::new(machine_id)?))
+    }
+}
+
+impl<C: Clock, W: WaitStrategy> AtomicHoraGenerator<C, W> {
+    /// Generate a new [HoraId]
+    ///
+    /// ## Panics
+    /// If the system time is incorrect. Use [AtomicHoraGenerator::try_next] to handle this case.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&self) -> HoraId {
+        self.try_next().expect("failed to generate HoraId")
+    }
+
+    /// Generate a new [HoraId], returning an error instead of panicking
+    ///
+    /// ## Fail condition
+    /// - If the clock is before the epoch or past the last timestamp of the layout
+    /// - If all sequence numbers of the tick are used and the [WaitStrategy] gives up
+    ///   ([HoraError::SequenceExhausted])
+    pub fn try_next(&self) -> Result<HoraId, HoraError> {
+        let sequence_bits = self.layout.sequence_bits();
+        let max_sequence = self.layout.max_sequence() as u64;
+        let mut state = self.state.load(Ordering::Relaxed);
+        loop {
+            let ticks = self.current_ticks()?;
+            let next = if ticks > state >> sequence_bits {
+                ticks << sequence_bits
+            } else if state & max_sequence < max_sequence {
+                // same tick, or the clock is behind and the last tick is borrowed
+                state + 1
+            } else {
+                if self.wait_strategy.wait().is_break() {
+                    return Err(HoraError::SequenceExhausted);
+                }
+                state = self.state.load(Ordering::Relaxed);
+                continue;
+            };
+            match self.state.compare_exchange_weak(
+                state,
+                next,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    let sequence = (next & max_sequence) as u32;
+                    let ticks = next >> sequence_bits;
+                    return Ok(self
+                        .layout
+                        .compose(ticks, self.machine_id, self.tenant, sequence));
+                }
+                Err(current) => state = current,
+            }
+        }
+    }
+
+    fn current_ticks(&self) -> Result<u64, HoraError> {
+        let ticks = ticks_since(self.clock.now_millis(), self.epoch)?;
+        if ticks > self.layout.max_ticks() {
+            return Err(HoraError::TimestampOutOfRange);
+        }
+        Ok(ticks)
+    }
+}
+
+/// Continue where `generator` stopped
+///
+/// The layout, machine ID, tenant, epoch, clock and wait strategy carry over. The rollback
+/// policy, [HoraGenerator::monotonic] and [HoraGenerator::random_sequence] don't apply to the
+/// atomic generator.
+impl<C, W> From<HoraGenerator<C, W>> for AtomicHoraGenerator<C, W> {
+    fn from(generator: HoraGenerator<C, W>) -> Self {
+        let state =
+            generator.last_gen << generator.layout.sequence_bits() | generator.sequence as u64;
+        Self {
+            machine_id: generator.machine_id,
+            layout: generator.layout,
+            tenant: generator.tenant,
+            epoch: generator.epoch,
+            state: AtomicU64::new(state),
+            clock: generator.clock,
+            wait_strategy: generator.wait_strategy,
+        }
+    }
+}
+
+impl<C: Clock> AtomicHoraGenerator<C> {
+    /// Create a generator with a custom [HoraLayout] that reads the time from `clock`
+    ///
+    /// See [HoraGenerator::with_layout_and_clock].
+    pub fn with_layout_and_clock(
+        layout: HoraLayout,
+        machine_id: u16,
+        clock: C,
+    ) -> Result<Self, HoraError> {
+        HoraGenerator::with_layout_and_clock(layout, machine_id, clock).map(Self::from)
+    }
+}
+
+#[cfg(all(test, not(hora_loom)))]
+mod tests {
+    use super::*;
+    use crate::{Fail, MockClock, EPOCH};
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<AtomicHoraGenerator>();
+    }
+
+    #[test]
+    fn sequence() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let generator =
+            AtomicHoraGenerator::with_layout_and_clock(HoraLayout::DEFAULT, 3, clock.clone())
+                .unwrap();
+        let first = generator.next();
+        let second = generator.next();
+        assert_eq!((first.sequence(), second.sequence()), (1, 2));
+        assert_eq!(first.machine_id(), 3);
+        assert_eq!(first.timestamp_millis(), EPOCH + 10_000);
+
+        clock.advance(10);
+        let third = generator.next();
+        assert_eq!(third.sequence(), 0);
+        // the clock moved backwards, the last tick continues
+        clock.rewind(20);
+        let fourth = generator.next();
+        assert_eq!(fourth.timestamp_millis(), third.timestamp_millis());
+        assert_eq!(fourth.sequence(), 1);
+    }
+
+    #[test]
+    fn exhausted() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let layout = HoraLayout::new(52, 8, 4);
+        let generator = HoraGenerator::with_layout_and_clock(layout, 1, clock.clone())
+            .unwrap()
+            .with_wait_strategy(Fail);
+        let generator = AtomicHoraGenerator::from(generator);
+        for _ in 0..15 {
+            generator.next();
+        }
+        assert_eq!(generator.try_next(), Err(HoraError::SequenceExhausted));
+        clock.advance(4);
+        assert_eq!(layout.sequence(&generator.next()), 0);
+    }
+
+    #[test]
+    fn keeps_tenant() {
+        let generator = HoraGenerator::with_layout(HoraLayout::TENANT, 1)
+            .unwrap()
+            .for_tenant(42)
+            .unwrap();
+        assert_eq!(AtomicHoraGenerator::from(generator).next().tenant(), 42);
+    }
+
+    #[test]
+    fn shared_across_threads() {
+        let generator = Arc::new(AtomicHoraGenerator::new(1).unwrap());
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let generator = Arc::clone(&generator);
+                thread::spawn(move || (0..1000).map(|_| generator.next()).collect::<Vec<_>>())
+            })
+            .collect();
+
+        let mut unique = HashSet::new();
+        for handle in handles {
+            let ids = handle.join().unwrap();
+            assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+            for id in ids {
+                assert!(unique.insert(id));
+            }
+        }
+        assert_eq!(unique.len(), 8000);
+    }
+}
+
+/// Model checks of every thread interleaving, run with
+/// `RUSTFLAGS="--cfg hora_loom" cargo test --release --lib loom_tests`
+#[cfg(all(test, hora_loom))]
+mod loom_tests {
+    use super::*;
+    use crate::{MockClock, EPOCH};
+    use loom::sync::Arc;
+    use loom::thread;
+
+    #[test]
+    fn no_duplicates() {
+        loom::model(|| {
+            let clock = MockClock::new(EPOCH + 1000);
+            let generator = Arc::new(AtomicHoraGenerator::from(
+                HoraGenerator::new_with_clock(1, clock).unwrap(),
+            ));
+            let other = {
+                let generator = Arc::clone(&generator);
+                thread::spawn(move || [generator.next(), generator.next()])
+            };
+
+            let first = generator.next();
+            let second = generator.next();
+            assert!(first < second);
+            let [third, fourth] = other.join().unwrap();
+            assert!(third < fourth);
+            let ids = [first, second, third, fourth];
+            for (index, id) in ids.iter().enumerate() {
+                assert!(!ids[index + 1..].contains(id), "duplicate ID {}", id);
+            }
+        });
+    }
+}
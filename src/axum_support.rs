@@ -0,0 +1,126 @@
+//! [axum] support for [HoraId]
+//!
+//! The feature enables `serde`, so `Path<HoraId>` and `Query` structs with [HoraId] fields work
+//! out of the box and axum answers invalid IDs with 400 Bad Request. For routes with a single
+//! path parameter [HoraId] is also an extractor by itself, which rejects with a
+//! [HoraIdRejection].
+
+use crate::{HoraId, HoraIdParseError};
+use axum::extract::rejection::PathRejection;
+use axum::extract::{FromRequestParts, Path};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use core::fmt;
+
+/// Rejection of the [HoraId] extractor
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use hora_id::HoraId;
+///
+/// async fn get_user(id: HoraId) -> String {
+///     format!("user {}", id)
+/// }
+///
+/// let app: Router = Router::new().route("/users/{id}", get(get_user));
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum HoraIdRejection {
+    /// The path parameter is not a valid [HoraId], answered with 400 Bad Request
+    InvalidId(HoraIdParseError),
+    /// The path parameter couldn't be read, for example because the route has none or several
+    Path(PathRejection),
+}
+
+impl fmt::Display for HoraIdRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HoraIdRejection::InvalidId(err) => write!(f, "Invalid HoraId in path: {}", err),
+            HoraIdRejection::Path(rejection) => write!(f, "{}", rejection.body_text()),
+        }
+    }
+}
+
+impl std::error::Error for HoraIdRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HoraIdRejection::InvalidId(err) => Some(err),
+            HoraIdRejection::Path(rejection) => Some(rejection),
+        }
+    }
+}
+
+impl IntoResponse for HoraIdRejection {
+    fn into_response(self) -> Response {
+        match self {
+            HoraIdRejection::InvalidId(_) => {
+                (StatusCode::BAD_REQUEST, self.to_string()).into_response()
+            }
+            HoraIdRejection::Path(rejection) => rejection.into_response(),
+        }
+    }
+}
+
+/// Extracts the only path parameter of the route
+impl<S: Send + Sync> FromRequestParts<S> for HoraId {
+    type Rejection = HoraIdRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(id) = Path::<String>::from_request_parts(parts, state)
+            .await
+            .map_err(HoraIdRejection::Path)?;
+        id.parse().map_err(HoraIdRejection::InvalidId)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use axum::routing::get;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    async fn call(app: &Router, uri: &str) -> (StatusCode, String) {
+        let request = Request::get(uri).body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), 1024).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn extract() {
+        let app = Router::new()
+            .route(
+                "/users/{id}",
+                get(|id: HoraId| async move { id.to_string() }),
+            )
+            .route(
+                "/orders/{id}",
+                get(|Path(id): Path<HoraId>| async move { id.to_string() }),
+            )
+            .route(
+                "/items/{shop}/{id}",
+                get(|id: HoraId| async move { id.to_string() }),
+            );
+
+        for path in ["/users/", "/orders/"] {
+            let ok = call(&app, &format!("{}00cd01daff010002", path)).await;
+            assert_eq!(ok, (StatusCode::OK, "00cd01daff010002".to_string()));
+            let (status, _) = call(&app, &format!("{}00cd01daff01000z", path)).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+
+        let (_, body) = call(&app, "/users/123").await;
+        assert_eq!(
+            body,
+            "Invalid HoraId in path: invalid length: expected 16, got 3"
+        );
+        let (status, _) = call(&app, "/items/1/00cd01daff010002").await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
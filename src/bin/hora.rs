@@ -0,0 +1,421 @@
+//! `hora` command line tool to generate, inspect and convert HoraIDs
+
+use hora_id::{HoraGenerator, HoraId};
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage:
+  hora gen [-n <count>] [--machine <id>] [--to <format>]
+  hora inspect [--from <format>] [<id>...]
+  hora parse [--from <format>] [<id>...]
+  hora convert --to <format> [--from <format>] [<id>...]
+
+IDs are read from standard input, one per line, if none are given.
+Without --from, hexadecimal, decimal u64 and Base32 are detected. A number of
+exactly 16 digits can be either hex or u64 and needs --from.
+
+Formats: hex, u64, base32, base58, base62, base64url, and uuid or ulid when built
+with the matching feature";
+
+/// Failure of a command, with the exit code it maps to
+#[derive(Debug, PartialEq)]
+enum CliError {
+    /// The command line is wrong, exits with 2 after printing the usage
+    Usage(String),
+    /// An ID couldn't be read or the output couldn't be written, exits with 1
+    Failed(String),
+}
+
+impl From<io::Error> for CliError {
+    fn from(err: io::Error) -> Self {
+        CliError::Failed(err.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Hex,
+    U64,
+    Base32,
+    Base58,
+    Base62,
+    Base64Url,
+    #[cfg(feature = "uuid")]
+    Uuid,
+    #[cfg(feature = "ulid")]
+    Ulid,
+}
+
+impl Format {
+    fn from_name(name: &str) -> Result<Self, CliError> {
+        match name {
+            "hex" => Ok(Format::Hex),
+            "u64" => Ok(Format::U64),
+            "base32" => Ok(Format::Base32),
+            "base58" => Ok(Format::Base58),
+            "base62" => Ok(Format::Base62),
+            "base64url" => Ok(Format::Base64Url),
+            #[cfg(feature = "uuid")]
+            "uuid" => Ok(Format::Uuid),
+            #[cfg(feature = "ulid")]
+            "ulid" => Ok(Format::Ulid),
+            _ => Err(CliError::Usage(format!("unknown format '{}'", name))),
+        }
+    }
+
+    fn encode(self, id: HoraId) -> String {
+        match self {
+            Format::Hex => id.to_string(),
+            Format::U64 => id.to_u64().to_string(),
+            Format::Base32 => id.to_base32(),
+            Format::Base58 => id.to_base58(),
+            Format::Base62 => id.to_base62(),
+            Format::Base64Url => id.to_base64url(),
+            #[cfg(feature = "uuid")]
+            Format::Uuid => id.to_uuid().to_string(),
+            #[cfg(feature = "ulid")]
+            Format::Ulid => id.to_ulid().to_string(),
+        }
+    }
+
+    fn decode(self, s: &str) -> Result<HoraId, String> {
+        let s = s.trim();
+        let id = match self {
+            Format::Hex => HoraId::parse_lenient(s).map_err(|err| err.to_string()),
+            Format::U64 => s
+                .parse::<u64>()
+                .map(HoraId::from)
+                .map_err(|err| err.to_string()),
+            Format::Base32 => HoraId::from_base32(s).map_err(|err| err.to_string()),
+            Format::Base58 => HoraId::from_base58(s).map_err(|err| err.to_string()),
+            Format::Base62 => HoraId::from_base62(s).map_err(|err| err.to_string()),
+            Format::Base64Url => HoraId::from_base64url(s).map_err(|err| err.to_string()),
+            #[cfg(feature = "uuid")]
+            Format::Uuid => s
+                .parse::<uuid::Uuid>()
+                .map_err(|err| err.to_string())
+                .and_then(|uuid| HoraId::try_from(uuid).map_err(|err| err.to_string())),
+            #[cfg(feature = "ulid")]
+            Format::Ulid => s
+                .parse::<ulid::Ulid>()
+                .map_err(|err| err.to_string())
+                .and_then(|ulid| HoraId::try_from(ulid).map_err(|err| err.to_string())),
+        };
+        id.map_err(|err| format!("invalid ID '{}': {}", s, err))
+    }
+}
+
+/// Decode `s` with `format`, or detect the format from its length and characters
+///
+/// Digits only are read as u64, unless there are exactly 16 of them, which is also a valid hex
+/// ID. The IDs of the first weeks after the epoch have 16 decimal digits, so that case is an
+/// error instead of a guess.
+fn decode(s: &str, format: Option<Format>) -> Result<HoraId, CliError> {
+    let format = match format {
+        Some(format) => format,
+        None => {
+            let s = s.trim();
+            if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+                if s.len() == 16 {
+                    return Err(CliError::Failed(format!(
+                        "'{}' can be hex or u64, pass --from hex or --from u64",
+                        s
+                    )));
+                }
+                Format::U64
+            } else if HoraId::parse_lenient(s).is_ok() {
+                Format::Hex
+            } else if s.len() == 13 {
+                Format::Base32
+            } else {
+                return Err(CliError::Failed(format!(
+                    "can't detect the format of '{}', pass --from",
+                    s
+                )));
+            }
+        }
+    };
+    format.decode(s).map_err(CliError::Failed)
+}
+
+/// Format Unix milliseconds as an RFC 3339 UTC timestamp
+fn format_millis(millis: u64) -> String {
+    let days = (millis / 86_400_000) as i64;
+    let ms_of_day = millis % 86_400_000;
+
+    // civil date from days since 1970-01-01, valid for the proleptic Gregorian calendar
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        ms_of_day / 3_600_000,
+        ms_of_day / 60_000 % 60,
+        ms_of_day / 1000 % 60,
+        ms_of_day % 1000
+    )
+}
+
+fn inspect(id: HoraId) -> String {
+    let parts = match id.decode() {
+        Ok(parts) => parts,
+        Err(err) => return format!("id         {}\nversion    {} ({})", id, id.version(), err),
+    };
+    format!(
+        "id         {}\n\
+         u64        {}\n\
+         version    {}\n\
+         timestamp  {}\n\
+         unix_ms    {}\n\
+         machine    {}\n\
+         sequence   {}",
+        id,
+        id.to_u64(),
+        parts.version,
+        format_millis(parts.timestamp_millis),
+        parts.timestamp_millis,
+        parts.machine_id,
+        parts.sequence
+    )
+}
+
+/// Options shared by the subcommands
+#[derive(Debug, Default)]
+struct Options {
+    count: Option<usize>,
+    machine: Option<u8>,
+    from: Option<Format>,
+    to: Option<Format>,
+    ids: Vec<String>,
+}
+
+fn parse_options(args: &[String]) -> Result<Options, CliError> {
+    let mut options = Options::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| CliError::Usage(format!("{} needs a value", name)))
+        };
+        match arg.as_str() {
+            "-n" | "--count" => {
+                let count = value(arg)?;
+                let count = count
+                    .parse()
+                    .map_err(|_| CliError::Usage(format!("invalid count '{}'", count)))?;
+                options.count = Some(count);
+            }
+            "-m" | "--machine" => {
+                let machine = value(arg)?;
+                let machine = machine.parse().map_err(|_| {
+                    CliError::Usage(format!(
+                        "invalid machine ID '{}', expected 0 to 255",
+                        machine
+                    ))
+                })?;
+                options.machine = Some(machine);
+            }
+            "--from" => options.from = Some(Format::from_name(value(arg)?)?),
+            "--to" => options.to = Some(Format::from_name(value(arg)?)?),
+            flag if flag.starts_with('-') && flag.len() > 1 => {
+                return Err(CliError::Usage(format!("unknown option '{}'", flag)));
+            }
+            id => options.ids.push(id.to_owned()),
+        }
+    }
+    Ok(options)
+}
+
+/// Run the command line `args`, without the program name, writing results to `out`
+fn run(args: &[String], input: impl BufRead, out: &mut impl Write) -> Result<(), CliError> {
+    let (command, rest) = args
+        .split_first()
+        .ok_or_else(|| CliError::Usage("missing command".to_owned()))?;
+    let options = parse_options(rest)?;
+
+    if command == "gen" {
+        if !options.ids.is_empty() || options.from.is_some() {
+            return Err(CliError::Usage("gen doesn't take IDs".to_owned()));
+        }
+        let format = options.to.unwrap_or(Format::Hex);
+        let mut generator = HoraGenerator::new(options.machine.unwrap_or(0))
+            .map_err(|err| CliError::Failed(err.to_string()))?;
+        for _ in 0..options.count.unwrap_or(1) {
+            writeln!(out, "{}", format.encode(generator.next()))?;
+        }
+        return Ok(());
+    }
+
+    let print: fn(HoraId, Option<Format>) -> String = match command.as_str() {
+        "inspect" => |id, _| inspect(id),
+        "parse" => |id, _| id.to_string(),
+        "convert" => |id, to| to.unwrap_or(Format::Hex).encode(id),
+        "help" | "-h" | "--help" => {
+            writeln!(out, "{}", USAGE)?;
+            return Ok(());
+        }
+        _ => return Err(CliError::Usage(format!("unknown command '{}'", command))),
+    };
+    if options.count.is_some() || options.machine.is_some() {
+        return Err(CliError::Usage(format!(
+            "{} doesn't take -n or --machine",
+            command
+        )));
+    }
+    if command == "convert" && options.to.is_none() {
+        return Err(CliError::Usage("convert needs --to".to_owned()));
+    }
+    if command != "convert" && options.to.is_some() {
+        return Err(CliError::Usage(format!("{} doesn't take --to", command)));
+    }
+
+    let mut handle = |s: &str| -> Result<(), CliError> {
+        let id = decode(s, options.from)?;
+        writeln!(out, "{}", print(id, options.to))?;
+        Ok(())
+    };
+    if options.ids.is_empty() {
+        for line in input.lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                handle(&line)?;
+            }
+        }
+    } else {
+        for id in &options.ids {
+            handle(id)?;
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut out = BufWriter::new(io::stdout().lock());
+    let result = run(&args, io::stdin().lock(), &mut out);
+    let flushed = out.flush();
+    match result.and(flushed.map_err(CliError::from)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(CliError::Usage(msg)) => {
+            eprintln!("hora: {}\n\n{}", msg, USAGE);
+            ExitCode::from(2)
+        }
+        Err(CliError::Failed(msg)) => {
+            eprintln!("hora: {}", msg);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_str(args: &str, input: &str) -> Result<String, CliError> {
+        let args: Vec<String> = args.split_whitespace().map(str::to_owned).collect();
+        let mut out = Vec::new();
+        run(&args, input.as_bytes(), &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn gen() {
+        let out = run_str("gen -n 100 --machine 7", "").unwrap();
+        let ids: Vec<HoraId> = out.lines().map(|line| line.parse().unwrap()).collect();
+        assert_eq!(ids.len(), 100);
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(ids.iter().all(|id| id.machine_id() == 7));
+
+        let out = run_str("gen --to base32", "").unwrap();
+        assert_eq!(out.trim().len(), 13);
+        assert!(matches!(
+            run_str("gen --machine 256", ""),
+            Err(CliError::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn inspect() {
+        let out = run_str("inspect 00cd01daff010002", "").unwrap();
+        assert!(out.contains("u64        57704410318438402\n"));
+        assert!(out.contains("timestamp  2025-06-05T12:02:34.997Z\n"));
+        assert!(out.contains("machine    1\n"));
+        assert!(out.contains("sequence   2\n"));
+        assert!(out.contains("version    0\n"));
+
+        let out = run_str("inspect 40cd01daff010002", "").unwrap();
+        assert!(out.contains("version    1 (unsupported HoraId format version 1)"));
+    }
+
+    #[test]
+    fn parse_and_convert() {
+        assert_eq!(
+            run_str(
+                "parse",
+                "57704410318438402\n0xcd01daff010002\n01K81VBZG2002\n"
+            )
+            .unwrap(),
+            "00cd01daff010002\n".repeat(3)
+        );
+        assert_eq!(
+            run_str("convert --to base62 00cd01daff010002", "").unwrap(),
+            "04GHmVCTHPu\n"
+        );
+        assert_eq!(
+            run_str("convert --from base62 --to u64 04GHmVCTHPu", "").unwrap(),
+            "57704410318438402\n"
+        );
+        assert!(matches!(
+            run_str("parse 04GHmVCTHPu", ""),
+            Err(CliError::Failed(_))
+        ));
+
+        // an ID of the first weeks after the epoch has 16 decimal digits
+        let early = HoraId::from(1_000_000_000_000_000u64);
+        let decimal = run_str("convert --to u64", &early.to_string()).unwrap();
+        assert_eq!(decimal, "1000000000000000\n");
+        assert_eq!(
+            run_str("parse", &decimal),
+            Err(CliError::Failed(
+                "'1000000000000000' can be hex or u64, pass --from hex or --from u64".into()
+            ))
+        );
+        assert_eq!(
+            run_str("parse --from u64", &decimal).unwrap(),
+            format!("{}\n", early)
+        );
+        assert_eq!(
+            run_str("parse 123456789012345678", "").unwrap(),
+            format!("{}\n", HoraId::from(123456789012345678u64))
+        );
+        assert!(matches!(
+            run_str("convert 00cd01daff010002", ""),
+            Err(CliError::Usage(_))
+        ));
+        assert!(matches!(run_str("frobnicate", ""), Err(CliError::Usage(_))));
+        for command in ["inspect", "parse"] {
+            assert_eq!(
+                run_str(&format!("{} --to u64 00cd01daff010002", command), ""),
+                Err(CliError::Usage(format!("{} doesn't take --to", command)))
+            );
+        }
+    }
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(format_millis(0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(format_millis(1735689600000), "2025-01-01T00:00:00.000Z");
+        assert_eq!(format_millis(951782400123), "2000-02-29T00:00:00.123Z");
+    }
+}
@@ -0,0 +1,139 @@
+//! Uniqueness stress test for generators used from many threads at once
+//!
+//! Usage: `cargo run --release --bin stress -- [threads] [ids-per-thread]`
+//!
+//! Runs two scenarios and exits with an error if either produces a duplicate or a thread sees
+//! an ID that isn't greater than its previous one:
+//! - all threads share one [SyncHoraGenerator]
+//! - every thread owns a [HoraGenerator] with its own machine ID
+
+use hora_id::{HoraGenerator, HoraId, SyncHoraGenerator};
+use std::process::ExitCode;
+use std::thread;
+use std::time::Instant;
+
+const THREADS: usize = 8;
+const IDS_PER_THREAD: usize = 5_000_000;
+
+/// Check that the IDs of every thread increase and that no ID appears twice overall
+fn check(per_thread: Vec<Vec<HoraId>>) -> Result<usize, String> {
+    for (thread, ids) in per_thread.iter().enumerate() {
+        if let Some(pair) = ids.windows(2).find(|pair| pair[0] >= pair[1]) {
+            return Err(format!(
+                "thread {} generated {} after {}",
+                thread, pair[1], pair[0]
+            ));
+        }
+    }
+
+    let mut all: Vec<u64> = per_thread
+        .into_iter()
+        .flatten()
+        .map(|id| id.to_u64())
+        .collect();
+    all.sort_unstable();
+    if let Some(pair) = all.windows(2).find(|pair| pair[0] == pair[1]) {
+        return Err(format!("duplicate ID {}", HoraId::from(pair[0])));
+    }
+    Ok(all.len())
+}
+
+fn run<F>(name: &str, threads: usize, generate: F) -> Result<(), String>
+where
+    F: Fn(usize) -> Vec<HoraId> + Sync,
+{
+    let start = Instant::now();
+    let per_thread = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|thread| {
+                let generate = &generate;
+                scope.spawn(move || generate(thread))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("generator thread panicked"))
+            .collect::<Vec<_>>()
+    });
+    let elapsed = start.elapsed();
+    let total = check(per_thread).map_err(|err| format!("{}: {}", name, err))?;
+    println!(
+        "{}: {} unique IDs from {} threads in {:?}",
+        name, total, threads, elapsed
+    );
+    Ok(())
+}
+
+fn parse_arg(arg: Option<String>, default: usize) -> Result<usize, String> {
+    match arg {
+        None => Ok(default),
+        Some(arg) => arg
+            .parse()
+            .ok()
+            .filter(|&n| n > 0)
+            .ok_or_else(|| format!("expected a positive number, got '{}'", arg)),
+    }
+}
+
+fn stress() -> Result<(), String> {
+    let mut args = std::env::args().skip(1);
+    let threads = parse_arg(args.next(), THREADS)?;
+    let count = parse_arg(args.next(), IDS_PER_THREAD)?;
+    if threads > 256 {
+        return Err("at most 256 threads, one per machine ID".to_owned());
+    }
+
+    let shared = SyncHoraGenerator::new(1).map_err(|err| err.to_string())?;
+    run("shared generator", threads, |_| {
+        (0..count).map(|_| shared.next()).collect()
+    })?;
+
+    run("generator per thread", threads, |thread| {
+        let mut generator = HoraGenerator::new(thread as u8).expect("valid machine ID");
+        let mut ids = Vec::with_capacity(count);
+        generator.next_n_into(count, &mut ids);
+        ids
+    })
+}
+
+fn main() -> ExitCode {
+    match stress() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("stress: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_problems() {
+        let ids = |nums: &[u64]| {
+            nums.iter()
+                .map(|&num| HoraId::from(num))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(check(vec![ids(&[1, 2, 5]), ids(&[3, 4])]), Ok(5));
+        assert!(check(vec![ids(&[1, 3]), ids(&[2, 3])])
+            .unwrap_err()
+            .starts_with("duplicate"));
+        assert!(check(vec![ids(&[1, 2]), ids(&[4, 3])])
+            .unwrap_err()
+            .starts_with("thread 1"));
+    }
+
+    #[test]
+    fn small_run() {
+        let shared = SyncHoraGenerator::new(1).unwrap();
+        assert_eq!(
+            run("shared generator", 4, |_| {
+                (0..10_000).map(|_| shared.next()).collect()
+            }),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,225 @@
+//! [bson] support for [HoraId]
+//!
+//! IDs are stored as 8 byte [Binary] values with the generic subtype. MongoDB compares binary
+//! values by length, then subtype, then bytes, so stored IDs sort in the same order as the IDs.
+//!
+//! For collections keyed by [ObjectId], an ID converts to an ObjectId of the same second with the
+//! 8 bytes of the ID after the 4 byte timestamp, which also keeps the order of the IDs.
+
+use crate::{HoraError, HoraId};
+use bson::oid::ObjectId;
+use bson::spec::BinarySubtype;
+use bson::{Binary, Bson};
+
+impl HoraId {
+    /// Convert the [HoraId] to an [ObjectId] of the same second
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap();
+    /// let oid = id.to_object_id();
+    /// assert_eq!(oid.to_hex(), "6841875a00cd01daff010002");
+    /// assert_eq!(HoraId::try_from_object_id(&oid), Ok(id));
+    /// ```
+    pub fn to_object_id(&self) -> ObjectId {
+        let mut bytes = [0; 12];
+        bytes[..4].copy_from_slice(&(self.unix_seconds() as u32).to_be_bytes());
+        bytes[4..].copy_from_slice(&self.inner);
+        ObjectId::from_bytes(bytes)
+    }
+
+    /// Extract the [HoraId] embedded with [HoraId::to_object_id]
+    ///
+    /// ## Fail condition
+    /// If the last 8 bytes of `oid` are not an ID of the ObjectId's second
+    pub fn try_from_object_id(oid: &ObjectId) -> Result<Self, HoraError> {
+        let bytes = oid.bytes();
+        let mut inner = [0; 8];
+        inner.copy_from_slice(&bytes[4..]);
+        let id = HoraId { inner };
+        let seconds = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        if id.unix_seconds() != seconds as u64 {
+            return Err(HoraError::IncompatibleId);
+        }
+        Ok(id)
+    }
+
+    /// Create the first [HoraId] of the second of any [ObjectId]
+    ///
+    /// The machine ID and sequence are zero, so the ID sorts before every generated ID of that
+    /// second. Use it as the lower bound when backfilling IDs for documents created at the time
+    /// of an ObjectId.
+    ///
+    /// ## Fail condition
+    /// If the ObjectId is from before the HoraID epoch (2025-01-01)
+    pub fn timestamp_from_object_id(oid: &ObjectId) -> Result<Self, HoraError> {
+        HoraId::min_for_millis(oid.timestamp().timestamp_millis() as u64)
+    }
+}
+
+impl From<HoraId> for ObjectId {
+    fn from(id: HoraId) -> Self {
+        id.to_object_id()
+    }
+}
+
+impl TryFrom<ObjectId> for HoraId {
+    type Error = HoraError;
+
+    fn try_from(oid: ObjectId) -> Result<Self, Self::Error> {
+        HoraId::try_from_object_id(&oid)
+    }
+}
+
+impl From<HoraId> for Binary {
+    fn from(id: HoraId) -> Self {
+        Binary {
+            subtype: BinarySubtype::Generic,
+            bytes: id.as_bytes().to_vec(),
+        }
+    }
+}
+
+impl From<HoraId> for Bson {
+    fn from(id: HoraId) -> Self {
+        Bson::Binary(Binary::from(id))
+    }
+}
+
+/// Serialize a [HoraId] as BSON [Binary], for use with `#[serde(with = "hora_id::bson_binary")]`
+///
+/// Deserializing accepts an 8 byte binary, a 64-bit integer or a hexadecimal string, so
+/// documents written with other representations can still be read.
+///
+/// ```
+/// use hora_id::HoraId;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Item {
+///     #[serde(rename = "_id", with = "hora_id::bson_binary")]
+///     id: HoraId,
+///     name: String,
+/// }
+///
+/// let item = Item { id: "00cd01daff010002".parse().unwrap(), name: "first".to_owned() };
+/// let doc = bson::to_document(&item).unwrap();
+/// assert!(matches!(doc.get("_id"), Some(bson::Bson::Binary(_))));
+/// ```
+pub mod bson_binary {
+    use crate::HoraId;
+    use bson::{Binary, Bson};
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(id: &HoraId, serializer: S) -> Result<S::Ok, S::Error> {
+        Binary::from(*id).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<HoraId, D::Error> {
+        match Bson::deserialize(deserializer)? {
+            Bson::Binary(binary) => {
+                HoraId::try_from(binary.bytes.as_slice()).map_err(D::Error::custom)
+            }
+            Bson::Int64(num) => Ok(HoraId::from(num as u64)),
+            Bson::String(s) => s.parse().map_err(D::Error::custom),
+            other => Err(D::Error::custom(format!(
+                "expected a binary, int64 or string HoraId, found {:?}",
+                other.element_type()
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bson::doc;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Item {
+        #[serde(rename = "_id", with = "crate::bson_binary")]
+        id: HoraId,
+    }
+
+    #[test]
+    fn conversions() {
+        let id: HoraId = "00cd01daff010002".parse().unwrap();
+        let bson = Bson::from(id);
+        match bson {
+            Bson::Binary(binary) => {
+                assert_eq!(binary.subtype, BinarySubtype::Generic);
+                assert_eq!(binary.bytes, id.as_bytes());
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(doc! { "_id": id }.get("_id"), Some(&Bson::from(id)));
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let item = Item {
+            id: "00cd01daff010002".parse().unwrap(),
+        };
+        let doc = bson::to_document(&item).unwrap();
+        assert_eq!(doc, doc! { "_id": item.id });
+        assert_eq!(bson::from_document::<Item>(doc).unwrap(), item);
+    }
+
+    #[test]
+    fn deserialize_other_representations() {
+        let id: HoraId = "00cd01daff010002".parse().unwrap();
+        let from_int: Item = bson::from_document(doc! { "_id": 57704410318438402i64 }).unwrap();
+        assert_eq!(from_int.id, id);
+        let from_str: Item = bson::from_document(doc! { "_id": "00cd01daff010002" }).unwrap();
+        assert_eq!(from_str.id, id);
+
+        assert!(bson::from_document::<Item>(doc! { "_id": 1.5 }).is_err());
+        let short = Binary {
+            subtype: BinarySubtype::Generic,
+            bytes: vec![1, 2, 3],
+        };
+        assert!(bson::from_document::<Item>(doc! { "_id": short }).is_err());
+    }
+
+    #[test]
+    fn object_id() {
+        let ids = [57704410318438402, 57704410318438403, 57704414613405698];
+        let oids: Vec<ObjectId> = ids.iter().map(|&num| HoraId::from(num).into()).collect();
+        assert!(oids.windows(2).all(|pair| pair[0] < pair[1]));
+        for (&num, &oid) in ids.iter().zip(&oids) {
+            let id = HoraId::from(num);
+            assert_eq!(
+                oid.timestamp().timestamp_millis() as u64,
+                id.timestamp_millis() / 1000 * 1000
+            );
+            assert_eq!(HoraId::try_from(oid), Ok(id));
+        }
+
+        let foreign = ObjectId::parse_str("6788d4da0123456789abcdef").unwrap();
+        assert_eq!(
+            HoraId::try_from_object_id(&foreign),
+            Err(HoraError::IncompatibleId)
+        );
+        let first = HoraId::timestamp_from_object_id(&foreign).unwrap();
+        assert_eq!(first.timestamp_millis(), 0x6788d4da * 1000);
+        assert_eq!((first.machine_id(), first.sequence()), (0, 0));
+        let old = ObjectId::parse_str("5f0000000123456789abcdef").unwrap();
+        assert_eq!(
+            HoraId::timestamp_from_object_id(&old),
+            Err(HoraError::TimestampOutOfRange)
+        );
+    }
+
+    #[test]
+    fn sort_order() {
+        let older = HoraId::from(57704410318438402);
+        let newer = HoraId::from(57704410335215618);
+        let (Bson::Binary(a), Bson::Binary(b)) = (Bson::from(older), Bson::from(newer)) else {
+            unreachable!()
+        };
+        assert!(a.bytes < b.bytes);
+    }
+}
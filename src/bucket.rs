@@ -0,0 +1,83 @@
+use crate::HoraId;
+
+/// Length of the time buckets returned by [HoraId::bucket]
+///
+/// Buckets are aligned to UTC. Weeks start on Monday, as in ISO 8601.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Granularity {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+}
+
+/// 1970-01-01 was a Thursday, the first Monday after it is 4 days later
+const FIRST_MONDAY_MILLIS: u64 = 4 * 86_400_000;
+
+impl Granularity {
+    /// Length of a bucket in milliseconds
+    pub const fn millis(&self) -> u64 {
+        match self {
+            Granularity::Second => 1000,
+            Granularity::Minute => 60_000,
+            Granularity::Hour => 3_600_000,
+            Granularity::Day => 86_400_000,
+            Granularity::Week => 7 * 86_400_000,
+        }
+    }
+}
+
+impl HoraId {
+    /// Start of the time bucket the [HoraId] was created in, as milliseconds since the Unix epoch
+    ///
+    /// IDs of the same hour, day or week get the same value, so they can be grouped or routed to
+    /// time-partitioned tables without converting to a datetime.
+    ///
+    /// ```
+    /// use hora_id::{Granularity, HoraId};
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap(); // 2025-06-05 12:02:34.997 UTC
+    /// assert_eq!(id.bucket(Granularity::Hour), 1749124800000); // 2025-06-05 12:00 UTC
+    /// assert_eq!(id.bucket(Granularity::Day), 1749081600000); // 2025-06-05 00:00 UTC
+    /// assert_eq!(id.bucket(Granularity::Week), 1748822400000); // 2025-06-02 00:00 UTC, a Monday
+    /// ```
+    pub fn bucket(&self, granularity: Granularity) -> u64 {
+        let millis = self.timestamp_millis();
+        let length = granularity.millis();
+        match granularity {
+            Granularity::Week => {
+                let since_monday = millis - FIRST_MONDAY_MILLIS;
+                since_monday - since_monday % length + FIRST_MONDAY_MILLIS
+            }
+            _ => millis - millis % length,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EPOCH;
+
+    #[test]
+    fn buckets() {
+        // the epoch, 2025-01-01, is a Wednesday
+        let first = HoraId::min_for_millis(EPOCH).unwrap();
+        assert_eq!(first.bucket(Granularity::Second), EPOCH);
+        assert_eq!(first.bucket(Granularity::Day), EPOCH);
+        assert_eq!(first.bucket(Granularity::Week), EPOCH - 2 * 86_400_000);
+
+        let id = HoraId::max_for_millis(EPOCH + 86_400_000 - 1).unwrap();
+        assert_eq!(id.bucket(Granularity::Second), EPOCH + 86_399_000);
+        assert_eq!(id.bucket(Granularity::Minute), EPOCH + 86_340_000);
+        assert_eq!(id.bucket(Granularity::Hour), EPOCH + 82_800_000);
+        assert_eq!(id.bucket(Granularity::Day), EPOCH);
+
+        let next_monday = EPOCH + 5 * 86_400_000;
+        let id = HoraId::min_for_millis(next_monday).unwrap();
+        assert_eq!(id.bucket(Granularity::Week), next_monday);
+        let id = HoraId::max_for_millis(next_monday - 1).unwrap();
+        assert_eq!(id.bucket(Granularity::Week), next_monday - 7 * 86_400_000);
+    }
+}
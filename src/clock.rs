@@ -0,0 +1,134 @@
+//! Time sources for [HoraGenerator](crate::HoraGenerator)
+
+#[cfg(feature = "std")]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "std")]
+use std::sync::Arc;
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A source of the current time for [HoraGenerator](crate::HoraGenerator)
+///
+/// Any `Fn() -> u64` closure returning milliseconds since the Unix epoch is a [Clock], which is
+/// enough for reading an RTC on embedded targets. Implement the trait directly for clocks that
+/// carry state.
+pub trait Clock {
+    /// Current time in milliseconds since the Unix epoch
+    fn now_millis(&self) -> u64;
+}
+
+impl<F: Fn() -> u64> Clock for F {
+    fn now_millis(&self) -> u64 {
+        self()
+    }
+}
+
+/// The system wall clock, used by [HoraGenerator::new](crate::HoraGenerator::new)
+///
+/// Only implements [Clock] with the `std` feature.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    /// A system time before the Unix epoch is reported as 0, which is before every HoraID epoch.
+    fn now_millis(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_millis() as u64)
+    }
+}
+
+/// A manually controlled [Clock] for deterministic tests
+///
+/// Clones share the same time, so a test can keep one clone and move the other into a
+/// generator, then [set](MockClock::set) or [advance](MockClock::advance) the time to reproduce
+/// same-tick, rollback and sequence overflow scenarios.
+///
+/// ## Usage
+/// ```
+/// use hora_id::{HoraGenerator, MockClock, EPOCH};
+///
+/// let clock = MockClock::new(EPOCH + 1000);
+/// let mut generator = HoraGenerator::new_with_clock(1, clock.clone()).unwrap();
+///
+/// let first = generator.next();
+/// let second = generator.next();
+/// assert_eq!(second.sequence(), first.sequence() + 1);
+///
+/// clock.advance(1000);
+/// let third = generator.next();
+/// assert_eq!(third.timestamp_millis(), EPOCH + 2000);
+/// assert_eq!(third.sequence(), 0);
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug, Clone, Default)]
+pub struct MockClock {
+    millis: Arc<AtomicU64>,
+}
+
+#[cfg(feature = "std")]
+impl MockClock {
+    /// Create a clock that is stopped at `millis` since the Unix epoch
+    pub fn new(millis: u64) -> Self {
+        Self {
+            millis: Arc::new(AtomicU64::new(millis)),
+        }
+    }
+
+    /// Set the time to `millis` since the Unix epoch, which may be earlier than the current time
+    pub fn set(&self, millis: u64) {
+        self.millis.store(millis, Ordering::SeqCst);
+    }
+
+    /// Move the time forward by `millis`
+    pub fn advance(&self, millis: u64) {
+        self.millis.fetch_add(millis, Ordering::SeqCst);
+    }
+
+    /// Move the time backward by `millis`
+    pub fn rewind(&self, millis: u64) {
+        self.millis.fetch_sub(millis, Ordering::SeqCst);
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for MockClock {
+    fn now_millis(&self) -> u64 {
+        self.millis.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closure() {
+        let clock = || 42;
+        assert_eq!(clock.now_millis(), 42);
+    }
+
+    #[test]
+    fn system() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        assert!(SystemClock.now_millis() - now < 1000);
+    }
+
+    #[test]
+    fn mock() {
+        let clock = MockClock::new(1000);
+        let shared = clock.clone();
+        assert_eq!(shared.now_millis(), 1000);
+        clock.advance(500);
+        assert_eq!(shared.now_millis(), 1500);
+        clock.rewind(200);
+        assert_eq!(shared.now_millis(), 1300);
+        clock.set(10);
+        assert_eq!(shared.now_millis(), 10);
+    }
+}
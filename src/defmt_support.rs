@@ -0,0 +1,19 @@
+//! [defmt] support for [HoraId] and [HoraId128]
+//!
+//! The IDs are sent as integers, 8 and 16 bytes on the wire, and formatted as the same
+//! hexadecimal strings as [Display](core::fmt::Display) on the host.
+
+use crate::{HoraId, HoraId128};
+use defmt::{Format, Formatter};
+
+impl Format for HoraId {
+    fn format(&self, f: Formatter<'_>) {
+        defmt::write!(f, "{=u64:016x}", self.to_u64())
+    }
+}
+
+impl Format for HoraId128 {
+    fn format(&self, f: Formatter<'_>) {
+        defmt::write!(f, "{=u128:032x}", self.to_u128())
+    }
+}
@@ -0,0 +1,132 @@
+//! [diesel] support for [HoraId]
+//!
+//! Maps to `BigInt` columns through [HoraId::to_u64] and to `Binary` columns as 8 bytes.
+//!
+//! Writing a `BigInt` converts the ID to a temporary integer, which diesel only supports per
+//! backend. Enable `diesel-postgres`, `diesel-mysql` or `diesel-sqlite` for the backends you use.
+//! Reading either column type and writing `Binary` work with every backend.
+
+use crate::HoraId;
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::{BigInt, Binary};
+
+#[cfg(feature = "diesel-mysql")]
+impl ToSql<BigInt, diesel::mysql::Mysql> for HoraId {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, diesel::mysql::Mysql>) -> serialize::Result {
+        let num = self.to_u64() as i64;
+        <i64 as ToSql<BigInt, diesel::mysql::Mysql>>::to_sql(&num, &mut out.reborrow())
+    }
+}
+
+#[cfg(feature = "diesel-postgres")]
+impl ToSql<BigInt, diesel::pg::Pg> for HoraId {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, diesel::pg::Pg>) -> serialize::Result {
+        let num = self.to_u64() as i64;
+        <i64 as ToSql<BigInt, diesel::pg::Pg>>::to_sql(&num, &mut out.reborrow())
+    }
+}
+
+#[cfg(feature = "diesel-sqlite")]
+impl ToSql<BigInt, diesel::sqlite::Sqlite> for HoraId {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, diesel::sqlite::Sqlite>) -> serialize::Result {
+        out.set_value(self.to_u64() as i64);
+        Ok(serialize::IsNull::No)
+    }
+}
+
+impl<DB: Backend> FromSql<BigInt, DB> for HoraId
+where
+    i64: FromSql<BigInt, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        let num = <i64 as FromSql<BigInt, DB>>::from_sql(bytes)?;
+        Ok(HoraId::from(num as u64))
+    }
+}
+
+impl<DB: Backend> ToSql<Binary, DB> for HoraId
+where
+    [u8]: ToSql<Binary, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        <[u8] as ToSql<Binary, DB>>::to_sql(&self.inner, out)
+    }
+}
+
+impl<DB: Backend> FromSql<Binary, DB> for HoraId
+where
+    Vec<u8>: FromSql<Binary, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        let bytes = <Vec<u8> as FromSql<Binary, DB>>::from_sql(bytes)?;
+        Ok(HoraId::try_from(bytes.as_slice())?)
+    }
+}
+
+#[cfg(all(test, feature = "diesel-sqlite"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sqlite_round_trip() {
+        use diesel::connection::SimpleConnection;
+        use diesel::prelude::*;
+        use diesel::sqlite::SqliteConnection;
+
+        diesel::table! {
+            items (id) {
+                id -> BigInt,
+                raw -> Binary,
+            }
+        }
+
+        #[derive(Debug, PartialEq, Queryable, Insertable)]
+        #[diesel(table_name = items)]
+        struct Item {
+            id: HoraId,
+            raw: HoraId,
+        }
+
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        conn.batch_execute(
+            "CREATE TABLE items (id BIGINT PRIMARY KEY NOT NULL, raw BLOB NOT NULL)",
+        )
+        .unwrap();
+
+        let id: HoraId = "00cd01daff010002".parse().unwrap();
+        let later = HoraId::from(id.to_u64() + 1);
+        let rows = [later, id].map(|id| Item { id, raw: id });
+        diesel::insert_into(items::table)
+            .values(&rows[..])
+            .execute(&mut conn)
+            .unwrap();
+
+        let loaded: Vec<Item> = items::table.order(items::id).load(&mut conn).unwrap();
+        assert_eq!(
+            loaded,
+            [
+                Item { id, raw: id },
+                Item {
+                    id: later,
+                    raw: later
+                }
+            ]
+        );
+
+        let found: HoraId = items::table
+            .select(items::raw)
+            .filter(items::id.eq(later))
+            .first(&mut conn)
+            .unwrap();
+        assert_eq!(found, later);
+
+        let raw: i64 = items::table
+            .select(items::id)
+            .filter(items::raw.eq(id))
+            .first(&mut conn)
+            .unwrap();
+        assert_eq!(raw, 57704410318438402);
+    }
+}
@@ -0,0 +1,1055 @@
+//! Alternative string encodings of [HoraId] and [HoraId128]
+//!
+//! The Base32, Base58 and Base62 encodings are fixed width and use alphabets in ascending ASCII
+//! order, so the encoded strings sort lexicographically in the same order as the IDs.
+//! Base64url follows RFC 4648 and does not preserve ordering.
+
+use crate::{hex, HoraId, HoraId128, HoraIdParseError};
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec};
+use core::fmt;
+
+/// Crockford's Base32 alphabet, excludes I, L, O and U
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Crockford's check symbols for the values 0 to 36, the Base32 alphabet followed by `*~$=U`
+#[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+const CROCKFORD_CHECK: &[u8; 37] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ*~$=U";
+
+/// Number of characters needed to encode 64 bits in Base32
+const BASE32_LEN: usize = 13;
+
+/// Number of characters needed to encode 128 bits in Base32
+const BASE32_LEN_128: usize = 26;
+
+/// Base62 alphabet, digits followed by uppercase and lowercase letters
+#[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+const BASE62: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Number of characters needed to encode 64 bits in Base62
+const BASE62_LEN: usize = 11;
+
+/// Number of characters needed to encode 128 bits in Base62
+const BASE62_LEN_128: usize = 22;
+
+/// Bitcoin Base58 alphabet, excludes the easily confused 0, O, I and l
+const BASE58: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Number of characters needed to encode 64 bits in Base58
+const BASE58_LEN: usize = 11;
+
+/// Number of characters needed to encode 128 bits in Base58
+const BASE58_LEN_128: usize = 22;
+
+/// URL and filename safe Base64 alphabet from RFC 4648
+#[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Lowercase hexadecimal digits
+pub(crate) const HEX_LOWER: &[u8; 16] = b"0123456789abcdef";
+
+/// Uppercase hexadecimal digits
+pub(crate) const HEX_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Binary digits
+pub(crate) const BINARY: &[u8; 2] = b"01";
+
+/// Write `bytes` as lowercase hexadecimal, at most 16 bytes
+pub(crate) fn fmt_hex(bytes: &[u8], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut buf = [0u8; 32];
+    f.write_str(encode_hex_into(bytes, &mut buf[..2 * bytes.len()]))
+}
+
+/// Write `bytes` as fixed width digits of `bits` bits each, 4 for hexadecimal and 1 for binary
+///
+/// Width, fill and the `#` flag of the formatter are applied, with `prefix` for `#`.
+pub(crate) fn fmt_bits(
+    bytes: &[u8],
+    bits: u32,
+    digits: &[u8],
+    prefix: &str,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    // large enough for 128 bits in binary
+    let mut buf = [0u8; 128];
+    let per_byte = (8 / bits) as usize;
+    let mask = (1u8 << bits) - 1;
+    for (index, byte) in bytes.iter().enumerate() {
+        for digit in 0..per_byte {
+            let shift = 8 - bits as usize * (digit + 1);
+            buf[index * per_byte + digit] = digits[((byte >> shift) & mask) as usize];
+        }
+    }
+    let s = core::str::from_utf8(&buf[..bytes.len() * per_byte]).expect("digits are ASCII");
+    f.pad_integral(true, prefix, s)
+}
+
+/// Decode a hexadecimal string of exactly `2 * N` characters
+pub(crate) fn decode_hex<const N: usize>(s: &str) -> Result<[u8; N], HoraIdParseError> {
+    if s.len() != 2 * N {
+        return Err(HoraIdParseError::InvalidLength {
+            expected: 2 * N,
+            got: s.len(),
+        });
+    }
+    let mut bytes = [0u8; N];
+    for (chars, out) in s.as_bytes().chunks_exact(16).zip(bytes.chunks_exact_mut(8)) {
+        let chunk = hex::decode_chunk(chars.try_into().unwrap()).ok_or_else(|| {
+            // only reached for invalid input, so find the first invalid character
+            let (index, char) = s
+                .char_indices()
+                .find(|(_, c)| !c.is_ascii_hexdigit())
+                .expect("a chunk failed to decode");
+            HoraIdParseError::InvalidCharacter { index, char }
+        })?;
+        out.copy_from_slice(&chunk);
+    }
+    Ok(bytes)
+}
+
+/// Write `bytes` as lowercase hexadecimal into `buf`, which must be twice as long
+///
+/// The length of `bytes` must be a multiple of 8.
+fn encode_hex_into<'a>(bytes: &[u8], buf: &'a mut [u8]) -> &'a str {
+    for (chunk, out) in bytes.chunks_exact(8).zip(buf.chunks_exact_mut(16)) {
+        hex::encode_chunk(chunk.try_into().unwrap(), out.try_into().unwrap());
+    }
+    // hexadecimal digits are ASCII
+    core::str::from_utf8(buf).unwrap()
+}
+
+/// Encode `num` with the given alphabet into all of `buf`, as a fixed width string
+fn encode_radix_into<'a>(mut num: u128, alphabet: &[u8], buf: &'a mut [u8]) -> &'a str {
+    let radix = alphabet.len() as u128;
+    for slot in buf.iter_mut().rev() {
+        *slot = alphabet[(num % radix) as usize];
+        num /= radix;
+    }
+    // all alphabets are ASCII
+    core::str::from_utf8(buf).unwrap()
+}
+
+/// Encode `num` with the given alphabet as a fixed width string of `len` characters
+#[cfg(feature = "alloc")]
+fn encode_radix(num: u128, alphabet: &[u8], len: usize) -> String {
+    let mut buf = vec![0; len];
+    encode_radix_into(num, alphabet, &mut buf);
+    // all alphabets are ASCII
+    String::from_utf8(buf).unwrap()
+}
+
+/// Decode a fixed width string of `len` characters, `digit` maps a character to its value
+fn decode_radix(
+    s: &str,
+    radix: u64,
+    len: usize,
+    digit: impl Fn(char) -> Option<u8>,
+) -> Result<u128, HoraIdParseError> {
+    if s.len() != len {
+        return Err(HoraIdParseError::InvalidLength {
+            expected: len,
+            got: s.len(),
+        });
+    }
+    let mut num = 0u128;
+    for (index, c) in s.char_indices() {
+        let value = digit(c).ok_or(HoraIdParseError::InvalidCharacter { index, char: c })?;
+        num = num
+            .checked_mul(radix as u128)
+            .and_then(|num| num.checked_add(value as u128))
+            .ok_or(HoraIdParseError::Overflow)?;
+    }
+    Ok(num)
+}
+
+/// Decode a fixed width string that must fit in 64 bits
+fn decode_radix_u64(
+    s: &str,
+    radix: u64,
+    len: usize,
+    digit: impl Fn(char) -> Option<u8>,
+) -> Result<u64, HoraIdParseError> {
+    let num = decode_radix(s, radix, len, digit)?;
+    u64::try_from(num).map_err(|_| HoraIdParseError::Overflow)
+}
+
+/// Encode bytes as URL-safe Base64 without padding
+#[cfg(feature = "alloc")]
+fn encode_base64url(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity((bytes.len() * 8).div_ceil(6));
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 6 {
+            bits -= 6;
+            out.push(BASE64URL[((buffer >> bits) & 0x3F) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        // pad the remaining bits with zeros
+        out.push(BASE64URL[((buffer << (6 - bits)) & 0x3F) as usize] as char);
+    }
+    out
+}
+
+/// Decode URL-safe Base64 without padding into exactly `N` bytes
+///
+/// The last character is rejected if its padding bits are not zero, so every byte array has
+/// exactly one accepted encoding.
+fn decode_base64url<const N: usize>(s: &str) -> Result<[u8; N], HoraIdParseError> {
+    let len = (N * 8).div_ceil(6);
+    if s.len() != len {
+        return Err(HoraIdParseError::InvalidLength {
+            expected: len,
+            got: s.len(),
+        });
+    }
+    let mut bytes = [0u8; N];
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    let mut pos = 0;
+    for (index, c) in s.char_indices() {
+        let value =
+            base64url_digit(c).ok_or(HoraIdParseError::InvalidCharacter { index, char: c })?;
+        buffer = ((buffer << 6) | value as u32) & 0xFFFF;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes[pos] = (buffer >> bits) as u8;
+            pos += 1;
+        }
+    }
+    if buffer & ((1 << bits) - 1) != 0 {
+        // `s` is ASCII at this point
+        let index = len - 1;
+        let char = s.as_bytes()[index] as char;
+        return Err(HoraIdParseError::InvalidCharacter { index, char });
+    }
+    Ok(bytes)
+}
+
+fn crockford_digit(c: char) -> Option<u8> {
+    let value = match c.to_ascii_uppercase() {
+        c @ '0'..='9' => c as u8 - b'0',
+        'O' => 0,
+        'I' | 'L' => 1,
+        c => CROCKFORD.iter().position(|&a| a as char == c)? as u8,
+    };
+    Some(value)
+}
+
+fn crockford_check_digit(c: char) -> Option<u8> {
+    match c {
+        '*' => Some(32),
+        '~' => Some(33),
+        '$' => Some(34),
+        '=' => Some(35),
+        'U' | 'u' => Some(36),
+        c => crockford_digit(c),
+    }
+}
+
+fn base62_digit(c: char) -> Option<u8> {
+    let value = match c {
+        '0'..='9' => c as u8 - b'0',
+        'A'..='Z' => c as u8 - b'A' + 10,
+        'a'..='z' => c as u8 - b'a' + 36,
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn base58_digit(c: char) -> Option<u8> {
+    if !c.is_ascii() {
+        return None;
+    }
+    BASE58.iter().position(|&a| a == c as u8).map(|v| v as u8)
+}
+
+fn base64url_digit(c: char) -> Option<u8> {
+    let value = match c {
+        'A'..='Z' => c as u8 - b'A',
+        'a'..='z' => c as u8 - b'a' + 26,
+        '0'..='9' => c as u8 - b'0' + 52,
+        '-' => 62,
+        '_' => 63,
+        _ => return None,
+    };
+    Some(value)
+}
+
+impl HoraId {
+    /// Parse a hexadecimal [HoraId] written in a non-canonical form
+    ///
+    /// Use this for IDs copied from logs, spreadsheets or typed by humans. Compared to
+    /// [str::parse], this method additionally
+    /// - trims surrounding whitespace
+    /// - accepts a `0x` or `0X` prefix, in which case leading zeros may be omitted
+    /// - ignores `-`, `_`, `:` and space separators between digits
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap();
+    /// assert_eq!(HoraId::parse_lenient(" 00CD01DA-FF01-0002 "), Ok(id));
+    /// assert_eq!(HoraId::parse_lenient("0xcd01daff010002"), Ok(id));
+    /// ```
+    pub fn parse_lenient(s: &str) -> Result<Self, HoraIdParseError> {
+        let trimmed = s.trim_start();
+        let mut offset = s.len() - trimmed.len();
+        let trimmed = trimmed.trim_end();
+        let (body, prefixed) = match trimmed
+            .strip_prefix("0x")
+            .or_else(|| trimmed.strip_prefix("0X"))
+        {
+            Some(body) => {
+                offset += 2;
+                (body, true)
+            }
+            None => (trimmed, false),
+        };
+
+        let mut num = 0u64;
+        let mut digits = 0;
+        for (index, c) in body.char_indices() {
+            if matches!(c, '-' | '_' | ':' | ' ') {
+                continue;
+            }
+            let digit = c.to_digit(16).ok_or(HoraIdParseError::InvalidCharacter {
+                index: offset + index,
+                char: c,
+            })?;
+            digits += 1;
+            num = num.wrapping_shl(4) | digit as u64;
+        }
+
+        let valid_length = if prefixed {
+            (1..=16).contains(&digits)
+        } else {
+            digits == 16
+        };
+        if !valid_length {
+            return Err(HoraIdParseError::InvalidLength {
+                expected: 16,
+                got: digits,
+            });
+        }
+        Ok(Self {
+            inner: num.to_be_bytes(),
+        })
+    }
+
+    /// Write the [HoraId] as 16 lowercase hexadecimal characters into `buf`, without allocating
+    ///
+    /// The returned string borrows `buf` and is the same as the [Display](fmt::Display) output.
+    /// Use this on hot paths such as loggers, where a [String] per ID is too costly.
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap();
+    /// let mut buf = [0; 16];
+    /// assert_eq!(id.encode_hex(&mut buf), "00cd01daff010002");
+    /// ```
+    pub fn encode_hex<'a>(&self, buf: &'a mut [u8; 16]) -> &'a str {
+        encode_hex_into(&self.inner, buf)
+    }
+
+    /// Encode the [HoraId] as hexadecimal after `prefix` and `_`, like `usr_00cd01daff010002`
+    ///
+    /// Prefixes tell apart the IDs of different object types in a public API. Parse the string
+    /// with [HoraId::parse_prefixed].
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap();
+    /// assert_eq!(id.to_prefixed("usr"), "usr_00cd01daff010002");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_prefixed(&self, prefix: &str) -> String {
+        let mut s = String::with_capacity(prefix.len() + 17);
+        s.push_str(prefix);
+        s.push('_');
+        s.push_str(self.encode_hex(&mut [0; 16]));
+        s
+    }
+
+    /// Parse a [HoraId] written by [HoraId::to_prefixed] with the same `prefix`
+    ///
+    /// ## Fail condition
+    /// Returns [HoraIdParseError::InvalidPrefix] if `s` doesn't start with `prefix` and `_`.
+    /// The indices of invalid characters are relative to the start of `s`.
+    ///
+    /// ```
+    /// use hora_id::{HoraId, HoraIdParseError};
+    ///
+    /// let id = HoraId::parse_prefixed("usr", "usr_00cd01daff010002").unwrap();
+    /// assert_eq!(id.to_string(), "00cd01daff010002");
+    /// assert_eq!(
+    ///     HoraId::parse_prefixed("usr", "org_00cd01daff010002"),
+    ///     Err(HoraIdParseError::InvalidPrefix)
+    /// );
+    /// ```
+    pub fn parse_prefixed(prefix: &str, s: &str) -> Result<Self, HoraIdParseError> {
+        let body = s
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_prefix('_'))
+            .ok_or(HoraIdParseError::InvalidPrefix)?;
+        let inner = decode_hex(body).map_err(|err| match err {
+            HoraIdParseError::InvalidCharacter { index, char } => {
+                HoraIdParseError::InvalidCharacter {
+                    index: index + prefix.len() + 1,
+                    char,
+                }
+            }
+            err => err,
+        })?;
+        Ok(Self { inner })
+    }
+
+    /// Encode the [HoraId] as a 13 character Crockford Base32 string
+    ///
+    /// The output uses uppercase letters and sorts in the same order as the IDs.
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap();
+    /// assert_eq!(id.to_base32(), "01K81VBZG2002");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_base32(&self) -> String {
+        encode_radix(self.to_u64() as u128, CROCKFORD, BASE32_LEN)
+    }
+
+    /// Write the [HoraId] as 13 Crockford Base32 characters into `buf`, without allocating
+    ///
+    /// The returned string borrows `buf` and is the same as [HoraId::to_base32].
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap();
+    /// let mut buf = [0; 13];
+    /// assert_eq!(id.encode_base32(&mut buf), "01K81VBZG2002");
+    /// ```
+    pub fn encode_base32<'a>(&self, buf: &'a mut [u8; 13]) -> &'a str {
+        encode_radix_into(self.to_u64() as u128, CROCKFORD, buf)
+    }
+
+    /// Decode a [HoraId] from a 13 character Crockford Base32 string
+    ///
+    /// Decoding is case-insensitive and accepts `O` for `0` and `I`/`L` for `1`.
+    pub fn from_base32(s: &str) -> Result<Self, HoraIdParseError> {
+        let num = decode_radix_u64(s, 32, BASE32_LEN, crockford_digit)?;
+        Ok(Self {
+            inner: num.to_be_bytes(),
+        })
+    }
+
+    /// Encode the [HoraId] as a 14 character Crockford Base32 string ending in a check symbol
+    ///
+    /// The check symbol is the ID modulo 37, as specified by Crockford. It catches every single
+    /// mistyped character and every swap of two neighbouring characters, so IDs read over the
+    /// phone can be told apart from IDs that don't exist with [HoraId::from_base32_check].
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap();
+    /// assert_eq!(id.to_base32_check(), "01K81VBZG2002=");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_base32_check(&self) -> String {
+        let mut s = self.to_base32();
+        s.push(CROCKFORD_CHECK[(self.to_u64() % 37) as usize] as char);
+        s
+    }
+
+    /// Decode a [HoraId] from a 14 character Crockford Base32 string ending in a check symbol
+    ///
+    /// Like [HoraId::from_base32], decoding is case-insensitive and accepts the aliases of `0`
+    /// and `1`.
+    ///
+    /// ## Fail condition
+    /// Returns [HoraIdParseError::ChecksumMismatch] if the string is well-formed but the check
+    /// symbol doesn't match, which means it was mistyped.
+    ///
+    /// ```
+    /// use hora_id::{HoraId, HoraIdParseError};
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap();
+    /// assert_eq!(HoraId::from_base32_check("01k81vbzg2002="), Ok(id));
+    /// assert_eq!(
+    ///     HoraId::from_base32_check("01K81VZBG2002="),
+    ///     Err(HoraIdParseError::ChecksumMismatch)
+    /// );
+    /// ```
+    pub fn from_base32_check(s: &str) -> Result<Self, HoraIdParseError> {
+        if s.len() != BASE32_LEN + 1 {
+            return Err(HoraIdParseError::InvalidLength {
+                expected: BASE32_LEN + 1,
+                got: s.len(),
+            });
+        }
+        // every valid character is ASCII, so the check symbol is the last byte
+        if let Some((index, char)) = s.char_indices().find(|(_, c)| !c.is_ascii()) {
+            return Err(HoraIdParseError::InvalidCharacter { index, char });
+        }
+        let (body, check) = s.split_at(BASE32_LEN);
+        let id = Self::from_base32(body)?;
+        let check = check.as_bytes()[0] as char;
+        let value = crockford_check_digit(check).ok_or(HoraIdParseError::InvalidCharacter {
+            index: BASE32_LEN,
+            char: check,
+        })?;
+        if value as u64 != id.to_u64() % 37 {
+            return Err(HoraIdParseError::ChecksumMismatch);
+        }
+        Ok(id)
+    }
+
+    /// Encode the [HoraId] as an 11 character Base62 string
+    ///
+    /// The alphabet is `0-9`, `A-Z`, `a-z`. The output is case-sensitive and sorts in the same
+    /// order as the IDs when compared byte-wise.
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap();
+    /// assert_eq!(id.to_base62(), "04GHmVCTHPu");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_base62(&self) -> String {
+        encode_radix(self.to_u64() as u128, BASE62, BASE62_LEN)
+    }
+
+    /// Decode a [HoraId] from an 11 character Base62 string
+    pub fn from_base62(s: &str) -> Result<Self, HoraIdParseError> {
+        let num = decode_radix_u64(s, 62, BASE62_LEN, base62_digit)?;
+        Ok(Self {
+            inner: num.to_be_bytes(),
+        })
+    }
+
+    /// Encode the [HoraId] as an 11 character Base58 string
+    ///
+    /// Uses the Bitcoin alphabet, which leaves out `0`, `O`, `I` and `l` so IDs can't be misread.
+    /// The value is left-padded with `1` (the zero digit) and sorts in the same order as the IDs.
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap();
+    /// assert_eq!(id.to_base58(), "18mbS1ZNVRT");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_base58(&self) -> String {
+        encode_radix(self.to_u64() as u128, BASE58, BASE58_LEN)
+    }
+
+    /// Decode a [HoraId] from an 11 character Base58 string
+    ///
+    /// ## Fail condition
+    /// Returns [HoraIdParseError::InvalidCharacter] for characters outside the Base58 alphabet,
+    /// including the excluded `0`, `O`, `I` and `l`.
+    pub fn from_base58(s: &str) -> Result<Self, HoraIdParseError> {
+        let num = decode_radix_u64(s, 58, BASE58_LEN, base58_digit)?;
+        Ok(Self {
+            inner: num.to_be_bytes(),
+        })
+    }
+
+    /// Encode the [HoraId] as an 11 character URL-safe Base64 string without padding
+    ///
+    /// This is the RFC 4648 `base64url` encoding of the 8 ID bytes. The output is safe to use in
+    /// query strings, cookies and file names, but unlike the other encodings it does not sort in
+    /// the same order as the IDs.
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap();
+    /// assert_eq!(id.to_base64url(), "AM0B2v8BAAI");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_base64url(&self) -> String {
+        encode_base64url(&self.inner)
+    }
+
+    /// Decode a [HoraId] from an 11 character URL-safe Base64 string without padding
+    ///
+    /// ## Fail condition
+    /// Besides invalid characters, the last character is rejected if its two padding bits are
+    /// not zero, so every [HoraId] has exactly one accepted encoding.
+    pub fn from_base64url(s: &str) -> Result<Self, HoraIdParseError> {
+        let inner = decode_base64url(s)?;
+        Ok(Self { inner })
+    }
+}
+
+impl HoraId128 {
+    /// Write the [HoraId128] as 32 lowercase hexadecimal characters into `buf`, without
+    /// allocating
+    ///
+    /// The returned string borrows `buf` and is the same as the [Display](fmt::Display) output.
+    pub fn encode_hex<'a>(&self, buf: &'a mut [u8; 32]) -> &'a str {
+        encode_hex_into(self.as_bytes(), buf)
+    }
+
+    /// Encode the [HoraId128] as a 26 character Crockford Base32 string
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_base32(&self) -> String {
+        encode_radix(self.to_u128(), CROCKFORD, BASE32_LEN_128)
+    }
+
+    /// Write the [HoraId128] as 26 Crockford Base32 characters into `buf`, without allocating
+    ///
+    /// The returned string borrows `buf` and is the same as [HoraId128::to_base32].
+    pub fn encode_base32<'a>(&self, buf: &'a mut [u8; 26]) -> &'a str {
+        encode_radix_into(self.to_u128(), CROCKFORD, buf)
+    }
+
+    /// Decode a [HoraId128] from a 26 character Crockford Base32 string
+    pub fn from_base32(s: &str) -> Result<Self, HoraIdParseError> {
+        let num = decode_radix(s, 32, BASE32_LEN_128, crockford_digit)?;
+        Ok(Self::from(num))
+    }
+
+    /// Encode the [HoraId128] as a 22 character Base62 string
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_base62(&self) -> String {
+        encode_radix(self.to_u128(), BASE62, BASE62_LEN_128)
+    }
+
+    /// Decode a [HoraId128] from a 22 character Base62 string
+    pub fn from_base62(s: &str) -> Result<Self, HoraIdParseError> {
+        let num = decode_radix(s, 62, BASE62_LEN_128, base62_digit)?;
+        Ok(Self::from(num))
+    }
+
+    /// Encode the [HoraId128] as a 22 character Base58 string
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_base58(&self) -> String {
+        encode_radix(self.to_u128(), BASE58, BASE58_LEN_128)
+    }
+
+    /// Decode a [HoraId128] from a 22 character Base58 string
+    pub fn from_base58(s: &str) -> Result<Self, HoraIdParseError> {
+        let num = decode_radix(s, 58, BASE58_LEN_128, base58_digit)?;
+        Ok(Self::from(num))
+    }
+
+    /// Encode the [HoraId128] as a 22 character URL-safe Base64 string without padding
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_base64url(&self) -> String {
+        encode_base64url(self.as_bytes())
+    }
+
+    /// Decode a [HoraId128] from a 22 character URL-safe Base64 string without padding
+    pub fn from_base64url(s: &str) -> Result<Self, HoraIdParseError> {
+        let bytes: [u8; 16] = decode_base64url(s)?;
+        Ok(Self::from(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lenient() {
+        let id = HoraId::from_u64(57704410318438402).unwrap();
+        for s in [
+            "00cd01daff010002",
+            "00CD01DAFF010002",
+            "0x00cd01daff010002",
+            "0X00CD01DAFF010002",
+            "0xcd01daff010002",
+            "00cd01da-ff01-0002",
+            "00:cd:01:da:ff:01:00:02",
+            "00cd_01da_ff01_0002",
+            "  00cd01daff010002\n",
+        ] {
+            assert_eq!(HoraId::parse_lenient(s), Ok(id), "{:?}", s);
+        }
+        assert_eq!(
+            HoraId::parse_lenient("0x0"),
+            Ok(HoraId::from_u64(0).unwrap())
+        );
+    }
+
+    #[test]
+    fn lenient_errors() {
+        assert_eq!(
+            HoraId::parse_lenient("cd01daff010002"),
+            Err(HoraIdParseError::InvalidLength {
+                expected: 16,
+                got: 14
+            })
+        );
+        assert_eq!(
+            HoraId::parse_lenient("0x"),
+            Err(HoraIdParseError::InvalidLength {
+                expected: 16,
+                got: 0
+            })
+        );
+        assert_eq!(
+            HoraId::parse_lenient("0x00cd01daff0100020"),
+            Err(HoraIdParseError::InvalidLength {
+                expected: 16,
+                got: 17
+            })
+        );
+        assert_eq!(
+            HoraId::parse_lenient(" 0x00cd01daff01000g"),
+            Err(HoraIdParseError::InvalidCharacter {
+                index: 18,
+                char: 'g'
+            })
+        );
+    }
+
+    #[test]
+    fn prefixed() {
+        let id = HoraId::from_u64(57704410318438402).unwrap();
+        assert_eq!(
+            HoraId::parse_prefixed("usr", &id.to_prefixed("usr")),
+            Ok(id)
+        );
+        assert_eq!(HoraId::parse_prefixed("", "_00cd01daff010002"), Ok(id));
+        assert_eq!(
+            HoraId::parse_prefixed("usr_live", "usr_live_00cd01daff010002"),
+            Ok(id)
+        );
+        for s in [
+            "usr00cd01daff010002",
+            "us_00cd01daff010002",
+            "00cd01daff010002",
+        ] {
+            assert_eq!(
+                HoraId::parse_prefixed("usr", s),
+                Err(HoraIdParseError::InvalidPrefix)
+            );
+        }
+        assert_eq!(
+            HoraId::parse_prefixed("usr", "usr_00cd01dzff010002"),
+            Err(HoraIdParseError::InvalidCharacter {
+                index: 11,
+                char: 'z'
+            })
+        );
+        assert_eq!(
+            HoraId::parse_prefixed("usr", "usr_00cd"),
+            Err(HoraIdParseError::InvalidLength {
+                expected: 16,
+                got: 4
+            })
+        );
+    }
+
+    #[test]
+    fn base32() {
+        let id = HoraId::from_u64(57704410318438402).unwrap();
+        let s = id.to_base32();
+        assert_eq!(s.len(), 13);
+        assert_eq!(HoraId::from_base32(&s), Ok(id));
+        assert_eq!(HoraId::from_base32(&s.to_lowercase()), Ok(id));
+
+        assert_eq!(HoraId::from_u64(0).unwrap().to_base32(), "0000000000000");
+        assert_eq!(
+            HoraId::from_u64(u64::MAX).unwrap().to_base32(),
+            "FZZZZZZZZZZZZ"
+        );
+    }
+
+    #[test]
+    fn encode_into_buffer() {
+        let mut hex = [0; 16];
+        let mut base32 = [0; 13];
+        for num in [0, 57704410318438402, u64::MAX] {
+            let id = HoraId::from_u64(num).unwrap();
+            assert_eq!(id.encode_hex(&mut hex), id.to_string());
+            assert_eq!(id.encode_base32(&mut base32), id.to_base32());
+        }
+
+        let id = HoraId128::from(0x00cd_01da_ff01_0203_0405_0607_0809_0a0b);
+        assert_eq!(id.encode_hex(&mut [0; 32]), id.to_string());
+        assert_eq!(id.encode_base32(&mut [0; 26]), id.to_base32());
+    }
+
+    #[test]
+    fn base32_check() {
+        let id = HoraId::from_u64(57704410318438402).unwrap();
+        let s = id.to_base32_check();
+        assert_eq!(HoraId::from_base32_check(&s), Ok(id));
+        for num in [0, 36, u64::MAX] {
+            let id = HoraId::from_u64(num).unwrap();
+            assert_eq!(HoraId::from_base32_check(&id.to_base32_check()), Ok(id));
+        }
+        assert_eq!(
+            HoraId::from_u64(36).unwrap().to_base32_check(),
+            "0000000000014U"
+        );
+
+        // no single substitution or swap of neighbours decodes to another ID
+        let chars: Vec<char> = s.chars().collect();
+        for index in 0..chars.len() {
+            for &c in CROCKFORD_CHECK {
+                let mut typo = chars.clone();
+                typo[index] = c as char;
+                if typo != chars {
+                    let typo: String = typo.into_iter().collect();
+                    assert!(HoraId::from_base32_check(&typo).is_err(), "{}", typo);
+                }
+            }
+            if index + 1 < chars.len() && chars[index] != chars[index + 1] {
+                let mut swapped = chars.clone();
+                swapped.swap(index, index + 1);
+                let swapped: String = swapped.into_iter().collect();
+                assert!(HoraId::from_base32_check(&swapped).is_err(), "{}", swapped);
+            }
+        }
+
+        assert_eq!(
+            HoraId::from_base32_check("01K81VBZG2002!"),
+            Err(HoraIdParseError::InvalidCharacter {
+                index: 13,
+                char: '!'
+            })
+        );
+        assert_eq!(
+            HoraId::from_base32_check("01K81VBZG200é"),
+            Err(HoraIdParseError::InvalidCharacter {
+                index: 12,
+                char: 'é'
+            })
+        );
+    }
+
+    #[test]
+    fn base32_aliases() {
+        assert_eq!(
+            HoraId::from_base32("OOOOOOOOOOOIL"),
+            Ok(HoraId::from_u64(33).unwrap())
+        );
+    }
+
+    #[test]
+    fn base32_errors() {
+        assert_eq!(
+            HoraId::from_base32("0000"),
+            Err(HoraIdParseError::InvalidLength {
+                expected: 13,
+                got: 4
+            })
+        );
+        assert_eq!(
+            HoraId::from_base32("000000000000U"),
+            Err(HoraIdParseError::InvalidCharacter {
+                index: 12,
+                char: 'U'
+            })
+        );
+        assert_eq!(
+            HoraId::from_base32("G000000000000"),
+            Err(HoraIdParseError::Overflow)
+        );
+    }
+
+    fn assert_sorted(encode: fn(&HoraId) -> String) {
+        let mut ids: Vec<HoraId> = [0, 1, 31, 32, 61, 62, 1 << 40, u64::MAX - 1, u64::MAX]
+            .into_iter()
+            .map(|n| HoraId::from_u64(n).unwrap())
+            .collect();
+        ids.sort();
+        let encoded: Vec<String> = ids.iter().map(encode).collect();
+        let mut sorted = encoded.clone();
+        sorted.sort();
+        assert_eq!(encoded, sorted);
+    }
+
+    #[test]
+    fn base32_ordering() {
+        assert_sorted(HoraId::to_base32);
+    }
+
+    #[test]
+    fn base62() {
+        let id = HoraId::from_u64(57704410318438402).unwrap();
+        let s = id.to_base62();
+        assert_eq!(s.len(), 11);
+        assert_eq!(HoraId::from_base62(&s), Ok(id));
+
+        assert_eq!(HoraId::from_u64(0).unwrap().to_base62(), "00000000000");
+        assert_eq!(
+            HoraId::from_u64(u64::MAX).unwrap().to_base62(),
+            "LygHa16AHYF"
+        );
+        assert_eq!(
+            HoraId::from_base62("LygHa16AHYF"),
+            Ok(HoraId::from_u64(u64::MAX).unwrap())
+        );
+    }
+
+    #[test]
+    fn base62_errors() {
+        assert_eq!(
+            HoraId::from_base62("0000"),
+            Err(HoraIdParseError::InvalidLength {
+                expected: 11,
+                got: 4
+            })
+        );
+        assert_eq!(
+            HoraId::from_base62("0000000000-"),
+            Err(HoraIdParseError::InvalidCharacter {
+                index: 10,
+                char: '-'
+            })
+        );
+        assert_eq!(
+            HoraId::from_base62("LygHa16AHYG"),
+            Err(HoraIdParseError::Overflow)
+        );
+    }
+
+    #[test]
+    fn base62_ordering() {
+        assert_sorted(HoraId::to_base62);
+    }
+
+    #[test]
+    fn base58() {
+        let id = HoraId::from_u64(57704410318438402).unwrap();
+        let s = id.to_base58();
+        assert_eq!(s.len(), 11);
+        assert_eq!(HoraId::from_base58(&s), Ok(id));
+
+        assert_eq!(HoraId::from_u64(0).unwrap().to_base58(), "11111111111");
+        assert_eq!(
+            HoraId::from_u64(u64::MAX).unwrap().to_base58(),
+            "jpXCZedGfVQ"
+        );
+        assert_eq!(
+            HoraId::from_base58("jpXCZedGfVQ"),
+            Ok(HoraId::from_u64(u64::MAX).unwrap())
+        );
+    }
+
+    #[test]
+    fn base58_errors() {
+        assert_eq!(
+            HoraId::from_base58("1111"),
+            Err(HoraIdParseError::InvalidLength {
+                expected: 11,
+                got: 4
+            })
+        );
+        for c in ['0', 'O', 'I', 'l'] {
+            let s = format!("1111111111{}", c);
+            assert_eq!(
+                HoraId::from_base58(&s),
+                Err(HoraIdParseError::InvalidCharacter { index: 10, char: c })
+            );
+        }
+        assert_eq!(
+            HoraId::from_base58("jpXCZedGfVR"),
+            Err(HoraIdParseError::Overflow)
+        );
+    }
+
+    #[test]
+    fn base58_ordering() {
+        assert_sorted(HoraId::to_base58);
+    }
+
+    #[test]
+    fn base64url() {
+        let id = HoraId::from_u64(57704410318438402).unwrap();
+        let s = id.to_base64url();
+        assert_eq!(s.len(), 11);
+        assert_eq!(HoraId::from_base64url(&s), Ok(id));
+
+        assert_eq!(HoraId::from_u64(0).unwrap().to_base64url(), "AAAAAAAAAAA");
+        assert_eq!(
+            HoraId::from_u64(u64::MAX).unwrap().to_base64url(),
+            "__________8"
+        );
+        assert_eq!(
+            HoraId::from_u64(0xfbff).unwrap().to_base64url(),
+            "AAAAAAAA-_8"
+        );
+        assert_eq!(
+            HoraId::from_base64url("AAAAAAAA-_8"),
+            Ok(HoraId::from_u64(0xfbff).unwrap())
+        );
+    }
+
+    #[test]
+    fn id128_encodings() {
+        type Encode = fn(&HoraId128) -> String;
+        type Decode = fn(&str) -> Result<HoraId128, HoraIdParseError>;
+
+        let max = HoraId128::from(u128::MAX);
+        let id = HoraId128::from(0x00cd_01da_ff01_0203_0405_0607_0809_0a0b);
+        let sorted: [(Encode, Decode); 3] = [
+            (HoraId128::to_base32, HoraId128::from_base32),
+            (HoraId128::to_base58, HoraId128::from_base58),
+            (HoraId128::to_base62, HoraId128::from_base62),
+        ];
+        for (encode, decode) in sorted {
+            assert_eq!(decode(&encode(&id)), Ok(id));
+            assert_eq!(decode(&encode(&max)), Ok(max));
+            assert!(encode(&id) < encode(&max));
+        }
+        assert_eq!(HoraId128::from_base64url(&id.to_base64url()), Ok(id));
+        assert_eq!(max.to_base32(), "7ZZZZZZZZZZZZZZZZZZZZZZZZZ");
+        assert_eq!(max.to_base64url(), "_____________________w");
+        assert_eq!(
+            HoraId128::from_base32("80000000000000000000000000"),
+            Err(HoraIdParseError::Overflow)
+        );
+    }
+
+    #[test]
+    fn base64url_errors() {
+        assert_eq!(
+            HoraId::from_base64url("AAAA"),
+            Err(HoraIdParseError::InvalidLength {
+                expected: 11,
+                got: 4
+            })
+        );
+        assert_eq!(
+            HoraId::from_base64url("AAAAAAAAAA+"),
+            Err(HoraIdParseError::InvalidCharacter {
+                index: 10,
+                char: '+'
+            })
+        );
+        // non-zero padding bits
+        assert_eq!(
+            HoraId::from_base64url("AAAAAAAAAAB"),
+            Err(HoraIdParseError::InvalidCharacter {
+                index: 10,
+                char: 'B'
+            })
+        );
+    }
+}
@@ -0,0 +1,305 @@
+use core::fmt;
+use core::time::Duration;
+
+/// Errors returned by [HoraGenerator](crate::HoraGenerator) and [HoraId](crate::HoraId)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HoraError {
+    /// The system clock is set to a time before the HoraID epoch
+    ClockBeforeEpoch,
+    /// All sequence numbers for the current tick have been used
+    SequenceExhausted,
+    /// The system clock moved backwards since the last ID was generated
+    ClockMovedBackwards {
+        /// How far the clock is behind the last generated timestamp
+        behind: Duration,
+    },
+    /// The timestamp is before the epoch or too far in the future to fit in an ID
+    TimestampOutOfRange,
+    /// The machine ID does not fit in the available machine bits
+    InvalidMachineId {
+        /// The rejected machine ID
+        machine_id: u64,
+        /// The largest machine ID that is accepted
+        max: u64,
+    },
+    /// The datacenter ID does not fit in the datacenter bits of the
+    /// [HoraLayout](crate::HoraLayout)
+    InvalidDatacenterId {
+        /// The rejected datacenter ID
+        datacenter_id: u64,
+        /// The largest datacenter ID that is accepted
+        max: u64,
+    },
+    /// The worker ID does not fit in the worker bits of the [HoraLayout](crate::HoraLayout)
+    InvalidWorkerId {
+        /// The rejected worker ID
+        worker_id: u64,
+        /// The largest worker ID that is accepted
+        max: u64,
+    },
+    /// The tenant does not fit in the tenant bits of the [HoraLayout](crate::HoraLayout)
+    InvalidTenant {
+        /// The rejected tenant
+        tenant: u64,
+        /// The largest tenant that is accepted
+        max: u64,
+    },
+    /// The [HoraId](crate::HoraId) has a format version this version of the crate can't decode
+    UnsupportedVersion {
+        /// The version stored in the ID
+        version: u8,
+    },
+    /// A [HoraGeneratorPool](crate::HoraGeneratorPool) can't be split into this many shards
+    InvalidShardCount {
+        /// The rejected number of shards
+        shards: u64,
+        /// The most shards the layout allows
+        max: u64,
+    },
+    /// More threads use a thread-local generator at the same time than it was created for
+    PartitionsExhausted {
+        /// The number of threads the generator was created for
+        partitions: u64,
+    },
+    /// A string could not be parsed as a [HoraId](crate::HoraId)
+    ParseError(HoraIdParseError),
+    /// The global generator was used before [init](crate::init) was called
+    NotInitialized,
+    /// [init](crate::init) was called more than once
+    AlreadyInitialized,
+    /// An ID of another format does not contain an embedded [HoraId](crate::HoraId)
+    IncompatibleId,
+    /// Saved [GeneratorState](crate::GeneratorState) is corrupt or was saved with another epoch
+    InvalidState,
+    /// Every machine ID is already claimed by another instance
+    MachineIdsExhausted,
+    /// The [HoraId](crate::HoraId) was created before the one it is compared with
+    EarlierId {
+        /// How much earlier it was created
+        by: Duration,
+    },
+    /// The tag of a signed [HoraId](crate::HoraId) doesn't match, it was forged or changed
+    InvalidSignature,
+    /// The service coordinating machine IDs returned an error
+    #[cfg(feature = "alloc")]
+    Coordination(alloc::string::String),
+    /// The information a machine ID is derived from is not available
+    #[cfg(feature = "alloc")]
+    MachineIdUnavailable(alloc::string::String),
+    /// A required environment variable is not set
+    #[cfg(feature = "std")]
+    MissingEnvVar {
+        /// Name of the variable
+        name: std::string::String,
+    },
+    /// An environment variable is set to a value that can't be used
+    #[cfg(feature = "std")]
+    InvalidEnvVar {
+        /// Name of the variable
+        name: std::string::String,
+        /// The rejected value
+        value: std::string::String,
+        /// What the value has to be
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for HoraError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HoraError::ClockBeforeEpoch => {
+                write!(f, "system clock is set to a time before the HoraID epoch")
+            }
+            HoraError::SequenceExhausted => {
+                write!(f, "sequence numbers for the current tick are exhausted")
+            }
+            HoraError::ClockMovedBackwards { behind } => {
+                write!(f, "system clock moved backwards by {:?}", behind)
+            }
+            HoraError::TimestampOutOfRange => {
+                write!(f, "timestamp is outside the range of a HoraId")
+            }
+            HoraError::InvalidMachineId { machine_id, max } => {
+                write!(
+                    f,
+                    "invalid machine ID {}: must be at most {}",
+                    machine_id, max
+                )
+            }
+            HoraError::InvalidDatacenterId { datacenter_id, max } => {
+                write!(
+                    f,
+                    "invalid datacenter ID {}: must be at most {}",
+                    datacenter_id, max
+                )
+            }
+            HoraError::InvalidWorkerId { worker_id, max } => {
+                write!(
+                    f,
+                    "invalid worker ID {}: must be at most {}",
+                    worker_id, max
+                )
+            }
+            HoraError::InvalidTenant { tenant, max } => {
+                write!(f, "invalid tenant {}: must be at most {}", tenant, max)
+            }
+            HoraError::UnsupportedVersion { version } => {
+                write!(f, "unsupported HoraId format version {}", version)
+            }
+            HoraError::InvalidShardCount { shards, max } => {
+                write!(
+                    f,
+                    "invalid shard count {}: must be from 1 to {}",
+                    shards, max
+                )
+            }
+            HoraError::PartitionsExhausted { partitions } => write!(
+                f,
+                "all {} partitions of the generator are used by other threads",
+                partitions
+            ),
+            HoraError::ParseError(err) => write!(f, "failed to parse HoraId: {}", err),
+            HoraError::NotInitialized => write!(f, "global generator is not initialized"),
+            HoraError::AlreadyInitialized => {
+                write!(f, "global generator is already initialized")
+            }
+            HoraError::IncompatibleId => write!(f, "ID does not contain an embedded HoraId"),
+            HoraError::InvalidState => write!(f, "saved generator state is invalid"),
+            HoraError::MachineIdsExhausted => write!(f, "no unclaimed machine ID is available"),
+            HoraError::EarlierId { by } => {
+                write!(f, "ID was created {:?} before the other ID", by)
+            }
+            HoraError::InvalidSignature => write!(f, "signature of the ID is invalid"),
+            #[cfg(feature = "alloc")]
+            HoraError::Coordination(msg) => write!(f, "machine ID coordination failed: {}", msg),
+            #[cfg(feature = "alloc")]
+            HoraError::MachineIdUnavailable(msg) => {
+                write!(f, "machine ID can't be derived: {}", msg)
+            }
+            #[cfg(feature = "std")]
+            HoraError::MissingEnvVar { name } => {
+                write!(f, "environment variable {} is not set", name)
+            }
+            #[cfg(feature = "std")]
+            HoraError::InvalidEnvVar {
+                name,
+                value,
+                expected,
+            } => write!(
+                f,
+                "environment variable {} is {:?}, expected {}",
+                name, value, expected
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HoraError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HoraError::ParseError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<HoraIdParseError> for HoraError {
+    fn from(err: HoraIdParseError) -> Self {
+        HoraError::ParseError(err)
+    }
+}
+
+#[cfg(any(feature = "etcd", feature = "segment-http"))]
+impl From<ureq::Error> for HoraError {
+    fn from(err: ureq::Error) -> Self {
+        HoraError::Coordination(err.to_string())
+    }
+}
+
+/// Error returned when parsing a [HoraId](crate::HoraId) from a string fails
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HoraIdParseError {
+    /// The input does not have the length of the encoding or byte representation
+    InvalidLength {
+        /// Number of characters or bytes the encoding requires
+        expected: usize,
+        /// Number of bytes in the input
+        got: usize,
+    },
+    /// The input contains a character that is not part of the encoding alphabet
+    InvalidCharacter {
+        /// Byte offset of the character in the input
+        index: usize,
+        /// The rejected character
+        char: char,
+    },
+    /// The decoded value does not fit in 64 bits
+    Overflow,
+    /// The input does not start with the expected prefix followed by `_`
+    InvalidPrefix,
+    /// The check character does not match the rest of the input, so it was mistyped
+    ChecksumMismatch,
+}
+
+impl fmt::Display for HoraIdParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HoraIdParseError::InvalidLength { expected, got } => {
+                write!(f, "invalid length: expected {}, got {}", expected, got)
+            }
+            HoraIdParseError::InvalidCharacter { index, char } => {
+                write!(f, "invalid character {:?} at index {}", char, index)
+            }
+            HoraIdParseError::Overflow => write!(f, "value does not fit in 64 bits"),
+            HoraIdParseError::InvalidPrefix => write!(f, "missing or unexpected prefix"),
+            HoraIdParseError::ChecksumMismatch => {
+                write!(f, "check character does not match, the ID was mistyped")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HoraIdParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HoraId;
+    use std::error::Error;
+
+    #[test]
+    fn parse_error_conversion() {
+        fn parse(s: &str) -> Result<HoraId, HoraError> {
+            Ok(s.parse::<HoraId>()?)
+        }
+        let err = parse("00cd").unwrap_err();
+        assert_eq!(
+            err,
+            HoraError::ParseError(HoraIdParseError::InvalidLength {
+                expected: 16,
+                got: 4
+            })
+        );
+        assert!(err.source().is_some());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            HoraError::ClockBeforeEpoch.to_string(),
+            "system clock is set to a time before the HoraID epoch"
+        );
+        assert_eq!(
+            HoraError::ParseError(HoraIdParseError::InvalidCharacter {
+                index: 3,
+                char: 'g'
+            })
+            .to_string(),
+            "failed to parse HoraId: invalid character 'g' at index 3"
+        );
+    }
+}
@@ -0,0 +1,441 @@
+//! Machine ID registration coordinated through etcd
+//!
+//! Every instance grants an etcd lease and registers an ephemeral key for one of the 256 machine
+//! IDs with a transaction that only succeeds if the key doesn't exist yet, so no two live
+//! instances can hold the same ID. A background thread keeps the lease alive. When an instance
+//! stops or crashes the lease is revoked or expires, which deletes the key.
+//!
+//! Requests go to the JSON gateway that etcd serves on its client port, so no gRPC toolchain
+//! is needed.
+
+use crate::{HoraError, HoraGenerator, MachineIdProvider};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use ureq::Agent;
+
+/// Claims a unique machine ID by registering an ephemeral key in etcd
+///
+/// The claimed ID is stored as `<prefix><machine_id>`, attached to a lease that expires after
+/// the TTL unless it is kept alive. Keep-alives are sent every third of the TTL on a background
+/// thread until [EtcdMachineIdAllocator::release] is called or the allocator is dropped.
+///
+/// ## Usage
+/// ```no_run
+/// use hora_id::EtcdMachineIdAllocator;
+/// use std::time::Duration;
+///
+/// let allocator = EtcdMachineIdAllocator::new("http://etcd:2379").with_ttl(Duration::from_secs(10));
+///
+/// let mut generator = allocator.generator().unwrap();
+/// let id = generator.next();
+/// assert_eq!(id.machine_id(), allocator.machine_id().unwrap());
+/// ```
+pub struct EtcdMachineIdAllocator {
+    agent: Agent,
+    endpoint: String,
+    key_prefix: String,
+    ttl: Duration,
+    lease: Mutex<Option<Lease>>,
+}
+
+struct Lease {
+    machine_id: u8,
+    lease_id: String,
+    state: Arc<LeaseState>,
+    stop: Sender<()>,
+    keep_alive: JoinHandle<()>,
+}
+
+/// Outcome of the keep-alives, shared with the keep-alive thread
+struct LeaseState {
+    /// Cleared when a keep-alive finds the lease expired
+    held: AtomicBool,
+    /// When the last successful keep-alive was sent, the lease expires one TTL after it
+    renewed: Mutex<Instant>,
+}
+
+impl LeaseState {
+    fn is_held(&self, ttl: Duration) -> bool {
+        let renewed = *self.renewed.lock().unwrap_or_else(PoisonError::into_inner);
+        self.held.load(Ordering::Acquire) && renewed.elapsed() < ttl
+    }
+}
+
+impl EtcdMachineIdAllocator {
+    /// Create an allocator for the etcd client URL `endpoint`, using keys prefixed with
+    /// `/hora_id/machine/` and a 30 second TTL
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            agent: Agent::new(),
+            endpoint: endpoint.into().trim_end_matches('/').to_owned(),
+            key_prefix: "/hora_id/machine/".to_owned(),
+            ttl: Duration::from_secs(30),
+            lease: Mutex::new(None),
+        }
+    }
+
+    /// Use `prefix` for the registered keys, to run several independent fleets on one cluster
+    pub fn with_key_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.key_prefix = prefix.into();
+        self
+    }
+
+    /// Set how long a registration survives without keep-alives, rounded up to whole seconds
+    ///
+    /// A crashed instance keeps its machine ID for up to this long.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Claim an unused machine ID and start keeping its lease alive
+    ///
+    /// Returns the ID that is already held if called again before [release](Self::release).
+    ///
+    /// ## Fail condition
+    /// If etcd can't be reached or all 256 machine IDs are registered
+    pub fn acquire(&self) -> Result<u8, HoraError> {
+        let mut lease = self.lease.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(lease) = lease.as_ref() {
+            return Ok(lease.machine_id);
+        }
+
+        let ttl_secs = self.ttl.as_secs() + u64::from(self.ttl.subsec_nanos() > 0);
+        let sent = Instant::now();
+        let granted = post(
+            &self.agent,
+            &self.endpoint,
+            "/v3/lease/grant",
+            json!({ "TTL": ttl_secs.max(1) }),
+        )?;
+        let lease_id = match &granted["ID"] {
+            Value::String(id) => id.clone(),
+            other => other.to_string(),
+        };
+
+        // start at a random ID so that instances starting together rarely race for the same key
+        let start = rand::random::<u8>();
+        for offset in 0..=u8::MAX {
+            let machine_id = start.wrapping_add(offset);
+            let key = STANDARD.encode(format!("{}{}", self.key_prefix, machine_id));
+            let txn = post(
+                &self.agent,
+                &self.endpoint,
+                "/v3/kv/txn",
+                json!({
+                    "compare": [{ "key": key, "target": "CREATE", "create_revision": "0" }],
+                    "success": [{ "request_put": {
+                        "key": key,
+                        "value": STANDARD.encode(&lease_id),
+                        "lease": lease_id,
+                    } }],
+                }),
+            );
+            match txn {
+                Ok(txn) if txn["succeeded"] == true => {
+                    *lease = Some(self.start_keep_alive(sent, machine_id, lease_id));
+                    return Ok(machine_id);
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    let _ = revoke(&self.agent, &self.endpoint, &lease_id);
+                    return Err(err);
+                }
+            }
+        }
+        revoke(&self.agent, &self.endpoint, &lease_id)?;
+        Err(HoraError::MachineIdsExhausted)
+    }
+
+    /// Claim a machine ID and create a [HoraGenerator] using it
+    ///
+    /// ## Fail condition
+    /// If [acquire](Self::acquire) fails or the system time is incorrect
+    pub fn generator(&self) -> Result<HoraGenerator, HoraError> {
+        HoraGenerator::new(self.acquire()?)
+    }
+
+    /// The machine ID currently registered, if any
+    pub fn machine_id(&self) -> Option<u8> {
+        let lease = self.lease.lock().unwrap_or_else(PoisonError::into_inner);
+        lease.as_ref().map(|lease| lease.machine_id)
+    }
+
+    /// Whether the lease is still alive
+    ///
+    /// Returns false if no ID was acquired, a keep-alive found that the lease expired, for
+    /// example after a long pause, or no keep-alive succeeded for a TTL, for example during a
+    /// network partition. etcd may have revoked the lease at that point, so IDs generated after
+    /// it may collide with another instance.
+    pub fn is_held(&self) -> bool {
+        let lease = self.lease.lock().unwrap_or_else(PoisonError::into_inner);
+        lease
+            .as_ref()
+            .is_some_and(|lease| lease.state.is_held(self.ttl))
+    }
+
+    /// Stop the keep-alives and revoke the lease, which deletes the registered key
+    ///
+    /// Does nothing if no ID is registered.
+    pub fn release(&self) -> Result<(), HoraError> {
+        let lease = self
+            .lease
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        let Some(lease) = lease else {
+            return Ok(());
+        };
+        drop(lease.stop);
+        let _ = lease.keep_alive.join();
+        revoke(&self.agent, &self.endpoint, &lease.lease_id)
+    }
+
+    fn start_keep_alive(&self, granted: Instant, machine_id: u8, lease_id: String) -> Lease {
+        let state = Arc::new(LeaseState {
+            held: AtomicBool::new(true),
+            renewed: Mutex::new(granted),
+        });
+        let (stop, stopped) = mpsc::channel::<()>();
+        let ttl = self.ttl;
+        let keep_alive = {
+            let state = Arc::clone(&state);
+            let agent = self.agent.clone();
+            let endpoint = self.endpoint.clone();
+            let lease_id = lease_id.clone();
+            thread::spawn(move || {
+                // only a disconnect of the sender stops the loop, no message is ever sent
+                while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(ttl / 3) {
+                    let body = json!({ "ID": lease_id });
+                    let sent = Instant::now();
+                    // on errors retry on the next interval, `is_held` turns false once the TTL
+                    // passed without a keep-alive
+                    if let Ok(res) = post(&agent, &endpoint, "/v3/lease/keepalive", body) {
+                        // an expired lease is reported without a TTL or with a TTL of 0
+                        let ttl = &res["result"]["TTL"];
+                        let alive = !ttl.is_null() && *ttl != "0" && *ttl != 0;
+                        if alive {
+                            *state.renewed.lock().unwrap_or_else(PoisonError::into_inner) = sent;
+                        }
+                        state.held.store(alive, Ordering::Release);
+                    }
+                }
+            })
+        };
+        Lease {
+            machine_id,
+            lease_id,
+            state,
+            stop,
+            keep_alive,
+        }
+    }
+}
+
+impl MachineIdProvider for EtcdMachineIdAllocator {
+    fn acquire(&self) -> Result<u8, HoraError> {
+        EtcdMachineIdAllocator::acquire(self)
+    }
+
+    fn release(&self) -> Result<(), HoraError> {
+        EtcdMachineIdAllocator::release(self)
+    }
+}
+
+impl Drop for EtcdMachineIdAllocator {
+    fn drop(&mut self) {
+        let _ = self.release();
+    }
+}
+
+fn post(agent: &Agent, endpoint: &str, path: &str, body: Value) -> Result<Value, HoraError> {
+    let res = agent
+        .post(&format!("{}{}", endpoint, path))
+        .send_json(body)?;
+    res.into_json()
+        .map_err(|err| HoraError::Coordination(err.to_string()))
+}
+
+fn revoke(agent: &Agent, endpoint: &str, lease_id: &str) -> Result<(), HoraError> {
+    post(
+        agent,
+        endpoint,
+        "/v3/lease/revoke",
+        json!({ "ID": lease_id }),
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    type Requests = Arc<Mutex<Vec<(String, Value)>>>;
+
+    /// Serve the etcd JSON gateway with `respond` and record every request
+    ///
+    /// A `null` response is sent as a 503 error.
+    fn serve(respond: fn(&str, &Value, usize) -> Value) -> (String, Requests) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Requests::default();
+        let recorded = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let recorded = Arc::clone(&recorded);
+                thread::spawn(move || {
+                    let mut stream = stream.unwrap();
+                    stream.set_nodelay(true).unwrap();
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut line = String::new();
+                    while reader.read_line(&mut line).unwrap() > 0 {
+                        let path = line.split(' ').nth(1).unwrap().to_owned();
+                        let mut len = 0;
+                        loop {
+                            line.clear();
+                            reader.read_line(&mut line).unwrap();
+                            if line == "\r\n" {
+                                break;
+                            }
+                            if let Some(value) = line.to_lowercase().strip_prefix("content-length:")
+                            {
+                                len = value.trim().parse().unwrap();
+                            }
+                        }
+                        let mut body = vec![0; len];
+                        reader.read_exact(&mut body).unwrap();
+                        let body: Value = serde_json::from_slice(&body).unwrap();
+
+                        let mut requests = recorded.lock().unwrap();
+                        let count = requests.iter().filter(|(p, _)| *p == path).count();
+                        let res = respond(&path, &body, count).to_string();
+                        requests.push((path, body));
+                        drop(requests);
+                        let status = if res == "null" {
+                            "503 Service Unavailable"
+                        } else {
+                            "200 OK"
+                        };
+                        let res = format!(
+                            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                            status,
+                            res.len(),
+                            res
+                        );
+                        stream.write_all(res.as_bytes()).unwrap();
+                        line.clear();
+                    }
+                });
+            }
+        });
+        (endpoint, requests)
+    }
+
+    fn paths(requests: &Requests) -> Vec<String> {
+        let requests = requests.lock().unwrap();
+        requests.iter().map(|(path, _)| path.clone()).collect()
+    }
+
+    #[test]
+    fn acquire_and_release() {
+        let (endpoint, requests) = serve(|path, _, count| match path {
+            "/v3/lease/grant" => json!({ "ID": "7587", "TTL": "30" }),
+            // the first key is taken by another instance
+            "/v3/kv/txn" => json!({ "succeeded": count > 0 }),
+            _ => json!({}),
+        });
+        let allocator = EtcdMachineIdAllocator::new(endpoint);
+        let machine_id = allocator.acquire().unwrap();
+        assert_eq!(allocator.acquire(), Ok(machine_id));
+        assert_eq!(allocator.machine_id(), Some(machine_id));
+        assert!(allocator.is_held());
+
+        {
+            let requests = requests.lock().unwrap();
+            let (_, txn) = &requests[2];
+            let key = STANDARD.encode(format!("/hora_id/machine/{}", machine_id));
+            assert_eq!(txn["compare"][0]["key"], key);
+            assert_eq!(txn["success"][0]["request_put"]["key"], key);
+            assert_eq!(txn["success"][0]["request_put"]["lease"], "7587");
+        }
+
+        allocator.release().unwrap();
+        assert_eq!(allocator.machine_id(), None);
+        assert_eq!(
+            paths(&requests),
+            [
+                "/v3/lease/grant",
+                "/v3/kv/txn",
+                "/v3/kv/txn",
+                "/v3/lease/revoke"
+            ]
+        );
+        assert_eq!(requests.lock().unwrap()[3].1, json!({ "ID": "7587" }));
+    }
+
+    #[test]
+    fn exhausted() {
+        let (endpoint, requests) = serve(|path, _, _| match path {
+            "/v3/lease/grant" => json!({ "ID": "1", "TTL": "30" }),
+            "/v3/kv/txn" => json!({ "succeeded": false }),
+            _ => json!({}),
+        });
+        let allocator = EtcdMachineIdAllocator::new(endpoint);
+        assert_eq!(allocator.acquire(), Err(HoraError::MachineIdsExhausted));
+        let paths = paths(&requests);
+        assert_eq!(paths.len(), 258);
+        assert_eq!(paths.last().unwrap(), "/v3/lease/revoke");
+    }
+
+    #[test]
+    fn lease_lost() {
+        let (endpoint, _) = serve(|path, _, _| match path {
+            "/v3/lease/grant" => json!({ "ID": "1", "TTL": "1" }),
+            "/v3/kv/txn" => json!({ "succeeded": true }),
+            _ => json!({ "result": { "ID": "1" } }),
+        });
+        let allocator = EtcdMachineIdAllocator::new(endpoint).with_ttl(Duration::from_millis(300));
+        allocator.acquire().unwrap();
+        thread::sleep(Duration::from_millis(250));
+        assert!(!allocator.is_held());
+    }
+
+    #[test]
+    fn keep_alive_failing() {
+        let (endpoint, requests) = serve(|path, _, _| match path {
+            "/v3/lease/grant" => json!({ "ID": "1", "TTL": "1" }),
+            "/v3/kv/txn" => json!({ "succeeded": true }),
+            // etcd can't be reached, for example during a partition
+            "/v3/lease/keepalive" => Value::Null,
+            _ => json!({}),
+        });
+        let allocator = EtcdMachineIdAllocator::new(endpoint).with_ttl(Duration::from_millis(300));
+        allocator.acquire().unwrap();
+        assert!(allocator.is_held());
+        thread::sleep(Duration::from_millis(400));
+        assert!(paths(&requests).contains(&"/v3/lease/keepalive".to_owned()));
+        assert!(!allocator.is_held());
+        assert!(allocator.machine_id().is_some());
+    }
+
+    #[test]
+    fn connection_error() {
+        // port 1 is reserved, so connecting fails immediately
+        let allocator = EtcdMachineIdAllocator::new("http://127.0.0.1:1");
+        assert!(matches!(
+            allocator.acquire(),
+            Err(HoraError::Coordination(_))
+        ));
+        assert!(allocator.generator().is_err());
+        assert!(!allocator.is_held());
+        assert_eq!(allocator.release(), Ok(()));
+    }
+}
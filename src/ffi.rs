@@ -0,0 +1,180 @@
+//! C ABI for embedding the generator in C, C++ and Go (cgo) services
+//!
+//! The declarations are in `include/hora_id.h`, generated with
+//! `cbindgen --config cbindgen.toml --output include/hora_id.h`. Build a library to link against
+//! with `cargo rustc --release --lib --features ffi --crate-type staticlib` (or `cdylib`).
+//!
+//! IDs cross the boundary as `uint64_t`. Every function that can fail returns a [HoraStatus]
+//! and writes its result through an out pointer, which is left untouched on failure.
+//!
+//! ```c
+//! #include "hora_id.h"
+//!
+//! HoraGeneratorHandle *generator = hora_generator_new(1);
+//! uint64_t id;
+//! if (generator && hora_next(generator, &id) == HORA_STATUS_OK) {
+//!     char hex[HORA_HEX_LEN + 1];
+//!     hora_to_hex(id, hex);
+//!     printf("%s\n", hex);
+//! }
+//! hora_generator_free(generator);
+//! ```
+
+use crate::{hex, HoraId, SyncHoraGenerator};
+use core::ffi::{c_char, CStr};
+
+/// Length of the hexadecimal form, without the terminating NUL
+pub const HORA_HEX_LEN: usize = 16;
+
+/// Result of the fallible FFI functions
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HoraStatus {
+    /// The result was written to the out pointer
+    Ok = 0,
+    /// A pointer argument was NULL
+    NullPointer = 1,
+    /// The string isn't a valid hexadecimal HoraID
+    InvalidId = 2,
+    /// The clock is before the HoraID epoch or moved backwards too far
+    ClockError = 3,
+}
+
+/// Thread-safe generator, shared between threads through the same pointer
+pub struct HoraGeneratorHandle {
+    generator: SyncHoraGenerator,
+}
+
+/// Create a generator for `machine_id`, returns NULL if the system clock is before the HoraID
+/// epoch
+///
+/// Release it with [hora_generator_free].
+#[no_mangle]
+pub extern "C" fn hora_generator_new(machine_id: u8) -> *mut HoraGeneratorHandle {
+    match SyncHoraGenerator::new(machine_id) {
+        Ok(generator) => Box::into_raw(Box::new(HoraGeneratorHandle { generator })),
+        Err(_) => core::ptr::null_mut(),
+    }
+}
+
+/// Release a generator created by [hora_generator_new], NULL is ignored
+///
+/// # Safety
+/// `generator` must be NULL or returned by [hora_generator_new] and not freed before, and no
+/// other thread may use it anymore.
+#[no_mangle]
+pub unsafe extern "C" fn hora_generator_free(generator: *mut HoraGeneratorHandle) {
+    if !generator.is_null() {
+        // SAFETY: the pointer came from `Box::into_raw` in `hora_generator_new`
+        drop(unsafe { Box::from_raw(generator) });
+    }
+}
+
+/// Generate a new ID into `out`
+///
+/// # Safety
+/// `generator` must be NULL or a live generator from [hora_generator_new], and `out` must be
+/// NULL or valid for writing a `uint64_t`.
+#[no_mangle]
+pub unsafe extern "C" fn hora_next(
+    generator: *const HoraGeneratorHandle,
+    out: *mut u64,
+) -> HoraStatus {
+    // SAFETY: the caller guarantees both pointers are NULL or valid
+    let (Some(handle), Some(out)) = (unsafe { generator.as_ref() }, unsafe { out.as_mut() }) else {
+        return HoraStatus::NullPointer;
+    };
+    match handle.generator.try_next() {
+        Ok(id) => {
+            *out = id.to_u64();
+            HoraStatus::Ok
+        }
+        Err(_) => HoraStatus::ClockError,
+    }
+}
+
+/// Write the 16 character hexadecimal form of `id` and a terminating NUL into `out`
+///
+/// # Safety
+/// `out` must be NULL or valid for writing `HORA_HEX_LEN + 1` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn hora_to_hex(id: u64, out: *mut c_char) -> HoraStatus {
+    if out.is_null() {
+        return HoraStatus::NullPointer;
+    }
+    let mut buf = [0u8; HORA_HEX_LEN + 1];
+    let (digits, _) = buf.split_first_chunk_mut().expect("buffer holds 16 digits");
+    hex::encode_chunk(&id.to_be_bytes(), digits);
+    // SAFETY: the caller guarantees `out` has room for the digits and the NUL
+    unsafe { core::ptr::copy_nonoverlapping(buf.as_ptr(), out.cast(), buf.len()) };
+    HoraStatus::Ok
+}
+
+/// Parse the NUL terminated hexadecimal form in `hex` into `out`
+///
+/// # Safety
+/// `hex` must be NULL or a NUL terminated string, and `out` must be NULL or valid for writing
+/// a `uint64_t`.
+#[no_mangle]
+pub unsafe extern "C" fn hora_parse(hex: *const c_char, out: *mut u64) -> HoraStatus {
+    if hex.is_null() {
+        return HoraStatus::NullPointer;
+    }
+    // SAFETY: the caller guarantees `out` is NULL or valid
+    let Some(out) = (unsafe { out.as_mut() }) else {
+        return HoraStatus::NullPointer;
+    };
+    // SAFETY: the caller guarantees `hex` is NUL terminated
+    let hex = unsafe { CStr::from_ptr(hex) };
+    match hex.to_str().ok().and_then(|s| s.parse::<HoraId>().ok()) {
+        Some(id) => {
+            *out = id.to_u64();
+            HoraStatus::Ok
+        }
+        None => HoraStatus::InvalidId,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ptr;
+
+    #[test]
+    fn hex_round_trip() {
+        let mut id = 0;
+        let status = unsafe { hora_parse(c"00cd01daff010002".as_ptr(), &mut id) };
+        assert_eq!(status, HoraStatus::Ok);
+        assert_eq!(id, 57704410318438402);
+
+        let mut buf: [c_char; HORA_HEX_LEN + 1] = [1; HORA_HEX_LEN + 1];
+        assert_eq!(unsafe { hora_to_hex(id, buf.as_mut_ptr()) }, HoraStatus::Ok);
+        let hex = unsafe { CStr::from_ptr(buf.as_ptr()) };
+        assert_eq!(hex, c"00cd01daff010002");
+
+        let status = unsafe { hora_parse(c"xyz".as_ptr(), &mut id) };
+        assert_eq!(status, HoraStatus::InvalidId);
+        assert_eq!(id, 57704410318438402);
+        let status = unsafe { hora_parse(ptr::null(), &mut id) };
+        assert_eq!(status, HoraStatus::NullPointer);
+        let status = unsafe { hora_to_hex(id, ptr::null_mut()) };
+        assert_eq!(status, HoraStatus::NullPointer);
+    }
+
+    #[test]
+    fn generator() {
+        let generator = hora_generator_new(1);
+        assert!(!generator.is_null());
+        let (mut first, mut second) = (0, 0);
+        assert_eq!(unsafe { hora_next(generator, &mut first) }, HoraStatus::Ok);
+        assert_eq!(unsafe { hora_next(generator, &mut second) }, HoraStatus::Ok);
+        assert!(first < second);
+        assert_eq!(HoraId::from_bytes(first.to_be_bytes()).machine_id(), 1);
+        assert_eq!(
+            unsafe { hora_next(generator, ptr::null_mut()) },
+            HoraStatus::NullPointer
+        );
+        unsafe { hora_generator_free(generator) };
+        unsafe { hora_generator_free(ptr::null_mut()) };
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{HoraError, HoraId, SyncHoraGenerator};
+use std::sync::OnceLock;
+
+static GENERATOR: OnceLock<SyncHoraGenerator> = OnceLock::new();
+
+/// Initialize the process-wide generator used by [generate]
+///
+/// ## Fail condition
+/// If the global generator was already initialized or the system time is incorrect
+///
+/// ## Usage
+/// ```no_run
+/// let machine_id = 1; // You'll ideally get this from environment variable or configuration
+/// hora_id::init(machine_id).unwrap();
+///
+/// let id = hora_id::generate();
+/// ```
+pub fn init(machine_id: u8) -> Result<(), HoraError> {
+    let generator = SyncHoraGenerator::new(machine_id)?;
+    GENERATOR
+        .set(generator)
+        .map_err(|_| HoraError::AlreadyInitialized)
+}
+
+/// Generate a new [HoraId] with the process-wide generator
+///
+/// ## Panics
+/// If [init] has not been called yet or the system time is incorrect.
+/// Use [try_generate] to handle these cases.
+pub fn generate() -> HoraId {
+    let generator = GENERATOR
+        .get()
+        .expect("hora_id::init must be called before hora_id::generate");
+    generator.next()
+}
+
+/// Generate a new [HoraId] with the process-wide generator
+///
+/// ## Fail condition
+/// If [init] has not been called yet or the system time is incorrect
+pub fn try_generate() -> Result<HoraId, HoraError> {
+    let generator = GENERATOR.get().ok_or(HoraError::NotInitialized)?;
+    generator.try_next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn global() {
+        assert_eq!(try_generate(), Err(HoraError::NotInitialized));
+        init(7).unwrap();
+        assert_eq!(init(8), Err(HoraError::AlreadyInitialized));
+
+        let id1 = generate();
+        let id2 = try_generate().unwrap();
+        assert_eq!(id1.machine_id(), 7);
+        assert!(id1 < id2);
+    }
+}
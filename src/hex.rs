@@ -0,0 +1,191 @@
+//! Hexadecimal encoding and decoding of 8 bytes at a time
+//!
+//! With the `simd` feature, x86_64 uses SSE2 and aarch64 uses NEON. Both are part of the baseline
+//! of their target, so no runtime detection is needed. All other builds use lookup tables.
+
+#[cfg(not(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64"))))]
+mod imp {
+    use crate::encoding::HEX_LOWER;
+
+    /// Marks bytes that aren't hexadecimal digits in [HEX_VALUES]
+    const INVALID: u8 = 0xff;
+
+    /// Two lowercase hexadecimal digits for every byte value
+    const HEX_PAIRS: [[u8; 2]; 256] = {
+        let mut pairs = [[0; 2]; 256];
+        let mut byte = 0;
+        while byte < 256 {
+            pairs[byte] = [HEX_LOWER[byte >> 4], HEX_LOWER[byte & 0xf]];
+            byte += 1;
+        }
+        pairs
+    };
+
+    /// Value of every ASCII hexadecimal digit in either case, [INVALID] for all other bytes
+    const HEX_VALUES: [u8; 256] = {
+        let mut values = [INVALID; 256];
+        let mut digit = 0;
+        while digit < 16 {
+            values[HEX_LOWER[digit] as usize] = digit as u8;
+            values[HEX_LOWER[digit].to_ascii_uppercase() as usize] = digit as u8;
+            digit += 1;
+        }
+        values
+    };
+
+    pub(crate) fn encode_chunk(bytes: &[u8; 8], out: &mut [u8; 16]) {
+        for (byte, pair) in bytes.iter().zip(out.chunks_exact_mut(2)) {
+            pair.copy_from_slice(&HEX_PAIRS[*byte as usize]);
+        }
+    }
+
+    pub(crate) fn decode_chunk(chars: &[u8; 16]) -> Option<[u8; 8]> {
+        let mut bytes = [0; 8];
+        let mut invalid = 0;
+        for (byte, pair) in bytes.iter_mut().zip(chars.chunks_exact(2)) {
+            let high = HEX_VALUES[pair[0] as usize];
+            let low = HEX_VALUES[pair[1] as usize];
+            invalid |= high | low;
+            *byte = (high << 4) | low;
+        }
+        // valid digits never set the high bit
+        (invalid & 0x80 == 0).then_some(bytes)
+    }
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod imp {
+    use core::arch::x86_64::*;
+
+    pub(crate) fn encode_chunk(bytes: &[u8; 8], out: &mut [u8; 16]) {
+        // SAFETY: SSE2 is always available on x86_64, the loads and stores are unaligned and
+        // within the bounds of `bytes` and `out`
+        unsafe {
+            let input = _mm_loadl_epi64(bytes.as_ptr() as *const __m128i);
+            let mask = _mm_set1_epi8(0x0f);
+            let high = _mm_and_si128(_mm_srli_epi16::<4>(input), mask);
+            let low = _mm_and_si128(input, mask);
+            let nibbles = _mm_unpacklo_epi8(high, low);
+            // digits above 9 are moved from after '9' to 'a'
+            let letters = _mm_cmpgt_epi8(nibbles, _mm_set1_epi8(9));
+            let ascii = _mm_add_epi8(nibbles, _mm_set1_epi8(b'0' as i8));
+            let ascii = _mm_add_epi8(
+                ascii,
+                _mm_and_si128(letters, _mm_set1_epi8((b'a' - b'0' - 10) as i8)),
+            );
+            _mm_storeu_si128(out.as_mut_ptr() as *mut __m128i, ascii);
+        }
+    }
+
+    pub(crate) fn decode_chunk(chars: &[u8; 16]) -> Option<[u8; 8]> {
+        let mut bytes = [0; 8];
+        // SAFETY: SSE2 is always available on x86_64, the loads and stores are unaligned and
+        // within the bounds of `chars` and `bytes`
+        unsafe {
+            let input = _mm_loadu_si128(chars.as_ptr() as *const __m128i);
+            // the comparisons are signed, so non-ASCII bytes are below every digit
+            let is_digit = _mm_and_si128(
+                _mm_cmpgt_epi8(input, _mm_set1_epi8(b'0' as i8 - 1)),
+                _mm_cmplt_epi8(input, _mm_set1_epi8(b'9' as i8 + 1)),
+            );
+            let lower = _mm_or_si128(input, _mm_set1_epi8(0x20));
+            let is_letter = _mm_and_si128(
+                _mm_cmpgt_epi8(lower, _mm_set1_epi8(b'a' as i8 - 1)),
+                _mm_cmplt_epi8(lower, _mm_set1_epi8(b'f' as i8 + 1)),
+            );
+            if _mm_movemask_epi8(_mm_or_si128(is_digit, is_letter)) != 0xffff {
+                return None;
+            }
+            let digits = _mm_sub_epi8(input, _mm_set1_epi8(b'0' as i8));
+            let letters = _mm_sub_epi8(lower, _mm_set1_epi8(b'a' as i8 - 10));
+            let values = _mm_or_si128(
+                _mm_and_si128(is_digit, digits),
+                _mm_and_si128(is_letter, letters),
+            );
+            // every 16 bit lane holds the high digit in its low byte and the low digit above it
+            let high = _mm_slli_epi16::<4>(_mm_and_si128(values, _mm_set1_epi16(0xff)));
+            let low = _mm_srli_epi16::<8>(values);
+            let packed = _mm_packus_epi16(_mm_or_si128(high, low), _mm_setzero_si128());
+            _mm_storel_epi64(bytes.as_mut_ptr() as *mut __m128i, packed);
+        }
+        Some(bytes)
+    }
+}
+
+#[cfg(all(feature = "simd", target_arch = "aarch64"))]
+mod imp {
+    use crate::encoding::HEX_LOWER;
+    use core::arch::aarch64::*;
+
+    pub(crate) fn encode_chunk(bytes: &[u8; 8], out: &mut [u8; 16]) {
+        // SAFETY: NEON is always available on aarch64, the loads and stores are within the
+        // bounds of `bytes`, `HEX_LOWER` and `out`
+        unsafe {
+            let input = vld1_u8(bytes.as_ptr());
+            let high = vshr_n_u8::<4>(input);
+            let low = vand_u8(input, vdup_n_u8(0x0f));
+            let nibbles = vcombine_u8(vzip1_u8(high, low), vzip2_u8(high, low));
+            let ascii = vqtbl1q_u8(vld1q_u8(HEX_LOWER.as_ptr()), nibbles);
+            vst1q_u8(out.as_mut_ptr(), ascii);
+        }
+    }
+
+    pub(crate) fn decode_chunk(chars: &[u8; 16]) -> Option<[u8; 8]> {
+        let mut bytes = [0; 8];
+        // SAFETY: NEON is always available on aarch64, the loads and stores are within the
+        // bounds of `chars` and `bytes`
+        unsafe {
+            let input = vld1q_u8(chars.as_ptr());
+            // bytes below '0' or 'a' wrap around, so one unsigned comparison checks each range
+            let digits = vsubq_u8(input, vdupq_n_u8(b'0'));
+            let is_digit = vcltq_u8(digits, vdupq_n_u8(10));
+            let letters = vsubq_u8(vorrq_u8(input, vdupq_n_u8(0x20)), vdupq_n_u8(b'a'));
+            let is_letter = vcltq_u8(letters, vdupq_n_u8(6));
+            if vminvq_u8(vorrq_u8(is_digit, is_letter)) != 0xff {
+                return None;
+            }
+            let values = vbslq_u8(is_digit, digits, vaddq_u8(letters, vdupq_n_u8(10)));
+            let high = vget_low_u8(vuzp1q_u8(values, values));
+            let low = vget_low_u8(vuzp2q_u8(values, values));
+            vst1_u8(bytes.as_mut_ptr(), vorr_u8(vshl_n_u8::<4>(high), low));
+        }
+        Some(bytes)
+    }
+}
+
+pub(crate) use imp::{decode_chunk, encode_chunk};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_every_byte() {
+        for first in (0..=255u8).step_by(8) {
+            let bytes: [u8; 8] = core::array::from_fn(|index| first + index as u8);
+            let mut out = [0; 16];
+            encode_chunk(&bytes, &mut out);
+            assert_eq!(
+                out,
+                *format!("{:016x}", u64::from_be_bytes(bytes)).as_bytes()
+            );
+            assert_eq!(decode_chunk(&out), Some(bytes));
+        }
+    }
+
+    #[test]
+    fn decode_every_byte() {
+        let valid = *b"0123456789abcdef";
+        for index in 0..16 {
+            for byte in 0..=255u8 {
+                let mut chars = valid;
+                chars[index] = byte;
+                let expected = byte.is_ascii_hexdigit().then(|| {
+                    let s = core::str::from_utf8(&chars).unwrap();
+                    u64::from_str_radix(s, 16).unwrap().to_be_bytes()
+                });
+                assert_eq!(decode_chunk(&chars), expected, "{:?}", chars);
+            }
+        }
+    }
+}
@@ -0,0 +1,202 @@
+//! HMAC signed IDs for [HoraId], using [hmac] and [sha2]
+//!
+//! A signed ID is the hexadecimal [HoraId], a `.` and the first 8 bytes of the HMAC-SHA256 of
+//! the ID bytes in hexadecimal, 33 characters in total.
+
+use crate::encoding::decode_hex;
+use crate::{HoraError, HoraId, HoraIdParseError};
+use core::fmt;
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::Sha256;
+
+/// Number of bytes of the HMAC kept in the tag
+const TAG_LEN: usize = 8;
+
+/// Length of the string form: the hexadecimal ID, `.` and the hexadecimal tag
+const SIGNED_LEN: usize = 17 + 2 * TAG_LEN;
+
+/// Secret key for signing and verifying [SignedHoraId]s
+///
+/// Every service that verifies IDs needs the key used to sign them.
+#[derive(Clone)]
+pub struct SigningKey {
+    mac: Hmac<Sha256>,
+}
+
+impl SigningKey {
+    /// Create a key from a secret of any length, 32 random bytes are recommended
+    pub fn new(secret: &[u8]) -> Self {
+        Self {
+            mac: Hmac::new_from_slice(secret).expect("HMAC accepts keys of any length"),
+        }
+    }
+
+    fn mac(&self, id: &HoraId) -> Hmac<Sha256> {
+        self.mac.clone().chain_update(id.as_bytes())
+    }
+}
+
+/// Doesn't print the key
+impl fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SigningKey(..)")
+    }
+}
+
+/// A [HoraId] with a truncated HMAC tag, to reject forged IDs without a database lookup
+///
+/// Create it with [HoraId::sign] and send its string form to clients. When an ID comes back,
+/// [SignedHoraId::verify] checks the tag and only returns IDs signed with the same key. The tag
+/// is 64 bits, so guessing a valid ID takes around 2^63 attempts.
+///
+/// ## Usage
+/// ```
+/// use hora_id::{HoraError, HoraId, SignedHoraId, SigningKey};
+///
+/// let key = SigningKey::new(b"a secret of 32 random bytes.....");
+/// let id: HoraId = "00cd01daff010002".parse().unwrap();
+///
+/// let token = id.sign(&key).to_string();
+/// assert_eq!(SignedHoraId::verify(&token, &key), Ok(id));
+///
+/// let forged = token.replace("0002.", "0003.");
+/// assert_eq!(SignedHoraId::verify(&forged, &key), Err(HoraError::InvalidSignature));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SignedHoraId {
+    id: HoraId,
+    tag: [u8; TAG_LEN],
+}
+
+impl HoraId {
+    /// Sign the [HoraId] with `key`, see [SignedHoraId]
+    pub fn sign(&self, key: &SigningKey) -> SignedHoraId {
+        let mut tag = [0; TAG_LEN];
+        tag.copy_from_slice(&key.mac(self).finalize().into_bytes()[..TAG_LEN]);
+        SignedHoraId { id: *self, tag }
+    }
+}
+
+impl SignedHoraId {
+    /// The signed [HoraId]
+    pub fn id(&self) -> HoraId {
+        self.id
+    }
+
+    /// Parse the string form of a [SignedHoraId] and return the [HoraId] if the tag is valid
+    ///
+    /// The tag is compared in constant time.
+    ///
+    /// ## Fail condition
+    /// - [HoraError::ParseError] if `s` isn't a signed ID, with indices relative to the start
+    ///   of `s`
+    /// - [HoraError::InvalidSignature] if the tag doesn't match, for example because the ID
+    ///   was changed or signed with another key
+    pub fn verify(s: &str, key: &SigningKey) -> Result<HoraId, HoraError> {
+        if s.len() != SIGNED_LEN {
+            return Err(HoraIdParseError::InvalidLength {
+                expected: SIGNED_LEN,
+                got: s.len(),
+            }
+            .into());
+        }
+        if s.as_bytes()[16] != b'.' {
+            // the character covering byte 16, which may start before it if it isn't ASCII
+            let (index, char) = s
+                .char_indices()
+                .find(|(index, char)| index + char.len_utf8() > 16)
+                .expect("s is longer than 16 bytes");
+            return Err(HoraIdParseError::InvalidCharacter { index, char }.into());
+        }
+        let (id, tag) = (&s[..16], &s[17..]);
+        let id: HoraId = id.parse()?;
+        let tag: [u8; TAG_LEN] = decode_hex(tag).map_err(|err| match err {
+            HoraIdParseError::InvalidCharacter { index, char } => {
+                HoraIdParseError::InvalidCharacter {
+                    index: index + 17,
+                    char,
+                }
+            }
+            err => err,
+        })?;
+        key.mac(&id)
+            .verify_truncated_left(&tag)
+            .map_err(|_| HoraError::InvalidSignature)?;
+        Ok(id)
+    }
+}
+
+/// Formats a [SignedHoraId] as the hexadecimal ID and tag separated by `.`
+impl fmt::Display for SignedHoraId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.", self.id)?;
+        crate::encoding::fmt_hex(&self.tag, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_and_verify() {
+        let key = SigningKey::new(b"secret");
+        let id: HoraId = "00cd01daff010002".parse().unwrap();
+        let token = id.sign(&key).to_string();
+        assert_eq!(token.len(), 33);
+        assert_eq!(id.sign(&key).id(), id);
+        assert_eq!(SignedHoraId::verify(&token, &key), Ok(id));
+
+        let other = SigningKey::new(b"other secret");
+        assert_eq!(
+            SignedHoraId::verify(&token, &other),
+            Err(HoraError::InvalidSignature)
+        );
+        let tampered = format!("{}.{}", HoraId::from(id.to_u64() + 1), &token[17..]);
+        assert_eq!(
+            SignedHoraId::verify(&tampered, &key),
+            Err(HoraError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        let key = SigningKey::new(b"secret");
+        assert_eq!(
+            SignedHoraId::verify("00cd01daff010002", &key),
+            Err(HoraError::ParseError(HoraIdParseError::InvalidLength {
+                expected: 33,
+                got: 16
+            }))
+        );
+        assert_eq!(
+            SignedHoraId::verify("00cd01daff010002.5c3e1a0b9f7d246z", &key),
+            Err(HoraError::ParseError(HoraIdParseError::InvalidCharacter {
+                index: 32,
+                char: 'z'
+            }))
+        );
+        // a tag of the wrong length reports the length of the whole input
+        assert_eq!(
+            SignedHoraId::verify("00cd01daff010002.5c3e1a0b", &key),
+            Err(HoraError::ParseError(HoraIdParseError::InvalidLength {
+                expected: 33,
+                got: 25
+            }))
+        );
+        assert_eq!(
+            SignedHoraId::verify("00cd01daff01000.25c3e1a0b9f7d2468", &key),
+            Err(HoraError::ParseError(HoraIdParseError::InvalidCharacter {
+                index: 16,
+                char: '2'
+            }))
+        );
+        assert_eq!(
+            SignedHoraId::verify("00cd01daff01000é5c3e1a0b9f7d2468", &key),
+            Err(HoraError::ParseError(HoraIdParseError::InvalidCharacter {
+                index: 15,
+                char: 'é'
+            }))
+        );
+    }
+}
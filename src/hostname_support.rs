@@ -0,0 +1,91 @@
+//! Machine IDs derived from the hostname, using [gethostname]
+
+use crate::machine_id::hash_machine_id;
+use crate::{HoraError, MachineIdProvider};
+
+/// Derives the machine ID from a hash of the hostname
+///
+/// A host keeps its machine ID as long as it keeps its name, e.g. `web-07` always maps to the
+/// same ID, which suits bare-metal fleets with stable names and no coordination service. Hosts
+/// can collide on the derived ID, see [derived machine IDs](MachineIdProvider#derived-machine-ids),
+/// so use [HostnameMachineId::with_override] to move a host that collides.
+///
+/// ## Usage
+/// ```no_run
+/// use hora_id::{HoraGenerator, HostnameMachineId};
+///
+/// let mut generator = HoraGenerator::from_provider(&HostnameMachineId::new()).unwrap();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HostnameMachineId {
+    hostname: Option<String>,
+    override_id: Option<u8>,
+}
+
+impl HostnameMachineId {
+    /// Derive the machine ID from the hostname of this machine, looked up on every acquire
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Derive the machine ID from a known hostname
+    ///
+    /// ```
+    /// use hora_id::{HostnameMachineId, MachineIdProvider};
+    ///
+    /// let web_07 = HostnameMachineId::from_hostname("web-07");
+    /// assert_eq!(web_07.acquire(), Ok(76));
+    /// ```
+    pub fn from_hostname(hostname: impl Into<String>) -> Self {
+        Self {
+            hostname: Some(hostname.into()),
+            override_id: None,
+        }
+    }
+
+    /// Use `machine_id` instead of the derived ID if it is `Some`
+    pub fn with_override(mut self, machine_id: Option<u8>) -> Self {
+        self.override_id = machine_id;
+        self
+    }
+}
+
+impl MachineIdProvider for HostnameMachineId {
+    fn acquire(&self) -> Result<u8, HoraError> {
+        if let Some(machine_id) = self.override_id {
+            return Ok(machine_id);
+        }
+        let hostname = match &self.hostname {
+            Some(hostname) => hostname.clone(),
+            None => gethostname::gethostname()
+                .into_string()
+                .map_err(|_| HoraError::MachineIdUnavailable("hostname is not unicode".into()))?,
+        };
+        if hostname.is_empty() {
+            return Err(HoraError::MachineIdUnavailable("hostname is empty".into()));
+        }
+        Ok(hash_machine_id(hostname.as_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derived() {
+        assert_eq!(HostnameMachineId::from_hostname("web-08").acquire(), Ok(97));
+        assert!(matches!(
+            HostnameMachineId::from_hostname("").acquire(),
+            Err(HoraError::MachineIdUnavailable(_))
+        ));
+        let overridden = HostnameMachineId::from_hostname("web-08").with_override(Some(2));
+        assert_eq!(overridden.acquire(), Ok(2));
+    }
+
+    #[test]
+    fn this_host() {
+        let provider = HostnameMachineId::new();
+        assert_eq!(provider.acquire(), provider.acquire());
+    }
+}
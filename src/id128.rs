@@ -0,0 +1,269 @@
+use crate::encoding::{decode_hex, fmt_bits, fmt_hex, BINARY, HEX_LOWER, HEX_UPPER};
+#[cfg(feature = "chrono")]
+use crate::ticks_to_utc;
+#[cfg(feature = "std")]
+use crate::{current_ticks, ticks_to_bytes, HoraError, MAX_TICKS};
+use crate::{ticks_from_bytes, ticks_to_millis, HoraIdParseError, EPOCH};
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, NaiveDateTime, Utc};
+use core::fmt;
+use core::str::FromStr;
+
+/// A time-sorted 16-byte (128-bit) identifier with random entropy
+///
+/// [HoraId](crate::HoraId) is compact but its sequence number makes IDs guessable and reveals how
+/// many IDs a machine generates. [HoraId128] keeps the same timestamp prefix and machine byte,
+/// followed by 80 random bits, for IDs that are exposed on the internet.
+///
+/// ## Composition
+/// - 5 byte timestamp (same as [HoraId](crate::HoraId))
+/// - 1 byte for machine ID (0-255)
+/// - 10 random bytes
+///
+/// IDs sort by time, but IDs from the same 1/256 second are in random order.
+///
+/// ## Usage
+/// ```no_run
+/// use hora_id::HoraId128;
+///
+/// let id = HoraId128::new(1).unwrap();
+/// println!("{}", id); // example: '00cd01daff01c3a1d2f39b0e447c51aa'
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash))
+)]
+pub struct HoraId128 {
+    pub(crate) inner: [u8; 16],
+}
+
+impl HoraId128 {
+    /// Generate a new [HoraId128] with the current time and 80 random bits
+    ///
+    /// ## Fail condition
+    /// - If the system time is before the HoraID epoch (2025-01-01)
+    /// - If the system time is after the last tick of version 0 IDs, in 2059
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn new(machine_id: u8) -> Result<Self, HoraError> {
+        Self::with_ticks(current_ticks()?, machine_id)
+    }
+
+    /// Create a [HoraId128] for `ticks` since [EPOCH] with 80 random bits
+    #[cfg(feature = "std")]
+    fn with_ticks(ticks: u64, machine_id: u8) -> Result<Self, HoraError> {
+        // the version bits above the timestamp stay 0, as in HoraId
+        if ticks > MAX_TICKS {
+            return Err(HoraError::TimestampOutOfRange);
+        }
+        let mut inner = [0u8; 16];
+        inner[..5].copy_from_slice(&ticks_to_bytes(ticks));
+        inner[5] = machine_id;
+        inner[6..].copy_from_slice(&rand::random::<[u8; 10]>());
+        Ok(Self { inner })
+    }
+
+    /// Convert a [HoraId128] to a number
+    pub fn to_u128(&self) -> u128 {
+        u128::from_be_bytes(self.inner)
+    }
+
+    /// Get the byte representation of [HoraId128]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.inner
+    }
+
+    /// Timestamp of the [HoraId128] as milliseconds since the Unix epoch
+    ///
+    /// See [HoraId::timestamp_millis](crate::HoraId::timestamp_millis) for how the stored
+    /// 1/256 second tick is rounded.
+    pub fn timestamp_millis(&self) -> u64 {
+        EPOCH + ticks_to_millis(ticks_from_bytes(&self.inner))
+    }
+
+    /// Machine ID of the generator that produced this [HoraId128]
+    pub fn machine_id(&self) -> u8 {
+        self.inner[5]
+    }
+
+    /// Retrieve a chrono [NaiveDateTime] from [HoraId128]
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+    pub fn to_datetime(&self) -> NaiveDateTime {
+        self.to_utc().naive_utc()
+    }
+
+    /// Retrieve a chrono [Utc] datetime from [HoraId128]
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+    pub fn to_utc(&self) -> DateTime<Utc> {
+        ticks_to_utc(ticks_from_bytes(&self.inner), EPOCH)
+    }
+}
+
+/// Formats a [HoraId128] as a 32 character lowercase hexadecimal string
+impl fmt::Display for HoraId128 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_hex(&self.inner, f)
+    }
+}
+
+/// Formats a [HoraId128] as 32 lowercase hexadecimal digits, `{:#x}` adds a `0x` prefix
+impl fmt::LowerHex for HoraId128 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_bits(&self.inner, 4, HEX_LOWER, "0x", f)
+    }
+}
+
+/// Formats a [HoraId128] as 32 uppercase hexadecimal digits, `{:#X}` adds a `0x` prefix
+impl fmt::UpperHex for HoraId128 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_bits(&self.inner, 4, HEX_UPPER, "0x", f)
+    }
+}
+
+/// Formats a [HoraId128] as 128 binary digits, `{:#b}` adds a `0b` prefix
+impl fmt::Binary for HoraId128 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_bits(&self.inner, 1, BINARY, "0b", f)
+    }
+}
+
+/// Parses a [HoraId128] from a 32 character hexadecimal string
+impl FromStr for HoraId128 {
+    type Err = HoraIdParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = decode_hex(s)?;
+        Ok(Self { inner })
+    }
+}
+
+impl From<[u8; 16]> for HoraId128 {
+    fn from(bytes: [u8; 16]) -> Self {
+        Self { inner: bytes }
+    }
+}
+
+impl From<HoraId128> for [u8; 16] {
+    fn from(id: HoraId128) -> Self {
+        id.inner
+    }
+}
+
+impl From<u128> for HoraId128 {
+    fn from(num: u128) -> Self {
+        Self {
+            inner: num.to_be_bytes(),
+        }
+    }
+}
+
+impl From<HoraId128> for u128 {
+    fn from(id: HoraId128) -> Self {
+        id.to_u128()
+    }
+}
+
+/// Create a [HoraId128] from a byte slice that must be exactly 16 bytes long
+impl TryFrom<&[u8]> for HoraId128 {
+    type Error = HoraIdParseError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let inner: [u8; 16] = bytes
+            .try_into()
+            .map_err(|_| HoraIdParseError::InvalidLength {
+                expected: 16,
+                got: bytes.len(),
+            })?;
+        Ok(Self { inner })
+    }
+}
+
+impl AsRef<[u8]> for HoraId128 {
+    fn as_ref(&self) -> &[u8] {
+        &self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HoraId;
+
+    #[test]
+    fn it_works() {
+        let id1 = HoraId128::new(7).unwrap();
+        let id2 = HoraId128::new(7).unwrap();
+        assert_ne!(id1, id2);
+        assert_eq!(id1.machine_id(), 7);
+
+        // same timestamp prefix as HoraId
+        let short = HoraId::new(Some(7)).unwrap();
+        assert!(short.timestamp_millis() - id1.timestamp_millis() < 1000);
+    }
+
+    #[test]
+    fn last_tick() {
+        let id = HoraId128::with_ticks(MAX_TICKS, 3).unwrap();
+        assert_eq!(id.inner[0] >> 6, 0);
+        assert_eq!(id.machine_id(), 3);
+        assert_eq!(
+            HoraId128::with_ticks(MAX_TICKS + 1, 3),
+            Err(HoraError::TimestampOutOfRange)
+        );
+    }
+
+    #[test]
+    fn strings() {
+        let id = HoraId128::new(1).unwrap();
+        let s = id.to_string();
+        assert_eq!(s.len(), 32);
+        assert_eq!(s.parse::<HoraId128>(), Ok(id));
+        assert_eq!(format!("{:x}", id), s);
+        assert_eq!(format!("{:X}", id), s.to_uppercase());
+        assert_eq!(format!("{:b}", id).len(), 128);
+
+        assert_eq!(
+            "00cd01daff010002".parse::<HoraId128>(),
+            Err(HoraIdParseError::InvalidLength {
+                expected: 32,
+                got: 16
+            })
+        );
+        assert_eq!(
+            "00cd01daff0100020000000000000z00".parse::<HoraId128>(),
+            Err(HoraIdParseError::InvalidCharacter {
+                index: 29,
+                char: 'z'
+            })
+        );
+    }
+
+    #[test]
+    fn conversions() {
+        let num = 0x00cd_01da_ff01_0203_0405_0607_0809_0a0b;
+        let id = HoraId128::from(num);
+        assert_eq!(u128::from(id), num);
+        assert_eq!(id.to_string(), "00cd01daff0102030405060708090a0b");
+        assert_eq!(HoraId128::try_from(id.as_bytes()), Ok(id));
+        assert_eq!(HoraId128::from(<[u8; 16]>::from(id)), id);
+        assert_eq!(id.machine_id(), 1);
+        assert_eq!(
+            id.timestamp_millis(),
+            "00cd01daff010002"
+                .parse::<HoraId>()
+                .unwrap()
+                .timestamp_millis()
+        );
+    }
+
+    #[test]
+    fn ordering() {
+        let older = HoraId128::from(0x00cd_01da_ff01_ffff_ffff_ffff_ffff_ffff);
+        let newer = HoraId128::from(0x00cd_01db_0001_0000_0000_0000_0000_0000);
+        assert!(older < newer);
+    }
+}
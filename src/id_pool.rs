@@ -0,0 +1,272 @@
+//! Pool of pre-generated IDs that a background thread keeps topped up
+
+use crate::{Clock, HoraError, HoraGenerator, HoraId, Spin, SystemClock, WaitStrategy};
+use crossbeam_queue::ArrayQueue;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// How long the background thread sleeps when nobody takes IDs from the pool
+const REFILL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Ready-made [HoraId]s in a lock-free ring buffer
+///
+/// A background thread generates IDs ahead of time and keeps the buffer full, so
+/// [IdPool::pop] only takes an ID out of the buffer and never reads the clock or waits for a
+/// lock. It wakes the thread up when the buffer is half empty. If the buffer runs empty anyway,
+/// [IdPool::next] generates the ID on the calling thread.
+///
+/// IDs carry the time they were generated, not the time they were taken from the pool. They
+/// can be older than the IDs of other generators when they are used, so the pool is a poor fit
+/// where the timestamp of an ID has to be close to the time of the event it names. IDs are taken
+/// in ascending order.
+///
+/// The background thread stops when the pool is dropped.
+///
+/// ## Usage
+/// ```no_run
+/// use hora_id::IdPool;
+///
+/// let pool = IdPool::new(1, 4096).unwrap();
+/// // in the request path
+/// let id = pool.next();
+/// println!("{}", id);
+/// ```
+pub struct IdPool<C = SystemClock, W = Spin>
+where
+    C: Clock + Send + 'static,
+    W: WaitStrategy + Send + 'static,
+{
+    shared: Arc<Shared<C, W>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+struct Shared<C, W> {
+    queue: ArrayQueue<HoraId>,
+    generator: Mutex<HoraGenerator<C, W>>,
+    stop: AtomicBool,
+}
+
+impl<C, W> Shared<C, W> {
+    fn generator(&self) -> MutexGuard<'_, HoraGenerator<C, W>> {
+        // the generator state is always consistent, so a poisoned lock is safe to reuse
+        self.generator
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl IdPool {
+    /// Create a pool of `capacity` IDs with the machine ID `machine_id`
+    ///
+    /// ## Fail condition
+    /// If the system time is before the HoraID epoch (2025-01-01)
+    ///
+    /// ## Panics
+    /// If `capacity` is zero
+    pub fn new(machine_id: u8, capacity: usize) -> Result<Self, HoraError> {
+        Ok(Self::from_generator(
+            HoraGenerator::new(machine_id)?,
+            capacity,
+        ))
+    }
+}
+
+impl<C: Clock + Send + 'static, W: WaitStrategy + Send + 'static> IdPool<C, W> {
+    /// Keep `capacity` IDs of `generator` ready on a background thread
+    ///
+    /// ## Panics
+    /// If `capacity` is zero or the thread can't be spawned
+    pub fn from_generator(generator: HoraGenerator<C, W>, capacity: usize) -> Self {
+        let shared = Arc::new(Shared {
+            queue: ArrayQueue::new(capacity),
+            generator: Mutex::new(generator),
+            stop: AtomicBool::new(false),
+        });
+        let worker = thread::Builder::new()
+            .name("hora-id-pool".into())
+            .spawn({
+                let shared = Arc::clone(&shared);
+                move || refill(&shared)
+            })
+            .expect("failed to spawn IdPool thread");
+        Self {
+            shared,
+            worker: Some(worker),
+        }
+    }
+
+    /// Take a pre-generated [HoraId] out of the pool, or `None` if it is empty
+    pub fn pop(&self) -> Option<HoraId> {
+        let id = self.shared.queue.pop();
+        if self.shared.queue.len() <= self.shared.queue.capacity() / 2 {
+            self.wake();
+        }
+        id
+    }
+
+    /// Take a [HoraId] out of the pool, generating it if the pool is empty
+    ///
+    /// ## Panics
+    /// If the pool is empty and the system time is incorrect. Use [IdPool::try_next] to handle
+    /// this case.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&self) -> HoraId {
+        self.try_next().expect("failed to generate HoraId")
+    }
+
+    /// Take a [HoraId] out of the pool, returning an error instead of panicking
+    ///
+    /// ## Fail condition
+    /// If the pool is empty and the generator fails, see [HoraGenerator::try_next]
+    pub fn try_next(&self) -> Result<HoraId, HoraError> {
+        if let Some(id) = self.pop() {
+            return Ok(id);
+        }
+        let mut generator = self.shared.generator();
+        // the background thread may have refilled the pool while this thread waited for the lock
+        match self.shared.queue.pop() {
+            Some(id) => Ok(id),
+            None => generator.try_next(),
+        }
+    }
+
+    /// Number of IDs ready in the pool
+    pub fn len(&self) -> usize {
+        self.shared.queue.len()
+    }
+
+    /// Whether the pool has no IDs ready
+    pub fn is_empty(&self) -> bool {
+        self.shared.queue.is_empty()
+    }
+
+    /// Most IDs the pool holds
+    pub fn capacity(&self) -> usize {
+        self.shared.queue.capacity()
+    }
+
+    fn wake(&self) {
+        if let Some(worker) = &self.worker {
+            worker.thread().unpark();
+        }
+    }
+}
+
+impl<C: Clock + Send + 'static, W: WaitStrategy + Send + 'static> Drop for IdPool<C, W> {
+    fn drop(&mut self) {
+        self.shared.stop.store(true, Ordering::Relaxed);
+        if let Some(worker) = self.worker.take() {
+            worker.thread().unpark();
+            // the thread only panics if the generator does, which `next` reports as well
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Loop of the background thread: fill the free slots, then sleep until woken up
+fn refill<C: Clock, W: WaitStrategy>(shared: &Shared<C, W>) {
+    let mut ids = Vec::with_capacity(shared.queue.capacity());
+    while !shared.stop.load(Ordering::Relaxed) {
+        let free = shared.queue.capacity() - shared.queue.len();
+        if free > 0 {
+            // push under the lock, so IDs that `try_next` generates are newer than all IDs in
+            // the pool
+            let mut generator = shared.generator();
+            // an error leaves the IDs generated before it, the next round tries again
+            let _ = generator.try_next_n_into(free, &mut ids);
+            for id in ids.drain(..) {
+                // only this thread pushes, so the slots counted as free stay free
+                let _ = shared.queue.push(id);
+            }
+        }
+        thread::park_timeout(REFILL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Fail, HoraLayout, MockClock, EPOCH};
+    use std::collections::HashSet;
+
+    fn wait_until_full<C: Clock + Send, W: WaitStrategy + Send>(pool: &IdPool<C, W>) {
+        while pool.len() < pool.capacity() {
+            thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<IdPool>();
+    }
+
+    #[test]
+    fn pops_in_order() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let generator = HoraGenerator::new_with_clock(3, clock).unwrap();
+        let pool = IdPool::from_generator(generator, 64);
+        assert_eq!(pool.capacity(), 64);
+        wait_until_full(&pool);
+
+        let ids: Vec<HoraId> = (0..64).map(|_| pool.pop().unwrap()).collect();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(ids.iter().all(|id| id.machine_id() == 3));
+        assert_eq!(ids[0].timestamp_millis(), EPOCH + 10_000);
+        // refilled after being emptied
+        wait_until_full(&pool);
+        assert!(pool.next() > ids[63]);
+    }
+
+    #[test]
+    fn generates_when_empty() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let layout = HoraLayout::new(52, 8, 4);
+        let generator = HoraGenerator::with_layout_and_clock(layout, 1, clock.clone())
+            .unwrap()
+            .with_wait_strategy(Fail);
+        let pool = IdPool::from_generator(generator, 32);
+
+        // the clock never moves on, so the pool and `try_next` share the 15 IDs of the tick
+        let mut unique = HashSet::new();
+        while let Ok(id) = pool.try_next() {
+            assert!(unique.insert(id));
+        }
+        assert_eq!(unique.len(), 15);
+        assert!(pool.is_empty());
+        assert_eq!(pool.try_next(), Err(HoraError::SequenceExhausted));
+
+        clock.advance(4);
+        assert_eq!(layout.sequence(&pool.next()), 0);
+    }
+
+    #[test]
+    fn shared_across_threads() {
+        let pool = IdPool::new(1, 256).unwrap();
+        let mut unique = HashSet::new();
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| scope.spawn(|| (0..1000).map(|_| pool.next()).collect::<Vec<_>>()))
+                .collect();
+            for handle in handles {
+                let ids = handle.join().unwrap();
+                assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+                unique.extend(ids);
+            }
+        });
+        assert_eq!(unique.len(), 8000);
+    }
+
+    #[test]
+    fn drop_stops_the_thread() {
+        let pool = IdPool::new(1, 16).unwrap();
+        wait_until_full(&pool);
+        let shared = Arc::clone(&pool.shared);
+        drop(pool);
+        assert!(shared.stop.load(Ordering::Relaxed));
+        // the thread released its handle when it exited
+        assert_eq!(Arc::strong_count(&shared), 1);
+    }
+}
@@ -0,0 +1,94 @@
+//! [jiff] support for [HoraId]
+
+use crate::{ticks_to_unix_nanos, unix_nanos_to_ticks, HoraError, HoraId, HoraParams};
+use jiff::{Timestamp, Zoned};
+
+impl HoraId {
+    /// Retrieve a [jiff] [Timestamp] from [HoraId]
+    ///
+    /// The returned time is exact to the 1/256 second tick stored in the ID.
+    pub fn to_timestamp(&self) -> Timestamp {
+        // every 40 bit timestamp is far within the range of Timestamp
+        Timestamp::from_nanosecond(ticks_to_unix_nanos(self.ticks())).unwrap()
+    }
+
+    /// Create a [HoraId] for the tick containing `timestamp`
+    ///
+    /// The machine ID and sequence are zero, so the ID sorts before every generated ID of that
+    /// tick. This is useful as a lower bound for range queries.
+    ///
+    /// ## Fail condition
+    /// If `timestamp` is before the HoraID epoch (2025-01-01) or too far in the future
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    /// use jiff::Timestamp;
+    ///
+    /// let timestamp: Timestamp = "2025-06-01T12:00:00Z".parse().unwrap();
+    /// let id = HoraId::from_timestamp(timestamp).unwrap();
+    /// assert_eq!(id.to_timestamp(), timestamp);
+    /// ```
+    pub fn from_timestamp(timestamp: Timestamp) -> Result<Self, HoraError> {
+        let ticks = unix_nanos_to_ticks(timestamp.as_nanosecond())?;
+        let params = HoraParams {
+            machine_id: 0,
+            ticks,
+            sequence: 0,
+        };
+        Ok(Self::with_params(params))
+    }
+
+    /// Create a [HoraId] for the tick containing the instant of `zoned`
+    ///
+    /// See [HoraId::from_timestamp].
+    pub fn from_zoned(zoned: &Zoned) -> Result<Self, HoraError> {
+        Self::from_timestamp(zoned.timestamp())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jiff::{SignedDuration, ToSpan};
+
+    #[test]
+    fn to_timestamp() {
+        let id: HoraId = "00cd01daff010002".parse().unwrap();
+        let timestamp = id.to_timestamp();
+        assert_eq!(timestamp.as_millisecond(), 1749124954996);
+        assert_eq!(timestamp.subsec_nanosecond() % 3_906_250, 0);
+
+        let id = HoraId::new(None).unwrap();
+        let elapsed = Timestamp::now().duration_since(id.to_timestamp());
+        assert!(elapsed < SignedDuration::from_secs(1));
+    }
+
+    #[test]
+    fn round_trip() {
+        let timestamp: Timestamp = "2025-03-20T10:30:15.5Z".parse().unwrap();
+        let id = HoraId::from_timestamp(timestamp).unwrap();
+        assert_eq!(id.to_timestamp(), timestamp);
+        assert_eq!(id.machine_id(), 0);
+        assert_eq!(id.sequence(), 0);
+
+        // times within a tick round down to the start of the tick
+        let later = timestamp.checked_add(3.milliseconds()).unwrap();
+        assert_eq!(HoraId::from_timestamp(later), Ok(id));
+    }
+
+    #[test]
+    fn zoned() {
+        let zoned: Zoned = "2025-03-20T11:30:15.5+01:00[+01:00]".parse().unwrap();
+        let id = HoraId::from_zoned(&zoned).unwrap();
+        assert_eq!(id.to_timestamp(), zoned.timestamp());
+    }
+
+    #[test]
+    fn out_of_range() {
+        let before: Timestamp = "2024-12-31T23:59:59Z".parse().unwrap();
+        assert_eq!(
+            HoraId::from_timestamp(before),
+            Err(HoraError::TimestampOutOfRange)
+        );
+    }
+}
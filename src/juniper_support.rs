@@ -0,0 +1,45 @@
+//! [juniper] support for [HoraId]
+//!
+//! [HoraId] is a custom `HoraId` scalar holding the 16 character hexadecimal string. GraphQL
+//! integers are 32 bits, so the numeric form can't be used.
+
+use crate::HoraId;
+use juniper::{graphql_scalar, ScalarValue};
+
+/// A time-sorted 64-bit ID as a 16 character hexadecimal string
+#[graphql_scalar]
+#[graphql(
+    name = "HoraId",
+    with = hora_id_scalar,
+    to_output_with = ScalarValue::from_displayable,
+    parse_token(String)
+)]
+type HoraIdScalar = HoraId;
+
+mod hora_id_scalar {
+    use super::HoraIdScalar;
+
+    pub(super) fn from_input(s: &str) -> Result<HoraIdScalar, Box<str>> {
+        s.parse()
+            .map_err(|err| format!("Failed to parse `HoraId`: {}", err).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use juniper::{graphql_input_value, FromInputValue, InputValue, ToInputValue};
+
+    #[test]
+    fn input_and_output() {
+        let id = HoraId::from_u64(57704410318438402).unwrap();
+        let input: InputValue = graphql_input_value!("00cd01daff010002");
+        assert_eq!(HoraId::from_input_value(&input), Ok(id));
+        assert_eq!(id.to_input_value(), input);
+
+        let input: InputValue = graphql_input_value!("00cd01daff01000z");
+        assert!(HoraId::from_input_value(&input).is_err());
+        let input: InputValue = graphql_input_value!(42);
+        assert!(HoraId::from_input_value(&input).is_err());
+    }
+}
@@ -0,0 +1,136 @@
+//! KSUID support for [HoraId], using [svix_ksuid]
+//!
+//! A [Ksuid] stores whole seconds since 2014-05-13 followed by a 16 byte payload. A [HoraId] is
+//! converted to the KSUID of its second, with the 8 bytes of the ID at the start of the payload,
+//! so KSUIDs converted from IDs sort in the same order as the IDs.
+
+use crate::{HoraError, HoraId, EPOCH};
+use svix_ksuid::{Ksuid, KsuidLike, KSUID_EPOCH};
+
+impl HoraId {
+    /// Convert the [HoraId] to a [Ksuid] of the same second, padding the payload with zeros
+    ///
+    /// The same ID always converts to the same KSUID.
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    /// use svix_ksuid::KsuidLike;
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap();
+    /// let ksuid = id.to_ksuid();
+    /// assert_eq!(ksuid.timestamp_seconds() as u64, id.timestamp_millis() / 1000);
+    /// assert_eq!(HoraId::try_from_ksuid(&ksuid), Ok(id));
+    /// ```
+    pub fn to_ksuid(&self) -> Ksuid {
+        self.ksuid_with_padding([0; 8])
+    }
+
+    /// Convert the [HoraId] to a [Ksuid] of the same second, padding the payload with random
+    /// bytes
+    ///
+    /// Use this where KSUIDs are expected to be unguessable beyond the embedded ID.
+    pub fn to_ksuid_random(&self) -> Ksuid {
+        self.ksuid_with_padding(rand::random())
+    }
+
+    fn ksuid_with_padding(&self, padding: [u8; 8]) -> Ksuid {
+        let seconds = self.unix_seconds() - KSUID_EPOCH as u64;
+        let mut payload = [0; 16];
+        payload[..8].copy_from_slice(&self.inner);
+        payload[8..].copy_from_slice(&padding);
+        Ksuid::new_raw(seconds as u32, Some(&payload))
+    }
+
+    /// Extract the [HoraId] embedded with [HoraId::to_ksuid] or [HoraId::to_ksuid_random]
+    ///
+    /// ## Fail condition
+    /// If the payload doesn't start with an ID of the KSUID's second
+    pub fn try_from_ksuid(ksuid: &Ksuid) -> Result<Self, HoraError> {
+        let mut inner = [0; 8];
+        inner.copy_from_slice(&ksuid.payload()[..8]);
+        let id = HoraId { inner };
+        if ksuid.timestamp_raw() < (EPOCH / 1000 - KSUID_EPOCH as u64) as u32
+            || id.unix_seconds() != ksuid.timestamp_seconds() as u64
+        {
+            return Err(HoraError::IncompatibleId);
+        }
+        Ok(id)
+    }
+
+    /// Create the first [HoraId] of the second of any [Ksuid]
+    ///
+    /// The machine ID and sequence are zero, so the ID sorts before every generated ID of that
+    /// second. This is useful to look up IDs around the time of a KSUID.
+    ///
+    /// ## Fail condition
+    /// If the KSUID is from before the HoraID epoch (2025-01-01)
+    pub fn from_ksuid_timestamp(ksuid: &Ksuid) -> Result<Self, HoraError> {
+        HoraId::min_for_millis(ksuid.timestamp_seconds() as u64 * 1000)
+    }
+}
+
+impl From<HoraId> for Ksuid {
+    fn from(id: HoraId) -> Self {
+        id.to_ksuid()
+    }
+}
+
+impl TryFrom<Ksuid> for HoraId {
+    type Error = HoraError;
+
+    fn try_from(ksuid: Ksuid) -> Result<Self, Self::Error> {
+        HoraId::try_from_ksuid(&ksuid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let id: HoraId = "00cd01daff010002".parse().unwrap();
+        let ksuid = id.to_ksuid();
+        assert_eq!(ksuid, id.to_ksuid());
+        assert_eq!(&ksuid.payload()[8..], [0; 8]);
+        assert_eq!(HoraId::try_from_ksuid(&ksuid), Ok(id));
+
+        let random = id.to_ksuid_random();
+        assert_eq!(random.timestamp_seconds(), ksuid.timestamp_seconds());
+        assert_eq!(HoraId::try_from_ksuid(&random), Ok(id));
+    }
+
+    #[test]
+    fn sort_order() {
+        let ids = [
+            57704410318438402,
+            57704410318438403,
+            57704410335215618,
+            57704414613405698,
+        ];
+        let ksuids: Vec<Ksuid> = ids
+            .iter()
+            .map(|&num| HoraId::from(num).to_ksuid())
+            .collect();
+        assert!(ksuids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn foreign_ksuid() {
+        let seconds = (EPOCH / 1000) as i64 + 90;
+        let ksuid = Ksuid::from_seconds(Some(seconds), Some(&[0xab; 16]));
+        assert_eq!(
+            HoraId::try_from_ksuid(&ksuid),
+            Err(HoraError::IncompatibleId)
+        );
+        let first = HoraId::from_ksuid_timestamp(&ksuid).unwrap();
+        assert_eq!(first.timestamp_millis(), seconds as u64 * 1000);
+        assert_eq!((first.machine_id(), first.sequence()), (0, 0));
+
+        let old = Ksuid::from_seconds(Some(seconds - 86_400 * 365), None);
+        assert_eq!(
+            HoraId::from_ksuid_timestamp(&old),
+            Err(HoraError::TimestampOutOfRange)
+        );
+    }
+}
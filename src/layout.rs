@@ -0,0 +1,507 @@
+use crate::version::VERSION_BITS;
+use crate::{ticks_to_millis, HoraError, HoraId, EPOCH};
+
+/// Bit layout of a [HoraId] produced by a [HoraGenerator](crate::HoraGenerator)
+///
+/// A [HoraId] is split into a timestamp, a machine ID and a sequence number, from the most to
+/// the least significant bits. The default layout gives 256 machines 65,536 IDs per tick.
+/// Deployments with more machines or a higher rate per machine can trade bits between the two.
+///
+/// | Layout                         | Timestamp | Machine | Sequence | Machines | IDs per tick |
+/// |--------------------------------|-----------|---------|----------|----------|--------------|
+/// | [HoraLayout::DEFAULT]          | 40        | 8       | 16       | 256      | 65,536       |
+/// | [HoraLayout::WIDE_MACHINE]     | 40        | 12      | 12       | 4,096    | 4,096        |
+/// | [HoraLayout::WIDE_SEQUENCE]    | 40        | 4       | 20       | 16       | 1,048,576    |
+/// | [HoraLayout::MULTI_DATACENTER] | 40        | 2 + 6   | 16       | 4 × 64   | 65,536       |
+/// | [HoraLayout::TENANT]           | 40        | 8       | 8 + 8    | 256      | 256          |
+///
+/// The timestamp counts 1/256 second ticks since 2025-01-01. Its 2 highest bits are the format
+/// version of the ID, see [HoraId::version], so 40 bits hold 2^38 ticks and last about 34
+/// years, until 2059. Each additional bit doubles that.
+///
+/// All IDs in one system must use the same layout, a [HoraId] does not record its layout.
+///
+/// ## Datacenters
+/// The machine ID can be split into a datacenter ID in its high bits and a worker ID in its low
+/// bits with [HoraLayout::with_datacenter_bits]. Each region then assigns worker IDs on its own
+/// without overlapping the machine IDs of other regions. The split doesn't change the IDs, only
+/// how the machine ID is built and read.
+///
+/// ```no_run
+/// use hora_id::{HoraGenerator, HoraLayout};
+///
+/// let mut generator = HoraGenerator::with_topology(2, 17).unwrap();
+/// let id = generator.next();
+/// assert_eq!(HoraLayout::MULTI_DATACENTER.datacenter_id(&id), 2);
+/// assert_eq!(HoraLayout::MULTI_DATACENTER.worker_id(&id), 17);
+/// ```
+///
+/// ## Usage
+/// ```no_run
+/// use hora_id::{HoraGenerator, HoraLayout};
+///
+/// let mut generator = HoraGenerator::with_layout(HoraLayout::WIDE_MACHINE, 3000).unwrap();
+/// let id = generator.next();
+/// assert_eq!(HoraLayout::WIDE_MACHINE.machine_id(&id), 3000);
+/// ```
+///
+/// Custom layouts are checked when they are built, so a `const` layout fails to compile if
+/// the bit widths are invalid.
+///
+/// ```compile_fail
+/// use hora_id::HoraLayout;
+///
+/// const BROKEN: HoraLayout = HoraLayout::new(40, 16, 16); // 72 bits
+/// ```
+///
+/// ## Tenants
+/// Bits between the machine ID and the sequence can carry a tenant or namespace tag with
+/// [HoraLayout::with_tenant_bits], so a service can route an ID to its tenant without a
+/// lookup. Each tenant gets its own sequence, so every tenant uses a separate generator, see
+/// [HoraGenerator::for_tenant](crate::HoraGenerator::for_tenant).
+///
+/// ```no_run
+/// use hora_id::{HoraGenerator, HoraLayout};
+///
+/// let mut generator = HoraGenerator::with_layout(HoraLayout::TENANT, 1)
+///     .unwrap()
+///     .for_tenant(42)
+///     .unwrap();
+/// assert_eq!(generator.next().tenant(), 42);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HoraLayout {
+    timestamp_bits: u8,
+    machine_bits: u8,
+    sequence_bits: u8,
+    datacenter_bits: u8,
+    tenant_bits: u8,
+}
+
+impl HoraLayout {
+    /// 40 bit timestamp, 8 bit machine ID, 16 bit sequence
+    pub const DEFAULT: HoraLayout = HoraLayout::new(40, 8, 16);
+
+    /// 40 bit timestamp, 12 bit machine ID, 12 bit sequence
+    pub const WIDE_MACHINE: HoraLayout = HoraLayout::new(40, 12, 12);
+
+    /// 40 bit timestamp, 4 bit machine ID, 20 bit sequence
+    pub const WIDE_SEQUENCE: HoraLayout = HoraLayout::new(40, 4, 20);
+
+    /// 40 bit timestamp, 2 bit datacenter ID, 6 bit worker ID, 16 bit sequence
+    ///
+    /// Produces the same IDs as [HoraLayout::DEFAULT], with machine ID `datacenter * 64 + worker`.
+    pub const MULTI_DATACENTER: HoraLayout = HoraLayout::DEFAULT.with_datacenter_bits(2);
+
+    /// 40 bit timestamp, 8 bit machine ID, 8 bit tenant, 8 bit sequence
+    ///
+    /// The tenant is the 7th byte of the ID, read by [HoraId::tenant].
+    pub const TENANT: HoraLayout = HoraLayout::DEFAULT.with_tenant_bits(8);
+
+    /// Create a custom layout
+    ///
+    /// ## Panics
+    /// - If the widths don't add up to 64 bits
+    /// - If the timestamp has less than 40 bits (about 34 years with the version bits)
+    /// - If the machine ID has more than 16 bits
+    /// - If the sequence has no bits or more than 32 bits
+    ///
+    /// When used in a `const` context, these checks fail at compile time.
+    pub const fn new(timestamp_bits: u8, machine_bits: u8, sequence_bits: u8) -> Self {
+        assert!(
+            timestamp_bits as u32 + machine_bits as u32 + sequence_bits as u32 == 64,
+            "layout must have exactly 64 bits"
+        );
+        assert!(timestamp_bits >= 40, "timestamp needs at least 40 bits");
+        assert!(machine_bits <= 16, "machine ID can have at most 16 bits");
+        assert!(
+            sequence_bits >= 1 && sequence_bits <= 32,
+            "sequence needs between 1 and 32 bits"
+        );
+        Self {
+            timestamp_bits,
+            machine_bits,
+            sequence_bits,
+            datacenter_bits: 0,
+            tenant_bits: 0,
+        }
+    }
+
+    /// Use the highest `bits` bits of the machine ID as a datacenter ID, the rest as worker ID
+    ///
+    /// ## Panics
+    /// If `bits` is more than the machine bits of the layout. When used in a `const` context,
+    /// this fails at compile time.
+    pub const fn with_datacenter_bits(mut self, bits: u8) -> Self {
+        assert!(
+            bits <= self.machine_bits,
+            "datacenter ID can't have more bits than the machine ID"
+        );
+        self.datacenter_bits = bits;
+        self
+    }
+
+    /// Take `bits` bits from the top of the sequence for a tenant tag
+    ///
+    /// ## Panics
+    /// If the tenant has more than 16 bits or leaves no bits for the sequence. When used in a
+    /// `const` context, this fails at compile time.
+    pub const fn with_tenant_bits(mut self, bits: u8) -> Self {
+        let available = self.sequence_bits + self.tenant_bits;
+        assert!(bits <= 16, "tenant can have at most 16 bits");
+        assert!(bits < available, "sequence needs at least 1 bit");
+        self.sequence_bits = available - bits;
+        self.tenant_bits = bits;
+        self
+    }
+
+    /// Number of bits used for the timestamp
+    pub const fn timestamp_bits(&self) -> u8 {
+        self.timestamp_bits
+    }
+
+    /// Number of bits used for the machine ID
+    pub const fn machine_bits(&self) -> u8 {
+        self.machine_bits
+    }
+
+    /// Number of bits used for the sequence number
+    pub const fn sequence_bits(&self) -> u8 {
+        self.sequence_bits
+    }
+
+    /// Number of bits used for the tenant tag
+    pub const fn tenant_bits(&self) -> u8 {
+        self.tenant_bits
+    }
+
+    /// Number of machine ID bits used for the datacenter ID
+    pub const fn datacenter_bits(&self) -> u8 {
+        self.datacenter_bits
+    }
+
+    /// Number of machine ID bits used for the worker ID
+    pub const fn worker_bits(&self) -> u8 {
+        self.machine_bits - self.datacenter_bits
+    }
+
+    /// Largest machine ID that fits in the layout
+    pub const fn max_machine_id(&self) -> u16 {
+        mask(self.machine_bits) as u16
+    }
+
+    /// Largest sequence number that fits in the layout
+    pub const fn max_sequence(&self) -> u32 {
+        mask(self.sequence_bits) as u32
+    }
+
+    /// Largest tenant tag that fits in the layout
+    pub const fn max_tenant(&self) -> u16 {
+        mask(self.tenant_bits) as u16
+    }
+
+    /// Largest datacenter ID that fits in the layout
+    pub const fn max_datacenter_id(&self) -> u16 {
+        mask(self.datacenter_bits) as u16
+    }
+
+    /// Largest worker ID that fits in the layout
+    pub const fn max_worker_id(&self) -> u16 {
+        mask(self.worker_bits()) as u16
+    }
+
+    /// Machine ID of the worker `worker_id` in the datacenter `datacenter_id`
+    ///
+    /// ## Fail condition
+    /// If either ID doesn't fit in its bits
+    pub fn machine_id_for(&self, datacenter_id: u16, worker_id: u16) -> Result<u16, HoraError> {
+        if datacenter_id > self.max_datacenter_id() {
+            return Err(HoraError::InvalidDatacenterId {
+                datacenter_id: datacenter_id as u64,
+                max: self.max_datacenter_id() as u64,
+            });
+        }
+        if worker_id > self.max_worker_id() {
+            return Err(HoraError::InvalidWorkerId {
+                worker_id: worker_id as u64,
+                max: self.max_worker_id() as u64,
+            });
+        }
+        Ok(((datacenter_id as u32) << self.worker_bits()) as u16 | worker_id)
+    }
+
+    /// Largest timestamp a generator writes, leaving the version bits at 0
+    pub(crate) const fn max_ticks(&self) -> u64 {
+        mask(self.timestamp_bits - VERSION_BITS)
+    }
+
+    /// Build a [HoraId] from its parts. The timestamp wraps around if it overflows its bits.
+    pub(crate) fn compose(
+        &self,
+        ticks: u64,
+        machine_id: u16,
+        tenant: u16,
+        sequence: u32,
+    ) -> HoraId {
+        let num = ((ticks & mask(self.timestamp_bits)) << self.timestamp_shift())
+            | ((machine_id as u64 & mask(self.machine_bits)) << self.machine_shift())
+            | ((tenant as u64 & mask(self.tenant_bits)) << self.sequence_bits)
+            | (sequence as u64 & mask(self.sequence_bits));
+        HoraId::from(num)
+    }
+
+    /// Position of the lowest machine ID bit
+    const fn machine_shift(&self) -> u8 {
+        self.tenant_bits + self.sequence_bits
+    }
+
+    /// Position of the lowest timestamp bit
+    const fn timestamp_shift(&self) -> u8 {
+        self.machine_bits + self.machine_shift()
+    }
+
+    /// Timestamp of a [HoraId] in 1/256 second ticks since 2025-01-01
+    pub(crate) fn ticks(&self, id: &HoraId) -> u64 {
+        id.to_u64() >> self.timestamp_shift()
+    }
+
+    /// Timestamp of a [HoraId] as milliseconds since the Unix epoch
+    ///
+    /// See [HoraId::timestamp_millis] for how the stored 1/256 second tick is rounded.
+    pub fn timestamp_millis(&self, id: &HoraId) -> u64 {
+        EPOCH + ticks_to_millis(self.ticks(id))
+    }
+
+    /// Machine ID stored in a [HoraId]
+    pub fn machine_id(&self, id: &HoraId) -> u16 {
+        ((id.to_u64() >> self.machine_shift()) & mask(self.machine_bits)) as u16
+    }
+
+    /// Datacenter ID stored in a [HoraId], the high bits of its machine ID
+    pub fn datacenter_id(&self, id: &HoraId) -> u16 {
+        self.datacenter_of(self.machine_id(id))
+    }
+
+    /// Worker ID stored in a [HoraId], the low bits of its machine ID
+    pub fn worker_id(&self, id: &HoraId) -> u16 {
+        self.worker_of(self.machine_id(id))
+    }
+
+    /// Datacenter part of `machine_id`
+    pub(crate) const fn datacenter_of(&self, machine_id: u16) -> u16 {
+        ((machine_id as u32) >> self.worker_bits()) as u16
+    }
+
+    /// Worker part of `machine_id`
+    pub(crate) const fn worker_of(&self, machine_id: u16) -> u16 {
+        machine_id & self.max_worker_id()
+    }
+
+    /// Tenant tag stored in a [HoraId], 0 if the layout has no tenant bits
+    pub fn tenant(&self, id: &HoraId) -> u16 {
+        ((id.to_u64() >> self.sequence_bits) & mask(self.tenant_bits)) as u16
+    }
+
+    /// Sequence number stored in a [HoraId]
+    pub fn sequence(&self, id: &HoraId) -> u32 {
+        (id.to_u64() & mask(self.sequence_bits)) as u32
+    }
+}
+
+impl Default for HoraLayout {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Mask with the lowest `bits` bits set
+const fn mask(bits: u8) -> u64 {
+    if bits >= 64 {
+        u64::MAX
+    } else {
+        (1 << bits) - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HoraGenerator, HoraParams};
+
+    #[test]
+    fn default_matches_hora_id() {
+        let params = HoraParams {
+            machine_id: 7,
+            ticks: 0x0012_3456_789a,
+            sequence: 0x0102,
+        };
+        let id = HoraId::with_params(params);
+        let layout = HoraLayout::DEFAULT;
+        assert_eq!(layout.compose(0x0012_3456_789a, 7, 0, 0x0102), id);
+        assert_eq!(layout.ticks(&id), 0x0012_3456_789a);
+        assert_eq!(layout.timestamp_millis(&id), id.timestamp_millis());
+        assert_eq!(layout.machine_id(&id), 7);
+        assert_eq!(layout.sequence(&id), 0x0102);
+    }
+
+    #[test]
+    fn presets() {
+        for layout in [
+            HoraLayout::DEFAULT,
+            HoraLayout::WIDE_MACHINE,
+            HoraLayout::WIDE_SEQUENCE,
+        ] {
+            let machine_id = layout.max_machine_id();
+            let sequence = layout.max_sequence();
+            let id = layout.compose(12345, machine_id, 0, sequence);
+            assert_eq!(layout.ticks(&id), 12345);
+            assert_eq!(layout.machine_id(&id), machine_id);
+            assert_eq!(layout.sequence(&id), sequence);
+        }
+        assert_eq!(HoraLayout::WIDE_MACHINE.max_machine_id(), 4095);
+        assert_eq!(HoraLayout::WIDE_SEQUENCE.max_sequence(), 1_048_575);
+    }
+
+    #[test]
+    #[should_panic(expected = "layout must have exactly 64 bits")]
+    fn invalid_layout() {
+        let bits = 16;
+        HoraLayout::new(40, bits, 16);
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp needs at least 40 bits")]
+    fn short_timestamp() {
+        let bits = 39;
+        HoraLayout::new(bits, 9, 16);
+    }
+
+    #[test]
+    fn generator() {
+        let layout = HoraLayout::WIDE_MACHINE;
+        let mut generator = HoraGenerator::with_layout(layout, 4095).unwrap();
+        let id1 = generator.next();
+        let id2 = generator.next();
+        assert!(id1 < id2);
+        assert_eq!(layout.machine_id(&id1), 4095);
+        assert_eq!(layout.machine_id(&id2), 4095);
+
+        assert!(matches!(
+            HoraGenerator::with_layout(layout, 4096),
+            Err(crate::HoraError::InvalidMachineId {
+                machine_id: 4096,
+                max: 4095
+            })
+        ));
+    }
+
+    #[test]
+    fn topology() {
+        let layout = HoraLayout::MULTI_DATACENTER;
+        assert_eq!(layout.datacenter_bits(), 2);
+        assert_eq!(layout.worker_bits(), 6);
+        assert_eq!(layout.max_datacenter_id(), 3);
+        assert_eq!(layout.max_worker_id(), 63);
+        assert_eq!(layout.machine_id_for(2, 17), Ok(145));
+
+        let id = layout.compose(12345, 145, 0, 9);
+        assert_eq!(id.machine_id(), 145);
+        assert_eq!(layout.datacenter_id(&id), 2);
+        assert_eq!(layout.worker_id(&id), 17);
+
+        assert_eq!(
+            layout.machine_id_for(4, 0),
+            Err(crate::HoraError::InvalidDatacenterId {
+                datacenter_id: 4,
+                max: 3
+            })
+        );
+        assert_eq!(
+            layout.machine_id_for(0, 64),
+            Err(crate::HoraError::InvalidWorkerId {
+                worker_id: 64,
+                max: 63
+            })
+        );
+
+        // the whole machine ID can be a datacenter
+        let layout = HoraLayout::WIDE_MACHINE.with_datacenter_bits(12);
+        assert_eq!(layout.machine_id_for(4095, 0), Ok(4095));
+        assert_eq!(HoraLayout::DEFAULT.worker_id(&id), 145);
+    }
+
+    #[test]
+    fn topology_generator() {
+        let clock = crate::MockClock::new(EPOCH + 10_000);
+        let layout = HoraLayout::MULTI_DATACENTER;
+        let machine_id = layout.machine_id_for(3, 63).unwrap();
+        let mut generator =
+            HoraGenerator::with_layout_and_clock(layout, machine_id, clock).unwrap();
+        assert_eq!(generator.datacenter_id(), 3);
+        assert_eq!(generator.worker_id(), 63);
+        assert_eq!(generator.machine_id(), 255);
+        assert_eq!(generator.layout(), layout);
+        assert_eq!(layout.worker_id(&generator.next()), 63);
+
+        let generator = HoraGenerator::with_topology(1, 2).unwrap();
+        assert_eq!((generator.datacenter_id(), generator.worker_id()), (1, 2));
+        assert!(matches!(
+            HoraGenerator::with_topology(0, 64),
+            Err(crate::HoraError::InvalidWorkerId { .. })
+        ));
+    }
+
+    #[test]
+    fn tenant() {
+        let layout = HoraLayout::TENANT;
+        assert_eq!(layout.tenant_bits(), 8);
+        assert_eq!(layout.sequence_bits(), 8);
+        assert_eq!(layout.max_tenant(), 255);
+        assert_eq!(layout.max_sequence(), 255);
+
+        let id = layout.compose(12345, 7, 42, 9);
+        assert_eq!(id.as_bytes()[5..], [7, 42, 9]);
+        assert_eq!(layout.ticks(&id), 12345);
+        assert_eq!(layout.machine_id(&id), 7);
+        assert_eq!(layout.tenant(&id), 42);
+        assert_eq!(layout.sequence(&id), 9);
+        assert_eq!(id.tenant(), 42);
+        assert_eq!(HoraLayout::DEFAULT.tenant(&id), 0);
+
+        // changing the tenant bits again gives the sequence back
+        let layout = layout.with_tenant_bits(4);
+        assert_eq!((layout.tenant_bits(), layout.sequence_bits()), (4, 12));
+        assert_eq!(layout.with_tenant_bits(0), HoraLayout::DEFAULT);
+    }
+
+    #[test]
+    fn tenant_generator() {
+        let clock = crate::MockClock::new(EPOCH + 10_000);
+        let layout = HoraLayout::TENANT;
+        let mut a = HoraGenerator::with_layout_and_clock(layout, 1, clock.clone())
+            .unwrap()
+            .for_tenant(1)
+            .unwrap();
+        let mut b = HoraGenerator::with_layout_and_clock(layout, 1, clock)
+            .unwrap()
+            .for_tenant(2)
+            .unwrap();
+        assert_eq!(a.tenant(), 1);
+        let (id_a, id_b) = (a.next(), b.next());
+        assert_ne!(id_a, id_b);
+        assert_eq!((id_a.tenant(), id_b.tenant()), (1, 2));
+        assert_eq!(layout.sequence(&id_a), layout.sequence(&id_b));
+
+        assert_eq!(
+            a.for_tenant(256).err(),
+            Some(crate::HoraError::InvalidTenant {
+                tenant: 256,
+                max: 255
+            })
+        );
+        assert!(matches!(
+            HoraGenerator::new(1).unwrap().for_tenant(1),
+            Err(crate::HoraError::InvalidTenant { max: 0, .. })
+        ));
+    }
+}
@@ -1,14 +1,18 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 //! Time sorted unique ID generator
 //! IDs are time-sorted and 8 bytes long, which is half the length of a UUID and ULID
 //!
 //! ## Composition
 //! HoraID has 3 parts
-//! - 4 byte timestamp high
-//! - 1 byte timestamp low
+//! - 4 byte timestamp high (seconds)
+//! - 1 byte timestamp low (1/256th of a second)
 //! - 1 byte for machine ID (0-255)
 //! - 2 bytes for sequence number
 //!
+//! The highest 2 bits of the timestamp are the format version, see [HoraId::version]. They stay
+//! 0 until 2059, and allow later formats to coexist with the IDs generated today.
+//!
 //! ## Usage
 //! Generate IDs in a distributed system
 //! ```no_run
@@ -19,50 +23,413 @@
 //!
 //! let id: HoraId = generator.next();
 //! println!("{}", id.to_string()); // example: '00cd01daff010002'
-//! println!("{}", id.to_u64()); // example: 57704355272392706
+//! println!("{}", id.to_u64()); // example: 57704410318438402
+//!
+//! // requires the `chrono` feature
+//! # #[cfg(feature = "chrono")]
 //! println!("{}", id.to_datetime()); // example: 2025-03-20 00:00:00
+//! # #[cfg(feature = "chrono")]
 //! println!("{}", id.to_utc()); // example: 2025-03-20 00:00:00 UTC
 //! ```
 //!
+//! Parse an ID back from its string form.
+//!
+//! ```
+//! use hora_id::HoraId;
+//!
+//! let id: HoraId = "00cd01daff010002".parse().unwrap();
+//! assert_eq!(id.to_u64(), 57704410318438402);
+//! ```
+//!
+//! Use a process-wide generator without passing it around.
+//!
+//! ```no_run
+//! hora_id::init(1).unwrap();
+//!
+//! let id = hora_id::generate();
+//! ```
+//!
 //! Quickly generate a new ID.
 //!
 //! ```no_run
 //! use hora_id::HoraId;
 //! let id = HoraId::rand().unwrap();
 //! ```
+//!
+//! ## `no_std`
+//! Disable the default `std` feature to use HoraID on embedded targets. Generators then read the
+//! time from a clock you supply, see [HoraGenerator::new_with_clock]. The `alloc` feature adds
+//! the methods that return a `String`.
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+// lets the code generated by `#[derive(TypedHoraId)]` refer to `::hora_id` in the tests
+#[cfg(all(test, feature = "derive"))]
+extern crate self as hora_id;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 #[cfg(feature = "chrono")]
 use chrono::{DateTime, NaiveDateTime, Utc};
-use std::time::{SystemTime, UNIX_EPOCH};
+use core::fmt;
+use core::str::FromStr;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use random_sequence::RandomSequence;
+
+#[cfg(feature = "actix")]
+#[cfg_attr(docsrs, doc(cfg(feature = "actix")))]
+mod actix_support;
+#[cfg(feature = "arbitrary")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arbitrary")))]
+mod arbitrary_support;
+#[cfg(target_has_atomic = "64")]
+#[cfg_attr(docsrs, doc(cfg(target_has_atomic = "64")))]
+mod atomic;
+#[cfg(feature = "axum")]
+#[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
+mod axum_support;
+#[cfg(feature = "bson")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bson")))]
+mod bson_support;
+mod bucket;
+mod clock;
+#[cfg(feature = "defmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+mod defmt_support;
+#[cfg(feature = "diesel")]
+#[cfg_attr(docsrs, doc(cfg(feature = "diesel")))]
+mod diesel_support;
+mod encoding;
+mod error;
+#[cfg(feature = "etcd")]
+#[cfg_attr(docsrs, doc(cfg(feature = "etcd")))]
+mod etcd_support;
+#[cfg(feature = "ffi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ffi")))]
+pub mod ffi;
+#[cfg(feature = "std")]
+mod global;
+mod hex;
+#[cfg(feature = "hmac")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hmac")))]
+mod hmac_support;
+#[cfg(feature = "hostname")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hostname")))]
+mod hostname_support;
+mod id128;
+#[cfg(feature = "id-pool")]
+#[cfg_attr(docsrs, doc(cfg(feature = "id-pool")))]
+mod id_pool;
+#[cfg(feature = "jiff")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jiff")))]
+mod jiff_support;
+#[cfg(feature = "juniper")]
+#[cfg_attr(docsrs, doc(cfg(feature = "juniper")))]
+mod juniper_support;
+#[cfg(feature = "ksuid")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ksuid")))]
+mod ksuid_support;
+mod layout;
+#[cfg(feature = "mac-address")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mac-address")))]
+mod mac_address_support;
+mod machine_id;
+mod obfuscate;
+#[cfg(feature = "std")]
+mod pool;
+#[cfg(feature = "postgres")]
+#[cfg_attr(docsrs, doc(cfg(feature = "postgres")))]
+mod postgres_support;
+#[cfg(feature = "quickcheck")]
+#[cfg_attr(docsrs, doc(cfg(feature = "quickcheck")))]
+mod quickcheck_support;
+#[cfg(feature = "std")]
+mod random_sequence;
+mod range;
+#[cfg(feature = "rayon")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+mod rayon_support;
+#[cfg(feature = "redis")]
+#[cfg_attr(docsrs, doc(cfg(feature = "redis")))]
+mod redis_support;
+#[cfg(feature = "rkyv")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rkyv")))]
+mod rkyv_support;
+#[cfg(feature = "rusqlite")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rusqlite")))]
+mod rusqlite_support;
+#[cfg(feature = "sea-orm")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sea-orm")))]
+mod sea_orm_support;
+mod segment;
+#[cfg(feature = "segment-http")]
+#[cfg_attr(docsrs, doc(cfg(feature = "segment-http")))]
+mod segment_http;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+mod serde_support;
+mod snowflake;
+#[cfg(feature = "sqlx")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sqlx")))]
+mod sqlx_support;
+mod state;
+mod stats;
+#[cfg(feature = "futures")]
+mod stream;
+#[cfg(feature = "std")]
+mod sync;
+#[cfg(feature = "thread-local")]
+#[cfg_attr(docsrs, doc(cfg(feature = "thread-local")))]
+mod thread_local_support;
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+mod time_support;
+#[cfg(feature = "tokio")]
+mod tokio_support;
+#[cfg(feature = "tower")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+mod tower_support;
+#[cfg(feature = "tracing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+mod tracing_support;
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+mod typed;
+#[cfg(feature = "ulid")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ulid")))]
+mod ulid_support;
+#[cfg(feature = "utoipa")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utoipa")))]
+mod utoipa_support;
+#[cfg(feature = "uuid")]
+#[cfg_attr(docsrs, doc(cfg(feature = "uuid")))]
+mod uuid_support;
+mod version;
+mod wait;
+#[cfg(feature = "wasm")]
+#[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+mod wasm_support;
+
+#[cfg(target_has_atomic = "64")]
+#[cfg_attr(docsrs, doc(cfg(target_has_atomic = "64")))]
+pub use atomic::AtomicHoraGenerator;
+#[cfg(feature = "axum")]
+#[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
+pub use axum_support::HoraIdRejection;
+#[cfg(feature = "bson")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bson")))]
+pub use bson_support::bson_binary;
+pub use bucket::Granularity;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use clock::MockClock;
+pub use clock::{Clock, SystemClock};
+pub use error::{HoraError, HoraIdParseError};
+#[cfg(feature = "etcd")]
+#[cfg_attr(docsrs, doc(cfg(feature = "etcd")))]
+pub use etcd_support::EtcdMachineIdAllocator;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use global::{generate, init, try_generate};
+#[cfg(feature = "hmac")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hmac")))]
+pub use hmac_support::{SignedHoraId, SigningKey};
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use hora_id_derive::TypedHoraId;
+#[cfg(feature = "hostname")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hostname")))]
+pub use hostname_support::HostnameMachineId;
+#[cfg(feature = "rkyv")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rkyv")))]
+pub use id128::ArchivedHoraId128;
+pub use id128::HoraId128;
+#[cfg(feature = "id-pool")]
+#[cfg_attr(docsrs, doc(cfg(feature = "id-pool")))]
+pub use id_pool::IdPool;
+pub use layout::HoraLayout;
+#[cfg(feature = "mac-address")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mac-address")))]
+pub use mac_address_support::MacAddressMachineId;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use machine_id::EnvMachineId;
+pub use machine_id::{MachineIdProvider, StaticMachineId};
+pub use obfuscate::{ObfuscatedHoraId, ObfuscationKey};
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use pool::HoraGeneratorPool;
+pub use range::HoraIdRange;
+#[cfg(feature = "redis")]
+#[cfg_attr(docsrs, doc(cfg(feature = "redis")))]
+pub use redis_support::RedisMachineIdAllocator;
+pub use segment::{SegmentAllocator, SegmentGenerator};
+#[cfg(feature = "segment-http")]
+#[cfg_attr(docsrs, doc(cfg(feature = "segment-http")))]
+pub use segment_http::HttpSegmentAllocator;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub use serde_support::decimal as serde_decimal;
+pub use snowflake::SnowflakeLayout;
+pub use state::GeneratorState;
+pub use stats::GeneratorStats;
+#[cfg(feature = "futures")]
+#[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+pub use stream::HoraStream;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use sync::SyncHoraGenerator;
+#[cfg(feature = "thread-local")]
+#[cfg_attr(docsrs, doc(cfg(feature = "thread-local")))]
+pub use thread_local_support::ThreadLocalHoraGenerator;
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub use tokio_support::AsyncHoraGenerator;
+#[cfg(feature = "tower")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+pub use tower_support::{HoraRequestId, HoraRequestIdFuture, HoraRequestIdLayer};
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub use typed::__private;
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use typed::TypedHoraId;
+pub use version::HoraParts;
+pub use wait::{Fail, Spin, WaitStrategy};
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use wait::{Sleep, Yield};
+#[cfg(feature = "wasm")]
+#[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+pub use wasm_support::{JsClock, WasmHoraGenerator, WasmHoraId};
+
+/// Default epoch of HoraIDs: Jan 01 2025 12:00:00 am UTC, in milliseconds since the Unix epoch
+///
+/// Generators count time from this epoch unless configured with [HoraGenerator::with_epoch].
+pub const EPOCH: u64 = 1735689600000;
+
+/// Number of timestamp ticks in one second. The lowest timestamp byte counts 1/256th of a second.
+const TICKS_PER_SECOND: u64 = 256;
+
+/// Length of one tick in nanoseconds. 1/256th of a second is exactly 3,906,250 nanoseconds.
+const NANOS_PER_TICK: u64 = 1_000_000_000 / TICKS_PER_SECOND;
+
+/// Largest tick count of a version 0 [HoraId], whose 40 bit timestamp starts with the 2 version
+/// bits
+const MAX_TICKS: u64 = (1 << 38) - 1;
+
+/// Convert a Unix time in milliseconds to ticks since `epoch`
+///
+/// ## Fail condition
+/// If the time is incorrect and before the `epoch` time
+fn ticks_since(now_millis: u64, epoch: u64) -> Result<u64, HoraError> {
+    if now_millis < epoch {
+        return Err(HoraError::ClockBeforeEpoch);
+    }
+    Ok(millis_to_ticks(now_millis - epoch))
+}
+
+/// Get the current time as ticks since [EPOCH]
+#[cfg(feature = "std")]
+fn current_ticks() -> Result<u64, HoraError> {
+    ticks_since(SystemClock.now_millis(), EPOCH)
+}
+
+/// Convert milliseconds to 1/256 second ticks, rounding down to the start of the tick
+fn millis_to_ticks(millis: u64) -> u64 {
+    (millis / 1000) * TICKS_PER_SECOND + (millis % 1000) * TICKS_PER_SECOND / 1000
+}
 
-/// Unix Epoch on Jan 01 2024 12:00:00 am
-const EPOCH: u64 = 1735689600000;
+/// Convert 1/256 second ticks to the first whole millisecond that falls within the tick
+///
+/// Rounding up guarantees that `millis_to_ticks(ticks_to_millis(ticks)) == ticks`.
+fn ticks_to_millis(ticks: u64) -> u64 {
+    (ticks / TICKS_PER_SECOND) * 1000
+        + ((ticks % TICKS_PER_SECOND) * 1000).div_ceil(TICKS_PER_SECOND)
+}
 
-/// Get the current epoch with base epoch starting at [EPOCH]
+/// Convert nanoseconds since the Unix epoch to ticks since [EPOCH]
 ///
 /// ## Fail condition
-/// If the system time is incorrect and before the [EPOCH] time
+/// If the time is before [EPOCH] or after the last tick of version 0 IDs, in 2059
+#[cfg_attr(not(any(feature = "time", feature = "jiff")), allow(dead_code))]
+fn unix_nanos_to_ticks(nanos: i128) -> Result<u64, HoraError> {
+    let since_epoch = nanos - EPOCH as i128 * 1_000_000;
+    if since_epoch < 0 {
+        return Err(HoraError::TimestampOutOfRange);
+    }
+    let ticks = since_epoch / NANOS_PER_TICK as i128;
+    if ticks > MAX_TICKS as i128 {
+        return Err(HoraError::TimestampOutOfRange);
+    }
+    Ok(ticks as u64)
+}
+
+/// Convert milliseconds since the Unix epoch to ticks since [EPOCH]
 ///
-fn current_epoch() -> Result<u64, String> {
-    let mut now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_millis() as u64;
-    if now < EPOCH {
-        return Err("Your device time is incorrect.".to_owned());
+/// ## Fail condition
+/// If the time is before [EPOCH] or after the last tick of version 0 IDs, in 2059
+fn unix_millis_to_ticks(millis: u64) -> Result<u64, HoraError> {
+    let since_epoch = millis
+        .checked_sub(EPOCH)
+        .ok_or(HoraError::TimestampOutOfRange)?;
+    let ticks = millis_to_ticks(since_epoch);
+    if ticks > MAX_TICKS {
+        return Err(HoraError::TimestampOutOfRange);
     }
-    now = now - EPOCH;
-    Ok(now)
+    Ok(ticks)
+}
+
+/// Convert ticks since [EPOCH] to nanoseconds since the Unix epoch
+#[cfg_attr(not(any(feature = "time", feature = "jiff")), allow(dead_code))]
+fn ticks_to_unix_nanos(ticks: u64) -> i128 {
+    EPOCH as i128 * 1_000_000 + ticks as i128 * NANOS_PER_TICK as i128
+}
+
+/// Convert a number of 1/256 second ticks to a [Duration]
+fn ticks_to_duration(ticks: u64) -> Duration {
+    Duration::from_nanos(ticks * NANOS_PER_TICK)
+}
+
+/// Big-endian 5 byte timestamp prefix shared by [HoraId] and [HoraId128]
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+fn ticks_to_bytes(ticks: u64) -> [u8; 5] {
+    let bytes = ticks.to_be_bytes();
+    [bytes[3], bytes[4], bytes[5], bytes[6], bytes[7]]
+}
+
+/// Read the tick count from the first 5 bytes of an ID
+fn ticks_from_bytes(bytes: &[u8]) -> u64 {
+    u64::from_be_bytes([0, 0, 0, bytes[0], bytes[1], bytes[2], bytes[3], bytes[4]])
 }
 
+/// Convert ticks since `epoch` to an exact chrono [Utc] datetime
+#[cfg(feature = "chrono")]
+fn ticks_to_utc(ticks: u64, epoch: u64) -> DateTime<Utc> {
+    let seconds = epoch / 1000 + ticks / TICKS_PER_SECOND;
+    let nanos = (epoch % 1000) * 1_000_000 + (ticks % TICKS_PER_SECOND) * NANOS_PER_TICK;
+    DateTime::from_timestamp(seconds as i64, 0).unwrap()
+        + chrono::TimeDelta::nanoseconds(nanos as i64)
+}
+
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
 pub(crate) struct HoraParams {
     machine_id: u8,
-    epoch: u64,
+    /// Timestamp in 1/256 second ticks since [EPOCH]
+    ticks: u64,
     sequence: u16,
 }
 
 /// ID Generator with guarantee to generate time-based unique IDs on a single machine
 ///
+/// The generator reads the time from a [Clock]. [HoraGenerator::new] uses the [SystemClock],
+/// [HoraGenerator::new_with_clock] accepts any other source such as the RTC of an embedded
+/// device or a [MockClock] in tests. While it waits for the clock, it follows a
+/// [WaitStrategy] `W`, which spins by default.
+///
 /// ## Usage
 /// ```no_run
 /// use hora_id::{HoraGenerator, HoraId};
@@ -74,67 +441,754 @@ pub(crate) struct HoraParams {
 /// // generate another ID
 /// let another_id: HoraId = generator.next();
 /// ```
-pub struct HoraGenerator {
-    /// Unique Machine identifier with support for max 256 unique machines
-    machine_id: u8,
-    /// sequence number in the same epoch,
-    sequence: u16,
-    /// Last time an ID was generated
+pub struct HoraGenerator<C = SystemClock, W = Spin> {
+    /// Unique Machine identifier, up to 256 unique machines with the default layout
+    machine_id: u16,
+    /// Bit widths of the generated IDs
+    layout: HoraLayout,
+    /// Tenant tag written into the IDs, see [HoraGenerator::for_tenant]
+    tenant: u16,
+    /// sequence number in the same tick
+    sequence: u32,
+    /// Last tick an ID was generated in
     last_gen: u64,
+    /// Unix time in milliseconds that timestamps are counted from
+    epoch: u64,
+    /// What to do when the clock moves backwards
+    rollback_policy: RollbackPolicy,
+    /// Continue into the next tick instead of waiting or failing, see [HoraGenerator::monotonic]
+    monotonic: bool,
+    /// The clock was behind `last_gen` when last read, so a rollback is only reported once
+    behind: bool,
+    /// All sequence numbers of `last_gen` are used and this was reported
+    exhausted: bool,
+    /// Counters for [HoraGenerator::stats]
+    stats: GeneratorStats,
+    /// Values drawn for the current tick, see [HoraGenerator::random_sequence]
+    #[cfg(feature = "std")]
+    random_sequence: Option<RandomSequence>,
+    /// Source of the current time in milliseconds since the Unix epoch
+    clock: C,
+    /// What to do while waiting for the clock
+    wait_strategy: W,
 }
 
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 impl HoraGenerator {
-    pub fn new(machine_id: u8) -> Result<Self, String> {
-        let epoch = current_epoch()?;
-        let epoch = rescale_epoch(epoch);
+    pub fn new(machine_id: u8) -> Result<Self, HoraError> {
+        Self::with_layout(HoraLayout::DEFAULT, machine_id as u16)
+    }
+
+    /// Create a generator that produces IDs with a custom [HoraLayout]
+    ///
+    /// ## Fail condition
+    /// - If `machine_id` doesn't fit in the machine bits of the layout
+    /// - If the system time is before the HoraID epoch (2025-01-01)
+    pub fn with_layout(layout: HoraLayout, machine_id: u16) -> Result<Self, HoraError> {
+        Self::with_layout_and_clock(layout, machine_id, SystemClock)
+    }
+
+    /// Create a generator for the worker `worker_id` in the datacenter `datacenter_id`, with
+    /// [HoraLayout::MULTI_DATACENTER]
+    ///
+    /// Use [HoraLayout::machine_id_for] with [HoraGenerator::with_layout] for other splits.
+    ///
+    /// ## Fail condition
+    /// - If `datacenter_id` is above 3 or `worker_id` is above 63
+    /// - If the system time is before the HoraID epoch (2025-01-01)
+    pub fn with_topology(datacenter_id: u8, worker_id: u8) -> Result<Self, HoraError> {
+        let layout = HoraLayout::MULTI_DATACENTER;
+        let machine_id = layout.machine_id_for(datacenter_id as u16, worker_id as u16)?;
+        Self::with_layout(layout, machine_id)
+    }
+
+    /// Create a generator with the machine ID acquired from a [MachineIdProvider]
+    ///
+    /// ## Fail condition
+    /// If the provider fails to acquire a machine ID or the system time is incorrect
+    ///
+    /// ## Usage
+    /// ```
+    /// use hora_id::{HoraGenerator, StaticMachineId};
+    ///
+    /// let mut generator = HoraGenerator::from_provider(&StaticMachineId(3)).unwrap();
+    /// assert_eq!(generator.next().machine_id(), 3);
+    /// ```
+    pub fn from_provider<P: MachineIdProvider + ?Sized>(provider: &P) -> Result<Self, HoraError> {
+        Self::new(provider.acquire()?)
+    }
+
+    /// Create a generator configured by environment variables
+    ///
+    /// The machine ID is read from `HORA_MACHINE_ID`, see [EnvMachineId]. If `HORA_EPOCH` is set,
+    /// it is used as a custom epoch in milliseconds since the Unix epoch, see
+    /// [HoraGenerator::with_epoch].
+    ///
+    /// ## Fail condition
+    /// If `HORA_MACHINE_ID` is missing or not a number from 0 to 255, if `HORA_EPOCH` is not a
+    /// number, or if the system time is before the epoch
+    ///
+    /// ## Usage
+    /// ```no_run
+    /// use hora_id::HoraGenerator;
+    ///
+    /// // HORA_MACHINE_ID=7 HORA_EPOCH=1767225600000 ./server
+    /// let mut generator = HoraGenerator::from_env().unwrap();
+    /// ```
+    pub fn from_env() -> Result<Self, HoraError> {
+        let generator = Self::from_provider(&EnvMachineId::default())?;
+        match machine_id::read_env("HORA_EPOCH")? {
+            None => Ok(generator),
+            Some(value) => match value.trim().parse() {
+                Ok(epoch_millis) => generator.with_epoch(epoch_millis),
+                Err(_) => Err(HoraError::InvalidEnvVar {
+                    name: "HORA_EPOCH".to_owned(),
+                    value,
+                    expected: "milliseconds since the Unix epoch",
+                }),
+            },
+        }
+    }
+}
+
+impl<C: Clock> HoraGenerator<C> {
+    /// Create a generator that reads the time from `clock` instead of the system clock
+    ///
+    /// `clock` is any [Clock], including closures returning milliseconds since the Unix epoch.
+    /// This is the only way to create a generator without the `std` feature.
+    ///
+    /// ## Fail condition
+    /// If `clock` returns a time before the HoraID epoch (2025-01-01)
+    ///
+    /// ## Usage
+    /// ```
+    /// use hora_id::HoraGenerator;
+    ///
+    /// # fn rtc_millis() -> u64 { 1750000000000 }
+    /// let mut generator = HoraGenerator::new_with_clock(1, rtc_millis).unwrap();
+    /// let id = generator.next();
+    /// assert_eq!(id.timestamp_millis(), 1750000000000);
+    /// ```
+    pub fn new_with_clock(machine_id: u8, clock: C) -> Result<Self, HoraError> {
+        Self::with_layout_and_clock(HoraLayout::DEFAULT, machine_id as u16, clock)
+    }
+
+    /// Create a generator with a custom [HoraLayout] that reads the time from `clock`
+    ///
+    /// See [HoraGenerator::with_layout] and [HoraGenerator::new_with_clock].
+    pub fn with_layout_and_clock(
+        layout: HoraLayout,
+        machine_id: u16,
+        clock: C,
+    ) -> Result<Self, HoraError> {
+        if machine_id > layout.max_machine_id() {
+            return Err(HoraError::InvalidMachineId {
+                machine_id: machine_id as u64,
+                max: layout.max_machine_id() as u64,
+            });
+        }
+        let ticks = ticks_since(clock.now_millis(), EPOCH)?;
         Ok(Self {
             machine_id,
+            layout,
+            tenant: 0,
             sequence: 0,
-            last_gen: epoch,
+            last_gen: ticks,
+            epoch: EPOCH,
+            rollback_policy: RollbackPolicy::default(),
+            monotonic: false,
+            behind: false,
+            exhausted: false,
+            stats: GeneratorStats::default(),
+            #[cfg(feature = "std")]
+            random_sequence: None,
+            clock,
+            wait_strategy: Spin,
         })
     }
+}
+
+impl<C: Clock, W: WaitStrategy> HoraGenerator<C, W> {
+    /// Machine ID written into every generated [HoraId]
+    pub fn machine_id(&self) -> u16 {
+        self.machine_id
+    }
+
+    /// Datacenter part of the machine ID, see [HoraLayout::with_datacenter_bits]
+    pub fn datacenter_id(&self) -> u16 {
+        self.layout.datacenter_of(self.machine_id)
+    }
+
+    /// Worker part of the machine ID, see [HoraLayout::with_datacenter_bits]
+    pub fn worker_id(&self) -> u16 {
+        self.layout.worker_of(self.machine_id)
+    }
+
+    /// Bit layout of the generated IDs
+    pub fn layout(&self) -> HoraLayout {
+        self.layout
+    }
+
+    /// Tenant tag written into every generated [HoraId], see [HoraGenerator::for_tenant]
+    pub fn tenant(&self) -> u16 {
+        self.tenant
+    }
+
+    /// Epoch the timestamps count from, in milliseconds since the Unix epoch, see
+    /// [HoraGenerator::with_epoch]
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Get the current time of the clock as ticks since the generator's epoch
+    ///
+    /// ## Fail condition
+    /// If the clock is before the epoch, or so far after it that the timestamp would reach the
+    /// version bits
+    fn current_ticks(&self) -> Result<u64, HoraError> {
+        let ticks = ticks_since(self.clock.now_millis(), self.epoch)?;
+        if ticks > self.layout.max_ticks() {
+            return Err(HoraError::TimestampOutOfRange);
+        }
+        Ok(ticks)
+    }
+
+    /// Count timestamps from a custom epoch instead of [EPOCH]
+    ///
+    /// A later epoch extends the lifetime of the IDs, for example by anchoring them to the launch
+    /// date of a product. IDs generated with a custom epoch must be decoded with the same epoch,
+    /// see [HoraId::timestamp_millis_with_epoch].
+    ///
+    /// ## Fail condition
+    /// If the system time is before `epoch_millis`
+    ///
+    /// ## Usage
+    /// ```no_run
+    /// use hora_id::HoraGenerator;
+    ///
+    /// let launch = 1767225600000; // 2026-01-01
+    /// let mut generator = HoraGenerator::new(1).unwrap().with_epoch(launch).unwrap();
+    /// let id = generator.next();
+    /// println!("{}", id.timestamp_millis_with_epoch(launch));
+    /// ```
+    pub fn with_epoch(mut self, epoch_millis: u64) -> Result<Self, HoraError> {
+        self.last_gen = ticks_since(self.clock.now_millis(), epoch_millis)?;
+        self.sequence = 0;
+        self.epoch = epoch_millis;
+        Ok(self)
+    }
+
+    /// Write `tenant` into the tenant bits of every generated ID
+    ///
+    /// The layout needs tenant bits, such as [HoraLayout::TENANT]. Sequences are counted per
+    /// generator, so generators of different tenants can share a machine ID, but each tenant
+    /// must only have one generator per machine ID.
+    ///
+    /// ## Fail condition
+    /// If `tenant` doesn't fit in the tenant bits of the layout
+    ///
+    /// ## Usage
+    /// ```
+    /// use hora_id::{HoraGenerator, HoraLayout};
+    ///
+    /// let mut acme = HoraGenerator::with_layout(HoraLayout::TENANT, 1)
+    ///     .unwrap()
+    ///     .for_tenant(7)
+    ///     .unwrap();
+    /// assert_eq!(acme.next().tenant(), 7);
+    /// ```
+    pub fn for_tenant(mut self, tenant: u16) -> Result<Self, HoraError> {
+        if tenant > self.layout.max_tenant() {
+            return Err(HoraError::InvalidTenant {
+                tenant: tenant as u64,
+                max: self.layout.max_tenant() as u64,
+            });
+        }
+        self.tenant = tenant;
+        Ok(self)
+    }
+
+    /// Set the [RollbackPolicy] used when the system clock moves backwards
+    ///
+    /// ## Usage
+    /// ```no_run
+    /// use hora_id::{HoraGenerator, RollbackPolicy};
+    ///
+    /// let mut generator = HoraGenerator::new(1)
+    ///     .unwrap()
+    ///     .with_rollback_policy(RollbackPolicy::Error);
+    /// ```
+    pub fn with_rollback_policy(mut self, policy: RollbackPolicy) -> Self {
+        self.rollback_policy = policy;
+        self
+    }
+
+    /// Never wait for the clock or fail because of it
+    ///
+    /// Every ID is strictly greater than the previous one of this generator, like the monotonic
+    /// mode of ULID. While the clock is behind the last generated timestamp, the generator keeps
+    /// counting up the sequence of that timestamp, ignoring the [RollbackPolicy]. When the
+    /// sequence of a tick is used up, it continues with the next tick ahead of the clock. The
+    /// timestamps of the IDs can run ahead of the clock during a burst or after a rollback, and
+    /// the clock catches up later.
+    ///
+    /// To keep the ordering across restarts, save and restore the [GeneratorState].
+    ///
+    /// Random sequence numbers aren't ordered, so this turns off
+    /// [HoraGenerator::random_sequence]. Of the two, the one enabled last applies.
+    ///
+    /// ## Usage
+    /// ```no_run
+    /// use hora_id::HoraGenerator;
+    ///
+    /// let mut generator = HoraGenerator::new(1).unwrap().monotonic();
+    /// let first = generator.next();
+    /// assert!(generator.next() > first);
+    /// ```
+    pub fn monotonic(mut self) -> Self {
+        self.monotonic = true;
+        #[cfg(feature = "std")]
+        {
+            self.random_sequence = None;
+        }
+        self
+    }
+
+    /// Fill the sequence bits with random values instead of counting up
+    ///
+    /// With a counter, the sequence numbers of IDs from the same tick reveal in which order the
+    /// IDs were created and how many were created in that tick. In this mode every ID gets a
+    /// random unused sequence number of its tick, drawing again on a collision, so IDs are only
+    /// ordered by their tick. The generator still produces at most as many IDs per tick as
+    /// there are sequence numbers and then waits as usual.
+    ///
+    /// Drawing gets slower as a tick fills up, so this mode suits services that generate far
+    /// fewer IDs per tick than the sequence allows.
+    ///
+    /// Enable it before restoring a [GeneratorState]. The state doesn't record which values were
+    /// used, so the generator skips the rest of the restored tick.
+    ///
+    /// This turns off [HoraGenerator::monotonic], whose IDs have to be ordered within a tick.
+    ///
+    /// ## Usage
+    /// ```no_run
+    /// use hora_id::HoraGenerator;
+    ///
+    /// let mut generator = HoraGenerator::new(1).unwrap().random_sequence();
+    /// let id = generator.next();
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn random_sequence(mut self) -> Self {
+        self.monotonic = false;
+        self.random_sequence = Some(RandomSequence::default());
+        self
+    }
+
+    /// Set the [WaitStrategy] used while waiting for the clock
+    ///
+    /// ## Usage
+    /// ```no_run
+    /// use hora_id::{HoraGenerator, Sleep};
+    /// use std::time::Duration;
+    ///
+    /// let mut generator = HoraGenerator::new(1)
+    ///     .unwrap()
+    ///     .with_wait_strategy(Sleep(Duration::from_millis(1)));
+    /// let id = generator.next();
+    /// ```
+    pub fn with_wait_strategy<S: WaitStrategy>(self, strategy: S) -> HoraGenerator<C, S> {
+        HoraGenerator {
+            machine_id: self.machine_id,
+            layout: self.layout,
+            tenant: self.tenant,
+            sequence: self.sequence,
+            last_gen: self.last_gen,
+            epoch: self.epoch,
+            rollback_policy: self.rollback_policy,
+            monotonic: self.monotonic,
+            behind: self.behind,
+            exhausted: self.exhausted,
+            stats: self.stats,
+            #[cfg(feature = "std")]
+            random_sequence: self.random_sequence,
+            clock: self.clock,
+            wait_strategy: strategy,
+        }
+    }
 
     /// Generate a new [HoraId]
+    ///
+    /// If all sequence numbers of the current tick are used (65,536 with the default layout),
+    /// this method waits until the clock moves on to the next tick instead of reusing a sequence
+    /// number, following the [WaitStrategy].
+    ///
+    /// ## Panics
+    /// If the system time is before the HoraID epoch (2025-01-01).
+    /// Use [HoraGenerator::try_next] to handle this case.
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> HoraId {
+        self.try_next().expect("failed to generate HoraId")
+    }
+
+    /// Generate a new [HoraId], returning an error instead of panicking
+    ///
+    /// ## Fail condition
+    /// - If the system time is before the HoraID epoch (2025-01-01)
+    /// - If the clock moved backwards and the [RollbackPolicy] is [RollbackPolicy::Error]
+    /// - If the generator has to wait and the [WaitStrategy] gives up
+    pub fn try_next(&mut self) -> Result<HoraId, HoraError> {
+        let ticks = self.next_ticks()?;
+        Ok(self.compose_id(ticks))
+    }
+
+    /// Generate `n` new [HoraId]s at once
+    ///
+    /// The clock is read once per tick instead of once per ID, and the remaining sequence
+    /// numbers of a tick are handed out without further checks. This is considerably faster than
+    /// calling [HoraGenerator::next] in a loop, for example to prepare a bulk insert.
+    ///
+    /// ## Panics
+    /// Under the same conditions as [HoraGenerator::next].
+    /// Use [HoraGenerator::try_next_n_into] to handle errors.
+    ///
+    /// ## Usage
+    /// ```no_run
+    /// use hora_id::HoraGenerator;
+    ///
+    /// let mut generator = HoraGenerator::new(1).unwrap();
+    /// let ids = generator.next_n(10_000);
+    /// assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn next_n(&mut self, n: usize) -> Vec<HoraId> {
+        let mut ids = Vec::with_capacity(n);
+        self.next_n_into(n, &mut ids);
+        ids
+    }
+
+    /// Append `n` new [HoraId]s to `ids`, see [HoraGenerator::next_n]
+    ///
+    /// Reusing the same vector between batches avoids an allocation per batch.
+    ///
+    /// ## Panics
+    /// Under the same conditions as [HoraGenerator::next].
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn next_n_into(&mut self, n: usize, ids: &mut Vec<HoraId>) {
+        self.try_next_n_into(n, ids)
+            .expect("failed to generate HoraId")
+    }
+
+    /// Append `n` new [HoraId]s to `ids`, returning an error instead of panicking
+    ///
+    /// ## Fail condition
+    /// Same as [HoraGenerator::try_next]. The IDs generated before the error remain in `ids`.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn try_next_n_into(&mut self, n: usize, ids: &mut Vec<HoraId>) -> Result<(), HoraError> {
+        ids.reserve(n);
+        let mut remaining = n;
+        while remaining > 0 {
+            let ticks = self.next_ticks()?;
+            ids.push(self.compose_id(ticks));
+            remaining -= 1;
+
+            // use up the rest of the tick without reading the clock again
+            let available = (self.layout.max_sequence() - self.sequence) as usize;
+            for _ in 0..available.min(remaining) {
+                self.sequence += 1;
+                ids.push(self.compose_id(ticks));
+            }
+            remaining -= available.min(remaining);
+        }
+        Ok(())
+    }
+
+    /// Reserve a block of up to `size` consecutive [HoraId]s, see [HoraGenerator::try_next_block]
+    ///
+    /// ## Panics
+    /// Under the same conditions as [HoraGenerator::next].
+    pub fn next_block(&mut self, size: u32) -> HoraIdRange {
+        self.try_next_block(size)
+            .expect("failed to generate HoraId")
+    }
+
+    /// Reserve a block of up to `size` consecutive [HoraId]s in one tick
+    ///
+    /// The IDs of the block are consecutive numbers, so the block can be handed to another
+    /// process as a [HoraIdRange] and used there without further coordination, see
+    /// [SegmentGenerator]. The block ends early at the last sequence number of the tick, so it
+    /// holds from one to [HoraLayout::max_sequence] + 1 IDs. With
+    /// [HoraGenerator::random_sequence] every block holds a single ID.
+    ///
+    /// ## Fail condition
+    /// Same as [HoraGenerator::try_next]
+    ///
+    /// ## Usage
+    /// ```no_run
+    /// use hora_id::HoraGenerator;
+    ///
+    /// let mut generator = HoraGenerator::new(1).unwrap();
+    /// let block = generator.try_next_block(10_000).unwrap();
+    /// for num in block.to_u64_range() {
+    ///     assert_eq!(hora_id::HoraId::from(num).machine_id(), 1);
+    /// }
+    /// ```
+    pub fn try_next_block(&mut self, size: u32) -> Result<HoraIdRange, HoraError> {
+        let ticks = self.next_ticks()?;
+        let start = self.compose_id(ticks);
+        #[cfg(feature = "std")]
+        if self.random_sequence.is_some() {
+            return Ok(HoraIdRange::new(start, start));
+        }
+        let extra = size
+            .saturating_sub(1)
+            .min(self.layout.max_sequence() - self.sequence);
+        self.sequence += extra;
+        self.stats.record_ids(extra as u64, self.sequence);
+        let end = self
+            .layout
+            .compose(ticks, self.machine_id, self.tenant, self.sequence);
+        Ok(HoraIdRange::new(start, end))
+    }
+
+    /// Reserve the next sequence number and return the tick it belongs to
+    ///
+    /// Applies the [RollbackPolicy] and waits for the next tick when the sequence is exhausted.
+    fn next_ticks(&mut self) -> Result<u64, HoraError> {
         loop {
-            let epoch = current_epoch().unwrap();
-            let scaled_epoch = rescale_epoch(epoch);
-            if scaled_epoch > self.last_gen {
+            if let Some(ticks) = self.reserve()? {
+                return Ok(ticks);
+            }
+            if self.wait_strategy.wait().is_break() {
+                let ticks = self.current_ticks()?;
+                return Err(if ticks < self.last_gen {
+                    HoraError::ClockMovedBackwards {
+                        behind: ticks_to_duration(self.last_gen - ticks),
+                    }
+                } else {
+                    HoraError::SequenceExhausted
+                });
+            }
+        }
+    }
+
+    /// Reserve the next sequence number without waiting for the clock
+    ///
+    /// Returns `None` if the generator has to wait, either because all sequence numbers of the
+    /// current tick are used or because the clock is behind with
+    /// [RollbackPolicy::WaitUntilCaughtUp].
+    fn reserve(&mut self) -> Result<Option<u64>, HoraError> {
+        let mut ticks = self.current_ticks()?;
+        if self.monotonic {
+            if ticks > self.last_gen {
+                self.sequence = 0;
+            } else if self.sequence == self.layout.max_sequence() {
+                // borrow the next tick instead of waiting for it, unless it would reach the
+                // version bits
+                if self.last_gen >= self.layout.max_ticks() {
+                    return Err(HoraError::TimestampOutOfRange);
+                }
                 self.sequence = 0;
+                ticks = self.last_gen + 1;
+            } else {
+                self.sequence += 1;
+                ticks = self.last_gen;
+            }
+            self.last_gen = ticks;
+            return Ok(Some(ticks));
+        }
+        if ticks < self.last_gen {
+            self.clock_behind(ticks);
+            match self.rollback_policy {
+                RollbackPolicy::Error => {
+                    return Err(HoraError::ClockMovedBackwards {
+                        behind: ticks_to_duration(self.last_gen - ticks),
+                    });
+                }
+                RollbackPolicy::WaitUntilCaughtUp => return Ok(None),
+                RollbackPolicy::BorrowSequence => ticks = self.last_gen,
             }
+        } else {
+            self.behind = false;
+        }
 
-            // generate_id
+        if ticks > self.last_gen {
+            self.sequence = 0;
+            self.exhausted = false;
+        } else if self.sequence == self.layout.max_sequence() {
+            // sequence exhausted, wait for the next tick
+            self.sequence_exhausted();
+            return Ok(None);
+        } else {
             self.sequence += 1;
-            let params = HoraParams {
-                machine_id: self.machine_id,
-                epoch,
-                sequence: self.sequence + 1,
-            };
-            let id = HoraId::with_params(params);
-            self.last_gen = scaled_epoch;
-            break id;
         }
+
+        self.last_gen = ticks;
+        Ok(Some(ticks))
+    }
+
+    /// Note that the clock is at `ticks`, behind the last generated tick
+    ///
+    /// Called on every attempt while the clock is behind, reports the rollback only once.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    fn clock_behind(&mut self, ticks: u64) {
+        if self.behind {
+            return;
+        }
+        self.behind = true;
+        self.stats.record_rollback();
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            behind = ?ticks_to_duration(self.last_gen - ticks),
+            policy = ?self.rollback_policy,
+            "system clock moved backwards"
+        );
     }
+
+    /// Note that all sequence numbers of the last generated tick are used
+    ///
+    /// Called on every attempt while waiting for the next tick, reports it only once per tick.
+    fn sequence_exhausted(&mut self) {
+        if self.exhausted {
+            return;
+        }
+        self.exhausted = true;
+        self.stats.record_exhaustion();
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            ticks = self.last_gen,
+            "all sequence numbers of the tick are used, waiting for the next tick"
+        );
+    }
+
+    /// Generate a new [HoraId] if that is possible without waiting for the clock
+    ///
+    /// Async wrappers call this and sleep for [HoraGenerator::until_next_tick] on `None`.
+    #[cfg_attr(not(any(feature = "futures", feature = "tokio")), allow(dead_code))]
+    pub(crate) fn poll_next_id(&mut self) -> Result<Option<HoraId>, HoraError> {
+        let id = self.reserve()?.map(|ticks| self.compose_id(ticks));
+        Ok(id)
+    }
+
+    /// Build the ID for the sequence number reserved in `ticks`
+    fn compose_id(&mut self, ticks: u64) -> HoraId {
+        self.stats.record_id(self.sequence);
+        #[cfg(feature = "std")]
+        if let Some(random) = &mut self.random_sequence {
+            let sequence = random.draw(ticks, self.layout.max_sequence());
+            return self
+                .layout
+                .compose(ticks, self.machine_id, self.tenant, sequence);
+        }
+        self.layout
+            .compose(ticks, self.machine_id, self.tenant, self.sequence)
+    }
+
+    /// Time until the clock reaches the tick after the last generated ID
+    #[cfg_attr(not(any(feature = "futures", feature = "tokio")), allow(dead_code))]
+    pub(crate) fn until_next_tick(&self) -> Duration {
+        let next = self.epoch + ticks_to_millis(self.last_gen + 1);
+        Duration::from_millis(next.saturating_sub(self.clock.now_millis()))
+    }
+}
+
+/// An endless stream of [HoraId]s
+///
+/// [HoraGenerator::next] and [Iterator::next] both generate a new ID, so all iterator adapters
+/// can be used to produce IDs.
+///
+/// ```no_run
+/// use hora_id::{HoraGenerator, HoraId};
+///
+/// let generator = HoraGenerator::new(1).unwrap();
+/// let ids: Vec<HoraId> = generator.take(1000).collect();
+/// ```
+///
+/// ## Panics
+/// Under the same conditions as [HoraGenerator::next]. The iterator never returns `None`.
+impl<C: Clock, W: WaitStrategy> Iterator for HoraGenerator<C, W> {
+    type Item = HoraId;
+
+    fn next(&mut self) -> Option<HoraId> {
+        Some(HoraGenerator::next(self))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<C: Clock, W: WaitStrategy> core::iter::FusedIterator for HoraGenerator<C, W> {}
+
+/// What a [HoraGenerator] does when the system clock moves backwards
+///
+/// Clocks can jump backwards after an NTP correction or when a virtual machine is resumed.
+/// Using the earlier time as-is could reuse a timestamp and produce duplicate IDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RollbackPolicy {
+    /// Return [HoraError::ClockMovedBackwards] from [HoraGenerator::try_next]
+    Error,
+    /// Spin until the clock catches up with the last generated timestamp
+    WaitUntilCaughtUp,
+    /// Keep using the last generated timestamp and continue its sequence.
+    /// If the sequence runs out, wait until the clock catches up.
+    #[default]
+    BorrowSequence,
 }
 
 /// A time-sorted 8-byte (64-bit) unique identifier
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+///
+/// IDs are ordered by their big-endian byte representation, which places the timestamp first.
+/// Comparing two [HoraId]s therefore orders them by generation time, then machine, then sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow)
+)]
+#[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::BigInt))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Binary))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash))
+)]
 pub struct HoraId {
     inner: [u8; 8],
 }
 
 impl HoraId {
+    /// The all-zero [HoraId], sorting before every generated ID
+    ///
+    /// Use it as a sentinel or a database default for "no ID".
+    pub const NIL: HoraId = HoraId { inner: [0; 8] };
+
+    /// The largest [HoraId], with all bits set, sorting after every generated ID
+    ///
+    /// Use it as the open end of a range scan.
+    pub const MAX: HoraId = HoraId { inner: [0xff; 8] };
+
+    /// Whether this is [HoraId::NIL]
+    pub const fn is_nil(&self) -> bool {
+        self.to_u64() == 0
+    }
+
     /// Quickly generate a new [HoraId]
     ///
     /// ## Caution
     /// Calling this method doesn't guarantee a unique ID for every call.
     /// This method shall only be used when you need to generate a new id rapidly.
     ///
-    pub fn new(machine_id: Option<u8>) -> Result<Self, String> {
-        let epoch = current_epoch()?;
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn new(machine_id: Option<u8>) -> Result<Self, HoraError> {
+        let ticks = current_ticks()?;
         let params = HoraParams {
             machine_id: machine_id.unwrap_or(0),
-            epoch,
+            ticks,
             sequence: 0,
         };
         let id = Self::with_params(params);
@@ -145,11 +1199,13 @@ impl HoraId {
     ///
     /// ## More info
     /// This method generates a random machine_id and sequence number
-    pub fn rand() -> Result<Self, String> {
-        let epoch = current_epoch()?;
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn rand() -> Result<Self, HoraError> {
+        let ticks = current_ticks()?;
         let params = HoraParams {
             machine_id: rand::random::<u8>(),
-            epoch,
+            ticks,
             sequence: rand::random::<u16>(),
         };
         let id = Self::with_params(params);
@@ -162,9 +1218,10 @@ impl HoraId {
     /// This method is mainly used by the [HoraGenerator] generator to get a new [HoraId].
     /// THe `HoraId::new` method also calls this method after getting the current epoch.
     ///
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
     fn with_params(params: HoraParams) -> Self {
-        let high = (params.epoch / 1000) as u32;
-        let low = (params.epoch % 1000) as u16;
+        let high = (params.ticks / TICKS_PER_SECOND) as u32;
+        let low = (params.ticks % TICKS_PER_SECOND) as u8;
 
         // create a default bytes array
         let mut tuid = [0u8; 8];
@@ -176,7 +1233,7 @@ impl HoraId {
         tuid[2] = bytes[2];
         tuid[3] = bytes[3];
         // set time low
-        tuid[4] = rescale_low(low);
+        tuid[4] = low;
 
         // add machine_id
         tuid[5] = params.machine_id;
@@ -192,92 +1249,337 @@ impl HoraId {
     }
 
     /// Convert a [HoraId] to a number
-    pub fn to_u64(&self) -> u64 {
+    ///
+    /// Databases without unsigned 64-bit integers store the number as a signed `BIGINT`. The
+    /// sign bit is the highest bit of the format version, see [HoraId::version], and version 0
+    /// IDs never set it, so the signed numbers sort in the same order as the IDs.
+    pub const fn to_u64(&self) -> u64 {
         u64::from_be_bytes(self.inner)
     }
 
     /// Convert a number to [HoraId]
-    pub fn from_u64(num: u64) -> Option<Self> {
+    ///
+    /// This and [HoraId::from_bytes] are `const`, so IDs can be declared as constants:
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    ///
+    /// const FIXTURE: HoraId = HoraId::from_bytes([0x00, 0xcd, 0x01, 0xda, 0xff, 0x01, 0x00, 0x02]);
+    /// const SAME: Option<HoraId> = HoraId::from_u64(57704410318438402);
+    /// assert_eq!(SAME, Some(FIXTURE));
+    /// ```
+    pub const fn from_u64(num: u64) -> Option<Self> {
         let d: [u8; 8] = num.to_be_bytes();
         let id = Self { inner: d };
         Some(id)
     }
 
-    /// Convert a [HoraId] to a [String]
-    pub fn to_string(&self) -> String {
-        format!(
-            "{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
-            self.inner[0],
-            self.inner[1],
-            self.inner[2],
-            self.inner[3],
-            self.inner[4],
-            self.inner[5],
-            self.inner[6],
-            self.inner[7]
-        )
-    }
-
-    /// Create a [HoraId] from a string slice
-    pub fn from_str(s: &str) -> Option<Self> {
-        if s.len() != 16 {
-            return None;
-        }
-        let num = u64::from_str_radix(s, 16).ok()?;
-        let bytes: [u8; 8] = num.to_be_bytes();
-        let id = Self { inner: bytes };
-        Some(id)
+    /// Create a [HoraId] from its big-endian byte representation
+    pub const fn from_bytes(bytes: [u8; 8]) -> Self {
+        Self { inner: bytes }
     }
 
     /// Get the byte representation of [HoraId]
-    pub fn as_bytes(&self) -> &[u8] {
+    pub const fn as_bytes(&self) -> &[u8] {
         &self.inner
     }
 
-    /// Retrieve a chrono [NaiveDateTime] from [HoraId]
-    #[cfg(feature = "chrono")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
-    pub fn to_datetime(&self) -> NaiveDateTime {
-        let mut high = [0; 4];
-        for i in 0..4 {
-            high[i] = self.inner[i];
-        }
-        let high = u32::from_be_bytes(high);
-        let low = u8::from_be_bytes([self.inner[4]]);
-        let low = upscale_low(low);
-
-        let timestamp = (high as u64 * 1000) + low as u64 + EPOCH;
-        NaiveDateTime::from_timestamp_millis(timestamp as i64).unwrap()
+    /// Timestamp of the [HoraId] as milliseconds since the Unix epoch
+    ///
+    /// The ID stores time in 1/256 second ticks, so this returns the first whole millisecond
+    /// within the stored tick. Any millisecond of that tick would have produced the same ID.
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap();
+    /// assert_eq!(id.timestamp_millis(), 1749124954997);
+    /// ```
+    pub fn timestamp_millis(&self) -> u64 {
+        EPOCH + ticks_to_millis(self.ticks())
     }
 
-    /// Retrieve a chrono [Utc] datetime from [HoraId]
-    #[cfg(feature = "chrono")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
-    pub fn to_utc(&self) -> DateTime<Utc> {
-        let timestamp = self.to_datetime();
-        DateTime::<Utc>::from_utc(timestamp, Utc)
+    /// Timestamp of the [HoraId] as milliseconds since the Unix epoch, for IDs generated with a
+    /// custom epoch
+    ///
+    /// See [HoraGenerator::with_epoch].
+    pub fn timestamp_millis_with_epoch(&self, epoch_millis: u64) -> u64 {
+        epoch_millis + ticks_to_millis(self.ticks())
     }
-}
 
-fn rescale_epoch(value: u64) -> u64 {
-    let high = value / 1000;
-    let low = (value % 1000) as u16;
-    let low = (low as f32) * 0.256;
-    let low = low as u64;
-    high * 1000 + low
-}
+    /// The smallest [HoraId] of the tick containing `millis`, with machine ID and sequence zero
+    ///
+    /// Together with [HoraId::max_for_millis] this turns a time window into an ID range for
+    /// queries such as SQL `BETWEEN`. Both round to the 1/256 second tick, so the range can
+    /// include IDs up to 3 milliseconds outside the window.
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    ///
+    /// let march = 1740787200000; // 2025-03-01
+    /// let april = 1743465600000; // 2025-04-01
+    /// let start = HoraId::min_for_millis(march).unwrap();
+    /// let end = HoraId::max_for_millis(april - 1).unwrap();
+    /// let sql = format!("SELECT * FROM items WHERE id BETWEEN {} AND {}", start.to_u64(), end.to_u64());
+    /// ```
+    ///
+    /// ## Fail condition
+    /// If `millis` is before the HoraID epoch (2025-01-01) or too far in the future
+    pub fn min_for_millis(millis: u64) -> Result<Self, HoraError> {
+        Ok(HoraId::with_params(HoraParams {
+            machine_id: 0,
+            ticks: unix_millis_to_ticks(millis)?,
+            sequence: 0,
+        }))
+    }
 
-/// Convert u16 to u8 with rescaling process
-fn rescale_low(value: u16) -> u8 {
-    let new_val = (value as f32) * (256.0) / (1000.0);
-    new_val as u8
+    /// The largest [HoraId] of the tick containing `millis`, with all machine ID and sequence
+    /// bits set
+    ///
+    /// See [HoraId::min_for_millis].
+    ///
+    /// ## Fail condition
+    /// If `millis` is before the HoraID epoch (2025-01-01) or too far in the future
+    pub fn max_for_millis(millis: u64) -> Result<Self, HoraError> {
+        Ok(HoraId::with_params(HoraParams {
+            machine_id: u8::MAX,
+            ticks: unix_millis_to_ticks(millis)?,
+            sequence: u16::MAX,
+        }))
+    }
+
+    /// Time elapsed since the [HoraId] was created, according to the system clock
+    ///
+    /// Returns [Duration::ZERO] for IDs from the future, for example created on a machine whose
+    /// clock is ahead.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn age(&self) -> Duration {
+        self.age_at(SystemClock.now_millis())
+    }
+
+    /// Whether the [HoraId] was created more than `age` ago, for retention and cleanup decisions
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    /// use std::time::Duration;
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap();
+    /// assert!(id.is_older_than(Duration::from_secs(60)));
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn is_older_than(&self, age: Duration) -> bool {
+        self.age() > age
+    }
+
+    /// Time between the creation of `earlier` and this [HoraId]
+    ///
+    /// The result is exact to the 1/256 second tick stored in the IDs, and zero for IDs of the
+    /// same tick. Use it to measure latency when the ID is the only timestamp passed along.
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    /// use std::time::Duration;
+    ///
+    /// let sent: HoraId = "00cd01daff010002".parse().unwrap();
+    /// let received: HoraId = "00cd01dc80070011".parse().unwrap();
+    /// assert_eq!(received.duration_since(&sent), Ok(Duration::from_nanos(1_503_906_250)));
+    /// ```
+    ///
+    /// ## Fail condition
+    /// Returns [HoraError::EarlierId] with the difference if this ID was created before `earlier`
+    pub fn duration_since(&self, earlier: &HoraId) -> Result<Duration, HoraError> {
+        let (ticks, earlier_ticks) = (self.ticks(), earlier.ticks());
+        if ticks < earlier_ticks {
+            return Err(HoraError::EarlierId {
+                by: ticks_to_duration(earlier_ticks - ticks),
+            });
+        }
+        Ok(ticks_to_duration(ticks - earlier_ticks))
+    }
+
+    /// Time elapsed from the creation of the [HoraId] until `now_millis`
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    fn age_at(&self, now_millis: u64) -> Duration {
+        Duration::from_millis(now_millis.saturating_sub(self.timestamp_millis()))
+    }
+
+    /// Machine ID of the generator that produced this [HoraId]
+    pub const fn machine_id(&self) -> u8 {
+        self.inner[5]
+    }
+
+    /// Tenant tag of a [HoraId] generated with [HoraLayout::TENANT]
+    ///
+    /// This is the 7th byte, which holds the high byte of the sequence in other layouts. Use
+    /// [HoraLayout::tenant] for custom layouts.
+    pub const fn tenant(&self) -> u8 {
+        self.inner[6]
+    }
+
+    /// Sequence number of this [HoraId] within its tick
+    pub const fn sequence(&self) -> u16 {
+        u16::from_be_bytes([self.inner[6], self.inner[7]])
+    }
+
+    /// Stable partition number from `0` to `n - 1` for routing the [HoraId] to a shard
+    ///
+    /// The partition is derived from the machine ID and sequence, not the timestamp, so IDs
+    /// created at the same time spread across all partitions instead of hitting one. The same
+    /// ID always maps to the same partition for a given `n`, across processes and versions.
+    ///
+    /// A generator that creates one ID per tick always uses sequence 0, so its IDs share a
+    /// partition. Spreading those needs several machine IDs.
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap();
+    /// let partition = id.shard(12);
+    /// assert!(partition < 12);
+    /// assert_eq!(partition, id.shard(12));
+    /// ```
+    ///
+    /// ## Panics
+    /// If `n` is zero
+    pub fn shard(&self, n: u32) -> u32 {
+        assert!(n > 0, "number of shards must not be zero");
+        let mut hash = u32::from_be_bytes([0, self.inner[5], self.inner[6], self.inner[7]]);
+        // finalizer of MurmurHash3, so neighbouring sequence numbers land far apart
+        hash ^= hash >> 16;
+        hash = hash.wrapping_mul(0x85eb_ca6b);
+        hash ^= hash >> 13;
+        hash = hash.wrapping_mul(0xc2b2_ae35);
+        hash ^= hash >> 16;
+        ((hash as u64 * n as u64) >> 32) as u32
+    }
+
+    /// Timestamp of the [HoraId] in 1/256 second ticks since [EPOCH]
+    ///
+    /// This is the whole 40 bit field, including the version bits above the 38 bits of version
+    /// 0. It is only the tick count of the ID if [HoraId::version] is 0.
+    fn ticks(&self) -> u64 {
+        ticks_from_bytes(&self.inner)
+    }
+
+    /// Timestamp of the [HoraId] in whole seconds since the Unix epoch
+    #[cfg_attr(not(any(feature = "bson", feature = "ksuid")), allow(dead_code))]
+    fn unix_seconds(&self) -> u64 {
+        EPOCH / 1000 + self.ticks() / TICKS_PER_SECOND
+    }
+
+    /// Retrieve a chrono [NaiveDateTime] from [HoraId]
+    ///
+    /// The returned time is exact to the 1/256 second tick stored in the ID.
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+    pub fn to_datetime(&self) -> NaiveDateTime {
+        self.to_utc().naive_utc()
+    }
+
+    /// Retrieve a chrono [Utc] datetime from [HoraId]
+    ///
+    /// The returned time is exact to the 1/256 second tick stored in the ID.
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+    pub fn to_utc(&self) -> DateTime<Utc> {
+        ticks_to_utc(self.ticks(), EPOCH)
+    }
+
+    /// Retrieve a chrono [Utc] datetime from a [HoraId] generated with a custom epoch
+    ///
+    /// See [HoraGenerator::with_epoch].
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+    pub fn to_utc_with_epoch(&self, epoch_millis: u64) -> DateTime<Utc> {
+        ticks_to_utc(self.ticks(), epoch_millis)
+    }
+}
+
+/// Formats a [HoraId] as a 16 character lowercase hexadecimal string
+impl fmt::Display for HoraId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        encoding::fmt_hex(&self.inner, f)
+    }
+}
+
+/// Formats a [HoraId] as 16 lowercase hexadecimal digits, `{:#x}` adds a `0x` prefix
+impl fmt::LowerHex for HoraId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        encoding::fmt_bits(&self.inner, 4, encoding::HEX_LOWER, "0x", f)
+    }
+}
+
+/// Formats a [HoraId] as 16 uppercase hexadecimal digits, `{:#X}` adds a `0x` prefix
+impl fmt::UpperHex for HoraId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        encoding::fmt_bits(&self.inner, 4, encoding::HEX_UPPER, "0x", f)
+    }
+}
+
+/// Formats a [HoraId] as 64 binary digits, `{:#b}` adds a `0b` prefix
+impl fmt::Binary for HoraId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        encoding::fmt_bits(&self.inner, 1, encoding::BINARY, "0b", f)
+    }
+}
+
+/// Parses a [HoraId] from a 16 character hexadecimal string
+impl FromStr for HoraId {
+    type Err = HoraIdParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = encoding::decode_hex(s)?;
+        Ok(Self { inner })
+    }
+}
+
+impl From<[u8; 8]> for HoraId {
+    fn from(bytes: [u8; 8]) -> Self {
+        Self { inner: bytes }
+    }
+}
+
+impl From<HoraId> for [u8; 8] {
+    fn from(id: HoraId) -> Self {
+        id.inner
+    }
 }
 
-/// Convert a u8 to u16 with rescaling process
-#[allow(dead_code)]
-fn upscale_low(value: u8) -> u16 {
-    let new_val = (value as f32) * (1000.0) / 256.0;
-    new_val as u16
+impl From<u64> for HoraId {
+    fn from(num: u64) -> Self {
+        Self {
+            inner: num.to_be_bytes(),
+        }
+    }
+}
+
+impl From<HoraId> for u64 {
+    fn from(id: HoraId) -> Self {
+        id.to_u64()
+    }
+}
+
+/// Create a [HoraId] from a byte slice that must be exactly 8 bytes long
+impl TryFrom<&[u8]> for HoraId {
+    type Error = HoraIdParseError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let inner: [u8; 8] = bytes
+            .try_into()
+            .map_err(|_| HoraIdParseError::InvalidLength {
+                expected: 8,
+                got: bytes.len(),
+            })?;
+        Ok(Self { inner })
+    }
+}
+
+impl AsRef<[u8]> for HoraId {
+    fn as_ref(&self) -> &[u8] {
+        &self.inner
+    }
 }
 
 #[cfg(test)]
@@ -310,6 +1612,64 @@ mod tests {
         assert_eq!(source_id.to_string(), derived_id.to_string());
     }
 
+    #[test]
+    fn display() {
+        let id = HoraId::from_u64(57704410318438402).unwrap();
+        assert_eq!(id.to_string(), "00cd01daff010002");
+        assert_eq!(format!("id={}", id), "id=00cd01daff010002");
+    }
+
+    #[test]
+    fn radix_formatting() {
+        let id = HoraId::from_u64(57704410318438402).unwrap();
+        assert_eq!(format!("{:x}", id), "00cd01daff010002");
+        assert_eq!(format!("{:X}", id), "00CD01DAFF010002");
+        assert_eq!(format!("{:#x}", id), "0x00cd01daff010002");
+        assert_eq!(format!("{:>20X}", id), "    00CD01DAFF010002");
+        assert_eq!(format!("{:b}", id), format!("{:064b}", id.to_u64()));
+        assert_eq!(
+            format!("{:#b}", HoraId::NIL),
+            format!("0b{}", "0".repeat(64))
+        );
+    }
+
+    #[test]
+    fn parse() {
+        let id: HoraId = "00cd01daff010002".parse().unwrap();
+        assert_eq!(id.to_u64(), 57704410318438402);
+        let id: HoraId = "00CD01DAFF010002".parse().unwrap();
+        assert_eq!(id.to_u64(), 57704410318438402);
+
+        assert_eq!(
+            "".parse::<HoraId>(),
+            Err(HoraIdParseError::InvalidLength {
+                expected: 16,
+                got: 0
+            })
+        );
+        assert_eq!(
+            "00cd01da".parse::<HoraId>(),
+            Err(HoraIdParseError::InvalidLength {
+                expected: 16,
+                got: 8
+            })
+        );
+        assert_eq!(
+            "00cd01daff01000g".parse::<HoraId>(),
+            Err(HoraIdParseError::InvalidCharacter {
+                index: 15,
+                char: 'g'
+            })
+        );
+        assert_eq!(
+            "+0cd01daff010002".parse::<HoraId>(),
+            Err(HoraIdParseError::InvalidCharacter {
+                index: 0,
+                char: '+'
+            })
+        );
+    }
+
     #[test]
     fn u64s() {
         let num = 57630818184577258;
@@ -319,6 +1679,27 @@ mod tests {
         assert_eq!(id.to_u64(), num);
     }
 
+    #[test]
+    fn conversions() {
+        let num = 57704410318438402;
+        let bytes = [0x00, 0xcd, 0x01, 0xda, 0xff, 0x01, 0x00, 0x02];
+
+        let id = HoraId::from(num);
+        assert_eq!(HoraId::from(bytes), id);
+        assert_eq!(u64::from(id), num);
+        assert_eq!(<[u8; 8]>::from(id), bytes);
+        assert_eq!(id.as_ref(), &bytes);
+
+        assert_eq!(HoraId::try_from(&bytes[..]), Ok(id));
+        assert_eq!(
+            HoraId::try_from(&bytes[..4]),
+            Err(HoraIdParseError::InvalidLength {
+                expected: 8,
+                got: 4
+            })
+        );
+    }
+
     #[test]
     fn eq() {
         let num = 57630818184577258;
@@ -328,6 +1709,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn clone() {
         let num = 57630818184577258;
         let id = HoraId::from_u64(num).unwrap();
@@ -335,6 +1717,32 @@ mod tests {
         assert_eq!(id, id2);
     }
 
+    #[test]
+    fn ordering() {
+        let older = HoraId::from_u64(57704410318438402).unwrap();
+        let newer = HoraId::from_u64(57704410335215618).unwrap();
+        assert!(older < newer);
+        assert_eq!(older.cmp(&newer), older.to_u64().cmp(&newer.to_u64()));
+
+        let mut ids = vec![newer, older];
+        ids.sort();
+        assert_eq!(ids, vec![older, newer]);
+    }
+
+    #[test]
+    fn collections() {
+        use std::collections::{BTreeMap, HashMap};
+
+        let id = HoraId::from_u64(57704410318438402).unwrap();
+        let mut hash_map = HashMap::new();
+        hash_map.insert(id, "a");
+        assert_eq!(hash_map.get(&id), Some(&"a"));
+
+        let mut tree_map = BTreeMap::new();
+        tree_map.insert(id, "a");
+        assert_eq!(tree_map.first_key_value(), Some((&id, &"a")));
+    }
+
     #[cfg(feature = "chrono")]
     #[test]
     fn chrono() {
@@ -348,36 +1756,187 @@ mod tests {
     }
 
     #[test]
-    fn rescaling() {
-        assert_eq!(rescale_low(0), 0);
-        assert_eq!(rescale_low(1), 0);
-        assert_eq!(rescale_low(5), 1);
-        assert_eq!(rescale_low(498), 127);
-        assert_eq!(rescale_low(500), 128);
-        assert_eq!(rescale_low(995), 254);
-        assert_eq!(rescale_low(997), 255);
-        assert_eq!(rescale_low(999), 255);
+    fn ticks() {
+        assert_eq!(millis_to_ticks(0), 0);
+        assert_eq!(millis_to_ticks(3), 0);
+        assert_eq!(millis_to_ticks(4), 1);
+        assert_eq!(millis_to_ticks(500), 128);
+        assert_eq!(millis_to_ticks(999), 255);
+        assert_eq!(millis_to_ticks(1000), 256);
+        assert_eq!(millis_to_ticks(1_001_500), 256_384);
+
+        assert_eq!(ticks_to_millis(0), 0);
+        assert_eq!(ticks_to_millis(1), 4);
+        assert_eq!(ticks_to_millis(128), 500);
+        assert_eq!(ticks_to_millis(255), 997);
+        assert_eq!(ticks_to_millis(256_384), 1_001_500);
     }
 
     #[test]
-    fn rescale() {
-        let value = upscale_low(rescale_low(500));
-        assert_eq!(value, 500);
+    fn ticks_round_trip() {
+        for ticks in 0..(TICKS_PER_SECOND * 3) {
+            assert_eq!(millis_to_ticks(ticks_to_millis(ticks)), ticks);
+        }
     }
 
     #[test]
-    fn epoch_rescaling() {
-        // test 1
-        let value = 1672531200000;
-        assert_eq!(rescale_epoch(value), value);
-        // test 2
-        assert_eq!(rescale_epoch(1672531200003), 1672531200000);
-        // test 3
-        assert_eq!(rescale_epoch(1672531200005), 1672531200001);
-        assert_eq!(rescale_epoch(1672531200006), 1672531200001);
-        // test 4
-        assert_eq!(rescale_epoch(1672531200998), 1672531200255);
-        assert_eq!(rescale_epoch(1672531200999), 1672531200255);
+    fn accessors() {
+        let params = HoraParams {
+            machine_id: 7,
+            ticks: 256 * 60 + 128,
+            sequence: 0x0102,
+        };
+        let id = HoraId::with_params(params);
+        assert_eq!(id.timestamp_millis(), EPOCH + 60_500);
+        assert_eq!(id.machine_id(), 7);
+        assert_eq!(id.sequence(), 0x0102);
+
+        let id = HoraId::new(Some(3)).unwrap();
+        let now = SystemClock.now_millis();
+        assert!(now - id.timestamp_millis() < 1000);
+        assert_eq!(id.machine_id(), 3);
+        assert_eq!(id.sequence(), 0);
+    }
+
+    #[test]
+    fn ticks_are_monotonic() {
+        let mut last = 0;
+        for millis in 0..10_000 {
+            let ticks = millis_to_ticks(millis);
+            assert!(ticks >= last);
+            last = ticks;
+        }
+    }
+
+    #[test]
+    fn ticks_layout() {
+        let params = HoraParams {
+            machine_id: 1,
+            ticks: 0x0012_3456_789a,
+            sequence: 2,
+        };
+        let id = HoraId::with_params(params);
+        assert_eq!(id.to_u64(), 0x1234_5678_9a01_0002);
+        assert_eq!(id.ticks(), 0x0012_3456_789a);
+        // the version bits are part of the field
+        assert_eq!(HoraId::MAX.ticks(), (1 << 40) - 1);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_round_trip() {
+        for ticks in [0, 1, 127, 255, 256, 0x0012_3456_789a] {
+            let params = HoraParams {
+                machine_id: 0,
+                ticks,
+                sequence: 0,
+            };
+            let time = HoraId::with_params(params).to_utc();
+            let since_epoch = (time.timestamp() as u64 - EPOCH / 1000) * TICKS_PER_SECOND
+                + time.timestamp_subsec_nanos() as u64 / NANOS_PER_TICK;
+            assert_eq!(since_epoch, ticks);
+            assert_eq!(time.timestamp_subsec_nanos() as u64 % NANOS_PER_TICK, 0);
+        }
+    }
+
+    #[test]
+    fn nil_and_max() {
+        assert!(HoraId::NIL.is_nil());
+        assert_eq!(HoraId::NIL.to_u64(), 0);
+        assert_eq!(HoraId::MAX.to_u64(), u64::MAX);
+        assert_eq!(HoraId::MAX.to_string(), "ffffffffffffffff");
+        assert!(!HoraId::MAX.is_nil());
+
+        let id = HoraId::new(None).unwrap();
+        assert!(!id.is_nil());
+        assert!(HoraId::NIL < id && id < HoraId::MAX);
+    }
+
+    #[test]
+    fn const_constructors() {
+        const ID: HoraId = HoraId::from_bytes([0x00, 0xcd, 0x01, 0xda, 0xff, 0x01, 0x00, 0x02]);
+        const NUM: u64 = ID.to_u64();
+        const MACHINE: u8 = ID.machine_id();
+        const SEQUENCE: u16 = ID.sequence();
+        const UNSET: bool = HoraId::NIL.is_nil();
+        static FROM_U64: Option<HoraId> = HoraId::from_u64(57704410318438402);
+
+        assert_eq!(ID, "00cd01daff010002".parse().unwrap());
+        assert_eq!(
+            (NUM, MACHINE, SEQUENCE, UNSET),
+            (57704410318438402, 1, 2, true)
+        );
+        assert_eq!(FROM_U64, Some(ID));
+    }
+
+    #[test]
+    fn age() {
+        let id: HoraId = "00cd01daff010002".parse().unwrap();
+        let created = id.timestamp_millis();
+        assert_eq!(id.age_at(created + 1500), Duration::from_millis(1500));
+        assert_eq!(id.age_at(created - 10), Duration::ZERO);
+        assert!(id.age() > Duration::from_secs(3600));
+        assert!(id.is_older_than(Duration::from_secs(3600)));
+
+        let new = HoraId::new(None).unwrap();
+        assert!(new.age() < Duration::from_secs(1));
+        assert!(!new.is_older_than(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn duration_since() {
+        let sent: HoraId = "00cd01daff010002".parse().unwrap();
+        let received: HoraId = "00cd01dc80070011".parse().unwrap();
+        let elapsed = Duration::from_nanos(1_503_906_250);
+        assert_eq!(received.duration_since(&sent), Ok(elapsed));
+        assert_eq!(
+            sent.duration_since(&received),
+            Err(HoraError::EarlierId { by: elapsed })
+        );
+        // machine ID and sequence don't matter
+        let same_tick = HoraId::max_for_millis(sent.timestamp_millis()).unwrap();
+        assert_eq!(sent.duration_since(&same_tick), Ok(Duration::ZERO));
+    }
+
+    #[test]
+    fn shard() {
+        let id: HoraId = "00cd01daff010002".parse().unwrap();
+        assert_eq!(id.shard(1), 0);
+        assert_eq!(id.shard(16), 6);
+        assert_eq!(id.shard(1000), 390);
+        // the timestamp doesn't affect the partition
+        assert_eq!(HoraId::from(id.to_u64() & 0xff_ffff).shard(1000), 390);
+
+        let mut counts = [0; 8];
+        for sequence in 0..8000 {
+            counts[HoraId::from(1 << 24 | sequence).shard(8) as usize] += 1;
+        }
+        assert!(counts.iter().all(|&count| (900..1100).contains(&count)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn shard_zero() {
+        HoraId::from(1).shard(0);
+    }
+
+    #[test]
+    fn bounds_for_millis() {
+        let id: HoraId = "00cd01daff010002".parse().unwrap();
+        let min = HoraId::min_for_millis(id.timestamp_millis()).unwrap();
+        let max = HoraId::max_for_millis(id.timestamp_millis()).unwrap();
+        assert_eq!(min.to_string(), "00cd01daff000000");
+        assert_eq!(max.to_string(), "00cd01daffffffff");
+        assert!(min <= id && id <= max);
+        // every millisecond of the tick maps to the same bounds
+        assert_eq!(HoraId::min_for_millis(id.timestamp_millis() + 2), Ok(min));
+        assert!(HoraId::min_for_millis(id.timestamp_millis() + 3).unwrap() > max);
+
+        assert_eq!(HoraId::min_for_millis(EPOCH).unwrap().to_u64(), 0);
+        assert_eq!(
+            HoraId::max_for_millis(EPOCH - 1),
+            Err(HoraError::TimestampOutOfRange)
+        );
     }
 }
 
@@ -385,7 +1944,6 @@ mod tests {
 mod gen_tests {
     use super::*;
 
-    #[cfg(feature = "chrono")]
     #[test]
     fn it_works() {
         let generator = HoraGenerator::new(1);
@@ -393,4 +1951,435 @@ mod gen_tests {
         let mut generator = generator.unwrap();
         generator.next();
     }
+
+    #[test]
+    fn try_next() {
+        let mut generator = HoraGenerator::new(1).unwrap();
+        let id1 = generator.try_next().unwrap();
+        let id2 = generator.try_next().unwrap();
+        assert!(id1 < id2);
+    }
+
+    #[test]
+    fn rollback_error() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let mut generator = HoraGenerator::new_with_clock(1, clock.clone())
+            .unwrap()
+            .with_rollback_policy(RollbackPolicy::Error);
+        generator.next();
+        clock.rewind(1500);
+        assert_eq!(
+            generator.try_next(),
+            Err(HoraError::ClockMovedBackwards {
+                behind: Duration::from_millis(1500)
+            })
+        );
+        clock.advance(1500);
+        assert!(generator.try_next().is_ok());
+    }
+
+    #[test]
+    fn rollback_borrow_sequence() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let mut generator = HoraGenerator::new_with_clock(1, clock.clone())
+            .unwrap()
+            .with_rollback_policy(RollbackPolicy::BorrowSequence);
+        let first = generator.next();
+        clock.rewind(1500);
+        let second = generator.next();
+        assert_eq!(second.ticks(), first.ticks());
+        assert_eq!(second.sequence(), first.sequence() + 1);
+    }
+
+    #[test]
+    fn monotonic_mode() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let layout = HoraLayout::new(52, 8, 4);
+        let mut generator = HoraGenerator::with_layout_and_clock(layout, 1, clock.clone())
+            .unwrap()
+            .with_rollback_policy(RollbackPolicy::Error)
+            .with_wait_strategy(Fail)
+            .monotonic();
+        let mut last = generator.next();
+        // 16 sequence numbers per tick, the clock stands still
+        for _ in 0..100 {
+            let id = generator.try_next().unwrap();
+            assert!(id > last);
+            last = id;
+        }
+        assert_eq!(layout.ticks(&last), millis_to_ticks(10_000) + 6);
+
+        clock.rewind(5_000);
+        let id = generator.try_next().unwrap();
+        assert!(id > last);
+        assert_eq!(layout.ticks(&id), layout.ticks(&last));
+
+        clock.advance(6_000);
+        let id = generator.try_next().unwrap();
+        assert_eq!(layout.ticks(&id), millis_to_ticks(11_000));
+        assert_eq!(layout.sequence(&id), 0);
+    }
+
+    #[test]
+    fn random_sequence() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let layout = HoraLayout::new(52, 8, 4);
+        let mut generator = HoraGenerator::with_layout_and_clock(layout, 1, clock.clone())
+            .unwrap()
+            .with_wait_strategy(Fail)
+            .random_sequence();
+        clock.advance(10);
+        // 16 sequence numbers per tick, the clock stands still
+        let mut ids: Vec<HoraId> = (0..16).map(|_| generator.try_next().unwrap()).collect();
+        assert!(ids.windows(2).any(|pair| pair[0] > pair[1]));
+        assert_eq!(generator.try_next(), Err(HoraError::SequenceExhausted));
+        ids.sort();
+        let sequences: Vec<u32> = ids.iter().map(|id| layout.sequence(id)).collect();
+        assert_eq!(sequences, (0..16).collect::<Vec<_>>());
+
+        clock.advance(10);
+        let id = generator.try_next().unwrap();
+        assert!(id > ids[15]);
+    }
+
+    #[test]
+    fn monotonic_stops_at_last_tick() {
+        // the last millisecond before the timestamp reaches the version bits, in 2059
+        let end = EPOCH + (1u64 << 38) / 256 * 1000 - 1;
+        let clock = MockClock::new(end);
+        let layout = HoraLayout::new(40, 16, 8);
+        let mut generator = HoraGenerator::with_layout_and_clock(layout, 1, clock)
+            .unwrap()
+            .monotonic();
+        let ids: Vec<HoraId> = (0..255).map(|_| generator.next()).collect();
+        assert!(ids.iter().all(|id| id.version() == 0));
+        assert_eq!(layout.ticks(&ids[254]), layout.max_ticks());
+        assert_eq!(generator.try_next(), Err(HoraError::TimestampOutOfRange));
+    }
+
+    #[test]
+    fn monotonic_and_random_sequence_exclude_each_other() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let mut generator = HoraGenerator::new_with_clock(1, clock.clone())
+            .unwrap()
+            .random_sequence()
+            .monotonic();
+        assert!(generator.random_sequence.is_none());
+        let mut last = generator.next();
+        for _ in 0..1000 {
+            let id = generator.next();
+            assert!(id > last);
+            last = id;
+        }
+
+        let generator = HoraGenerator::new_with_clock(1, clock)
+            .unwrap()
+            .monotonic()
+            .random_sequence();
+        assert!(!generator.monotonic);
+        assert!(generator.random_sequence.is_some());
+    }
+
+    #[test]
+    fn rollback_wait() {
+        let mut generator = HoraGenerator::new(1)
+            .unwrap()
+            .with_rollback_policy(RollbackPolicy::WaitUntilCaughtUp);
+        let future = current_ticks().unwrap() + 3;
+        generator.last_gen = future;
+        let id = generator.next();
+        assert!(id.ticks() >= future);
+    }
+
+    #[test]
+    fn custom_epoch() {
+        let now = SystemClock.now_millis();
+        let epoch = now - 10_000;
+        let mut generator = HoraGenerator::new(1).unwrap().with_epoch(epoch).unwrap();
+        let id = generator.next();
+        // about 10 seconds of ticks since the custom epoch
+        assert!(id.ticks() >= 10 * TICKS_PER_SECOND);
+        assert!(id.ticks() < 20 * TICKS_PER_SECOND);
+        assert!(id.timestamp_millis_with_epoch(epoch) - now < 1000);
+        assert!(id.timestamp_millis() < now);
+
+        let future = HoraGenerator::new(1).unwrap().with_epoch(now + 60_000);
+        assert!(matches!(future, Err(HoraError::ClockBeforeEpoch)));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn custom_epoch_chrono() {
+        let epoch = 1767225600123;
+        let params = HoraParams {
+            machine_id: 0,
+            ticks: 256 + 128,
+            sequence: 0,
+        };
+        let id = HoraId::with_params(params);
+        let time = id.to_utc_with_epoch(epoch);
+        assert_eq!(time.timestamp_millis() as u64, epoch + 1500);
+    }
+
+    #[test]
+    fn custom_clock() {
+        let now = EPOCH + 60_500;
+        let mut generator = HoraGenerator::new_with_clock(2, || now).unwrap();
+        let first = generator.next();
+        let second = generator.next();
+        assert_eq!(first.timestamp_millis(), now);
+        assert_eq!(first.machine_id(), 2);
+        assert_eq!(second.ticks(), first.ticks());
+        assert_eq!(second.sequence(), first.sequence() + 1);
+
+        let layout = HoraLayout::WIDE_MACHINE;
+        let mut generator = HoraGenerator::with_layout_and_clock(layout, 4000, || now).unwrap();
+        assert_eq!(layout.machine_id(&generator.next()), 4000);
+    }
+
+    #[test]
+    fn custom_clock_before_epoch() {
+        let generator = HoraGenerator::new_with_clock(1, || EPOCH - 1);
+        assert!(matches!(generator, Err(HoraError::ClockBeforeEpoch)));
+    }
+
+    #[test]
+    fn same_tick() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let mut generator = HoraGenerator::new_with_clock(1, clock.clone()).unwrap();
+        for sequence in 1..=100 {
+            assert_eq!(generator.next().sequence(), sequence);
+        }
+        // still within the same 1/256 second tick
+        clock.advance(3);
+        assert_eq!(generator.next().sequence(), 101);
+        clock.advance(1);
+        assert_eq!(generator.next().sequence(), 0);
+    }
+
+    #[test]
+    fn sequence_overflow_waits_for_next_tick() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let layout = HoraLayout::WIDE_MACHINE;
+        let mut generator = HoraGenerator::with_layout_and_clock(layout, 1, clock.clone()).unwrap();
+        let mut last = generator.next();
+        while layout.sequence(&last) < layout.max_sequence() {
+            last = generator.next();
+        }
+
+        // the generator spins until the clock moves on to the next tick
+        let handle = std::thread::spawn(move || generator.next());
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(!handle.is_finished());
+        clock.advance(4);
+        let id = handle.join().unwrap();
+        assert_eq!(id.ticks(), last.ticks() + 1);
+        assert_eq!(layout.sequence(&id), 0);
+    }
+
+    #[test]
+    fn next_n() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let layout = HoraLayout::WIDE_MACHINE;
+        let mut generator = HoraGenerator::with_layout_and_clock(layout, 1, clock.clone()).unwrap();
+        let first = generator.next();
+
+        let ids = generator.next_n(100);
+        assert_eq!(ids.len(), 100);
+        assert!(ids.iter().all(|id| id.ticks() == first.ticks()));
+        assert_eq!(layout.sequence(&ids[99]), layout.sequence(&first) + 100);
+
+        clock.advance(4);
+        let mut ids = Vec::new();
+        generator.next_n_into(3, &mut ids);
+        generator.next_n_into(2, &mut ids);
+        let sequences: Vec<_> = ids.iter().map(|id| layout.sequence(id)).collect();
+        assert_eq!(sequences, [0, 1, 2, 3, 4]);
+        assert_eq!(generator.next_n(0), []);
+    }
+
+    #[test]
+    fn next_n_spans_ticks() {
+        // more IDs than fit in a single tick
+        let mut generator = HoraGenerator::new(1).unwrap();
+        let ids = generator.next_n(200_000);
+        assert_eq!(ids.len(), 200_000);
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(generator.next() > ids[ids.len() - 1]);
+    }
+
+    #[test]
+    fn try_next_n_into_error() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let mut generator = HoraGenerator::new_with_clock(1, clock.clone())
+            .unwrap()
+            .with_rollback_policy(RollbackPolicy::Error);
+        generator.next();
+        clock.rewind(1000);
+        let mut ids = Vec::new();
+        assert!(generator.try_next_n_into(5, &mut ids).is_err());
+        assert!(ids.is_empty());
+    }
+
+    #[test]
+    fn wait_strategy_fail() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let layout = HoraLayout::new(47, 16, 1);
+        let mut generator = HoraGenerator::with_layout_and_clock(layout, 1, clock.clone())
+            .unwrap()
+            .with_wait_strategy(Fail);
+        assert_eq!(layout.sequence(&generator.next()), 1);
+        assert_eq!(generator.try_next(), Err(HoraError::SequenceExhausted));
+        clock.advance(4);
+        assert_eq!(layout.sequence(&generator.next()), 0);
+
+        let mut generator = generator.with_rollback_policy(RollbackPolicy::WaitUntilCaughtUp);
+        clock.rewind(1000);
+        assert!(matches!(
+            generator.try_next(),
+            Err(HoraError::ClockMovedBackwards { .. })
+        ));
+    }
+
+    #[test]
+    fn wait_strategy_sleep() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let layout = HoraLayout::new(47, 16, 1);
+        let mut generator = HoraGenerator::with_layout_and_clock(layout, 1, clock.clone())
+            .unwrap()
+            .with_wait_strategy(Sleep(Duration::from_millis(1)));
+        let last = generator.next();
+
+        let handle = std::thread::spawn(move || generator.next());
+        std::thread::sleep(Duration::from_millis(50));
+        clock.advance(4);
+        let id = handle.join().unwrap();
+        assert_eq!(layout.ticks(&id), layout.ticks(&last) + 1);
+    }
+
+    #[test]
+    fn iterator() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let mut generator = HoraGenerator::new_with_clock(1, clock.clone()).unwrap();
+        let ids: Vec<HoraId> = generator.by_ref().take(5).collect();
+        assert_eq!(ids.len(), 5);
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(generator.next() > ids[4]);
+
+        let mut iter = generator.map(|id| id.sequence()).skip(2);
+        assert_eq!(iter.next(), Some(ids[4].sequence() + 4));
+    }
+
+    #[test]
+    fn from_env() {
+        // the only test reading these variables, so setting them doesn't race other tests
+        std::env::remove_var("HORA_MACHINE_ID");
+        std::env::remove_var("HORA_EPOCH");
+        assert!(matches!(
+            HoraGenerator::from_env(),
+            Err(HoraError::MissingEnvVar { .. })
+        ));
+
+        std::env::set_var("HORA_MACHINE_ID", "9");
+        let mut generator = HoraGenerator::from_env().unwrap();
+        assert_eq!(generator.next().machine_id(), 9);
+
+        let epoch = EPOCH + 86_400_000;
+        std::env::set_var("HORA_EPOCH", epoch.to_string());
+        let mut generator = HoraGenerator::from_env().unwrap();
+        let id = generator.next();
+        assert!(id.timestamp_millis_with_epoch(epoch) > id.timestamp_millis());
+
+        std::env::set_var("HORA_EPOCH", "yesterday");
+        assert!(matches!(
+            HoraGenerator::from_env(),
+            Err(HoraError::InvalidEnvVar {
+                expected: "milliseconds since the Unix epoch",
+                ..
+            })
+        ));
+        std::env::remove_var("HORA_MACHINE_ID");
+        std::env::remove_var("HORA_EPOCH");
+    }
+
+    #[test]
+    fn sequence_exhaustion() {
+        // far more than the 65,536 IDs available in a single tick
+        let mut generator = HoraGenerator::new(1).unwrap();
+        let mut last = generator.next();
+        for _ in 0..3_000_000 {
+            let id = generator.next();
+            assert!(id > last, "duplicate or unordered id {} after {}", id, last);
+            last = id;
+        }
+    }
+
+    #[test]
+    fn monotonic() {
+        let mut generator = HoraGenerator::new(1).unwrap();
+        let mut last = generator.next();
+        for _ in 0..10_000 {
+            let id = generator.next();
+            assert!(id > last);
+            last = id;
+        }
+    }
+}
+
+#[cfg(test)]
+mod prop_tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn round_trips(num: u64) {
+            let id = HoraId::from(num);
+            prop_assert_eq!(id.to_u64(), num);
+            prop_assert_eq!(id.to_string().parse::<HoraId>(), Ok(id));
+            prop_assert_eq!(HoraId::try_from(id.as_bytes()), Ok(id));
+            prop_assert_eq!(HoraId::from(<[u8; 8]>::from(id)), id);
+            prop_assert_eq!(HoraId::from_base32(&id.to_base32()), Ok(id));
+            prop_assert_eq!(HoraId::from_base58(&id.to_base58()), Ok(id));
+            prop_assert_eq!(HoraId::from_base62(&id.to_base62()), Ok(id));
+            prop_assert_eq!(HoraId::from_base64url(&id.to_base64url()), Ok(id));
+        }
+
+        #[test]
+        fn ordering_is_consistent(a: u64, b: u64) {
+            let (x, y) = (HoraId::from(a), HoraId::from(b));
+            let order = a.cmp(&b);
+            prop_assert_eq!(x.cmp(&y), order);
+            prop_assert_eq!(x.as_bytes().cmp(y.as_bytes()), order);
+            prop_assert_eq!(x.to_string().cmp(&y.to_string()), order);
+            prop_assert_eq!(x.to_base32().cmp(&y.to_base32()), order);
+            prop_assert_eq!(x.to_base58().cmp(&y.to_base58()), order);
+            prop_assert_eq!(x.to_base62().cmp(&y.to_base62()), order);
+            if x < y {
+                prop_assert!(x.timestamp_millis() <= y.timestamp_millis());
+            }
+        }
+
+        #[test]
+        fn hex_decoding_never_panics(s in "\\PC{16}") {
+            if let Ok(id) = s.parse::<HoraId>() {
+                prop_assert_eq!(id.to_string(), s.to_ascii_lowercase());
+            }
+            let _ = HoraId::parse_lenient(&s);
+        }
+
+        #[test]
+        fn hex_digits_always_decode(s in "[0-9a-fA-F]{16}") {
+            let id = s.parse::<HoraId>().unwrap();
+            prop_assert_eq!(id.to_string(), s.to_ascii_lowercase());
+        }
+
+        #[test]
+        fn encoded_decoding_never_panics(s in "\\PC{0,16}") {
+            let _ = HoraId::from_base32(&s);
+            let _ = HoraId::from_base58(&s);
+            let _ = HoraId::from_base62(&s);
+            let _ = HoraId::from_base64url(&s);
+        }
+    }
 }
@@ -0,0 +1,95 @@
+//! Machine IDs derived from a MAC address, using [mac_address]
+
+use crate::machine_id::hash_machine_id;
+use crate::{HoraError, MachineIdProvider};
+
+/// Derives the machine ID from a hash of the MAC address of the primary network interface
+///
+/// This needs no configuration, but machines can collide on the derived ID, see
+/// [derived machine IDs](MachineIdProvider#derived-machine-ids). Use
+/// [MacAddressMachineId::with_override] to move a machine that collides.
+///
+/// The MAC address of containers and virtual machines may change when they are recreated, which
+/// changes the derived ID.
+///
+/// ## Usage
+/// ```no_run
+/// use hora_id::{HoraGenerator, MacAddressMachineId};
+///
+/// let override_id = std::env::var("HORA_MACHINE_ID").ok().and_then(|id| id.parse().ok());
+/// let provider = MacAddressMachineId::new().with_override(override_id);
+/// let mut generator = HoraGenerator::from_provider(&provider).unwrap();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MacAddressMachineId {
+    mac: Option<[u8; 6]>,
+    override_id: Option<u8>,
+}
+
+impl MacAddressMachineId {
+    /// Derive the machine ID from the primary network interface, looked up on every acquire
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Derive the machine ID from a known MAC address, for example of a specific interface
+    pub fn from_mac(mac: [u8; 6]) -> Self {
+        Self {
+            mac: Some(mac),
+            override_id: None,
+        }
+    }
+
+    /// Use `machine_id` instead of the derived ID if it is `Some`
+    pub fn with_override(mut self, machine_id: Option<u8>) -> Self {
+        self.override_id = machine_id;
+        self
+    }
+}
+
+impl MachineIdProvider for MacAddressMachineId {
+    fn acquire(&self) -> Result<u8, HoraError> {
+        if let Some(machine_id) = self.override_id {
+            return Ok(machine_id);
+        }
+        let mac = match self.mac {
+            Some(mac) => mac,
+            None => mac_address::get_mac_address()
+                .map_err(|err| HoraError::MachineIdUnavailable(err.to_string()))?
+                .ok_or_else(|| {
+                    HoraError::MachineIdUnavailable("no network interface has a MAC address".into())
+                })?
+                .bytes(),
+        };
+        Ok(hash_machine_id(&mac))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derived() {
+        let provider = MacAddressMachineId::from_mac([0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e]);
+        assert_eq!(provider.acquire(), Ok(196));
+        assert_eq!(provider.with_override(Some(3)).acquire(), Ok(3));
+    }
+
+    #[test]
+    fn primary_interface() {
+        let derived = MacAddressMachineId::new().acquire();
+        match mac_address::get_mac_address() {
+            Ok(Some(mac)) => assert_eq!(
+                derived,
+                MacAddressMachineId::from_mac(mac.bytes()).acquire()
+            ),
+            // the sandbox may not have a network interface, but the error must be descriptive
+            _ => assert!(matches!(derived, Err(HoraError::MachineIdUnavailable(_)))),
+        }
+        assert_eq!(
+            MacAddressMachineId::new().with_override(Some(8)).acquire(),
+            Ok(8)
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! Sources of the machine ID used by [HoraGenerator](crate::HoraGenerator)
+
+use crate::HoraError;
+
+/// Acquires the machine ID of this instance
+///
+/// Implement this to plug a configuration source or coordination service into
+/// [HoraGenerator::from_provider](crate::HoraGenerator::from_provider), so the provider can be
+/// swapped without changing how generators are built.
+///
+/// ## Derived machine IDs
+/// The `hostname` and `mac-address` features derive the machine ID from a hash of the host,
+/// which needs no configuration but can't rule out collisions among 256 IDs: two hosts share an
+/// ID with a chance of about 16% in a fleet of 10 and 53% in a fleet of 20. Check the derived
+/// IDs of the fleet and override the ID of a host that collides. Larger fleets need an allocator
+/// that hands each ID to one instance at a time, such as `RedisMachineIdAllocator` (feature
+/// `redis`) or `EtcdMachineIdAllocator` (feature `etcd`).
+///
+/// ## Usage
+/// ```
+/// use hora_id::{HoraError, MachineIdProvider};
+///
+/// struct FromConfig {
+///     machine_id: u8,
+/// }
+///
+/// impl MachineIdProvider for FromConfig {
+///     fn acquire(&self) -> Result<u8, HoraError> {
+///         Ok(self.machine_id)
+///     }
+/// }
+///
+/// let provider = FromConfig { machine_id: 7 };
+/// assert_eq!(provider.acquire(), Ok(7));
+/// ```
+pub trait MachineIdProvider {
+    /// Return the machine ID to use, claiming it first if the provider coordinates IDs
+    fn acquire(&self) -> Result<u8, HoraError>;
+
+    /// Give the machine ID back so that another instance can use it
+    ///
+    /// Providers that don't claim IDs do nothing, which is the default.
+    fn release(&self) -> Result<(), HoraError> {
+        Ok(())
+    }
+}
+
+/// A fixed machine ID, for example from a configuration file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaticMachineId(pub u8);
+
+impl MachineIdProvider for StaticMachineId {
+    fn acquire(&self) -> Result<u8, HoraError> {
+        Ok(self.0)
+    }
+}
+
+impl<P: MachineIdProvider + ?Sized> MachineIdProvider for &P {
+    fn acquire(&self) -> Result<u8, HoraError> {
+        (**self).acquire()
+    }
+
+    fn release(&self) -> Result<(), HoraError> {
+        (**self).release()
+    }
+}
+
+/// Reads the machine ID from an environment variable, `HORA_MACHINE_ID` by default
+///
+/// ## Usage
+/// ```no_run
+/// use hora_id::{EnvMachineId, HoraGenerator};
+///
+/// let mut generator = HoraGenerator::from_provider(&EnvMachineId::new("POD_ORDINAL")).unwrap();
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvMachineId {
+    name: std::string::String,
+}
+
+#[cfg(feature = "std")]
+impl EnvMachineId {
+    /// Name of the variable read by [EnvMachineId::default]
+    pub const DEFAULT_VAR: &'static str = "HORA_MACHINE_ID";
+
+    /// Read the machine ID from the variable `name`
+    pub fn new(name: impl Into<std::string::String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for EnvMachineId {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_VAR)
+    }
+}
+
+#[cfg(feature = "std")]
+impl MachineIdProvider for EnvMachineId {
+    fn acquire(&self) -> Result<u8, HoraError> {
+        let value = read_env(&self.name)?.ok_or_else(|| HoraError::MissingEnvVar {
+            name: self.name.clone(),
+        })?;
+        value.trim().parse().map_err(|_| HoraError::InvalidEnvVar {
+            name: self.name.clone(),
+            value,
+            expected: "a machine ID from 0 to 255",
+        })
+    }
+}
+
+/// Read an environment variable, treating a value that isn't unicode as invalid
+#[cfg(feature = "std")]
+pub(crate) fn read_env(name: &str) -> Result<Option<std::string::String>, HoraError> {
+    match std::env::var(name) {
+        Ok(value) => Ok(Some(value)),
+        Err(std::env::VarError::NotPresent) => Ok(None),
+        Err(std::env::VarError::NotUnicode(value)) => Err(HoraError::InvalidEnvVar {
+            name: name.to_owned(),
+            value: value.to_string_lossy().into_owned(),
+            expected: "valid unicode",
+        }),
+    }
+}
+
+/// Fold `bytes` into a machine ID with the 32-bit FNV-1a hash
+///
+/// The hash is fixed so that the same input maps to the same machine ID across versions and
+/// platforms.
+#[cfg_attr(
+    not(any(feature = "hostname", feature = "mac-address")),
+    allow(dead_code)
+)]
+pub(crate) fn hash_machine_id(bytes: &[u8]) -> u8 {
+    let hash = bytes.iter().fold(0x811c9dc5u32, |hash, &byte| {
+        (hash ^ byte as u32).wrapping_mul(0x01000193)
+    });
+    (hash ^ (hash >> 8) ^ (hash >> 16) ^ (hash >> 24)) as u8
+}
+
+#[cfg(feature = "alloc")]
+impl<P: MachineIdProvider + ?Sized> MachineIdProvider for alloc::boxed::Box<P> {
+    fn acquire(&self) -> Result<u8, HoraError> {
+        (**self).acquire()
+    }
+
+    fn release(&self) -> Result<(), HoraError> {
+        (**self).release()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn static_machine_id() {
+        let provider = StaticMachineId(42);
+        assert_eq!(provider.acquire(), Ok(42));
+        assert_eq!(provider.release(), Ok(()));
+    }
+
+    #[test]
+    fn env_machine_id() {
+        let provider = EnvMachineId::new("HORA_TEST_ENV_MACHINE_ID");
+        assert_eq!(
+            provider.acquire(),
+            Err(HoraError::MissingEnvVar {
+                name: "HORA_TEST_ENV_MACHINE_ID".to_owned()
+            })
+        );
+
+        std::env::set_var("HORA_TEST_ENV_MACHINE_ID", " 12\n");
+        assert_eq!(provider.acquire(), Ok(12));
+
+        std::env::set_var("HORA_TEST_ENV_MACHINE_ID", "256");
+        let err = provider.acquire().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "environment variable HORA_TEST_ENV_MACHINE_ID is \"256\", expected a machine ID from 0 to 255"
+        );
+        std::env::remove_var("HORA_TEST_ENV_MACHINE_ID");
+    }
+
+    #[test]
+    fn hash_is_stable() {
+        // pinned values, changing them would move every derived machine ID
+        assert_eq!(hash_machine_id(b""), 197);
+        assert_eq!(hash_machine_id(b"web-07"), 76);
+        assert_eq!(hash_machine_id(b"web-08"), 97);
+    }
+
+    #[test]
+    fn trait_object() {
+        let providers: Vec<Box<dyn MachineIdProvider>> =
+            vec![Box::new(StaticMachineId(1)), Box::new(StaticMachineId(2))];
+        let ids: Vec<u8> = providers.iter().map(|p| p.acquire().unwrap()).collect();
+        assert_eq!(ids, [1, 2]);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::encoding::decode_hex;
+use crate::{HoraId, HoraIdParseError};
+use core::fmt;
+use core::str::FromStr;
+
+/// Number of Feistel rounds, 8 rounds let every output bit depend on every input bit many times
+const ROUNDS: usize = 8;
+
+/// Secret key of the permutation applied by [HoraId::obfuscate]
+///
+/// Keep the key in your configuration next to other secrets. Changing it changes every
+/// obfuscated ID, so IDs handed out before can no longer be resolved.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ObfuscationKey {
+    rounds: [u32; ROUNDS],
+}
+
+impl ObfuscationKey {
+    /// Create a key from 16 secret bytes
+    pub const fn new(key: [u8; 16]) -> Self {
+        let mut state = u128::from_le_bytes(key);
+        let mut rounds = [0; ROUNDS];
+        let mut round = 0;
+        while round < ROUNDS {
+            // SplitMix64 over both halves of the key, so similar keys give unrelated round keys
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15_9e37_79b9_7f4a_7c15);
+            let mut z = (state as u64) ^ ((state >> 64) as u64).rotate_left(round as u32 * 8);
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^= z >> 31;
+            rounds[round] = z as u32;
+            round += 1;
+        }
+        Self { rounds }
+    }
+}
+
+/// Doesn't print the key
+impl fmt::Debug for ObfuscationKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ObfuscationKey(..)")
+    }
+}
+
+/// Round function, the finalizer of MurmurHash3 applied to the half block mixed with the key
+fn round(half: u32, key: u32) -> u32 {
+    let mut hash = half ^ key;
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x85eb_ca6b);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(0xc2b2_ae35);
+    hash ^ (hash >> 16)
+}
+
+/// A [HoraId] scrambled with a secret key, for exposing IDs outside your system
+///
+/// Generated IDs are sequential, so anyone who sees a few of them can estimate how many objects
+/// you create and guess valid IDs. [HoraId::obfuscate] applies a keyed Feistel permutation over
+/// all 64 bits, which hides the time, machine ID and sequence from outside. Every [HoraId] maps
+/// to exactly one [ObfuscatedHoraId] and [ObfuscatedHoraId::deobfuscate] restores it, so the
+/// internal IDs stay sortable and nothing needs to be stored.
+///
+/// This is obfuscation, not encryption: the permutation is not a vetted cipher. Don't rely on it
+/// to keep the IDs secret from a determined attacker with many ID pairs.
+///
+/// ## Usage
+/// ```
+/// use hora_id::{HoraId, ObfuscatedHoraId, ObfuscationKey};
+///
+/// let key = ObfuscationKey::new(*b"0123456789abcdef");
+/// let id: HoraId = "00cd01daff010002".parse().unwrap();
+///
+/// let public = id.obfuscate(&key);
+/// let parsed: ObfuscatedHoraId = public.to_string().parse().unwrap();
+/// assert_eq!(parsed.deobfuscate(&key), id);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObfuscatedHoraId {
+    num: u64,
+}
+
+impl HoraId {
+    /// Scramble the [HoraId] with `key`, see [ObfuscatedHoraId]
+    pub fn obfuscate(&self, key: &ObfuscationKey) -> ObfuscatedHoraId {
+        let num = self.to_u64();
+        let (mut left, mut right) = ((num >> 32) as u32, num as u32);
+        for &round_key in &key.rounds {
+            (left, right) = (right, left ^ round(right, round_key));
+        }
+        ObfuscatedHoraId {
+            num: (left as u64) << 32 | right as u64,
+        }
+    }
+}
+
+impl ObfuscatedHoraId {
+    /// Restore the [HoraId], `key` must be the one it was obfuscated with
+    ///
+    /// Every value decodes to some [HoraId], so a wrong key or a guessed value gives an ID that
+    /// doesn't exist rather than an error.
+    pub fn deobfuscate(&self, key: &ObfuscationKey) -> HoraId {
+        let (mut left, mut right) = ((self.num >> 32) as u32, self.num as u32);
+        for &round_key in key.rounds.iter().rev() {
+            (left, right) = (right ^ round(left, round_key), left);
+        }
+        HoraId::from((left as u64) << 32 | right as u64)
+    }
+
+    /// The obfuscated value as a number
+    pub const fn to_u64(&self) -> u64 {
+        self.num
+    }
+
+    /// Create an [ObfuscatedHoraId] from a number returned by [ObfuscatedHoraId::to_u64]
+    pub const fn from_u64(num: u64) -> Self {
+        Self { num }
+    }
+}
+
+/// Formats an [ObfuscatedHoraId] as a 16 character lowercase hexadecimal string
+impl fmt::Display for ObfuscatedHoraId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        crate::encoding::fmt_hex(&self.num.to_be_bytes(), f)
+    }
+}
+
+/// Parses an [ObfuscatedHoraId] from a 16 character hexadecimal string
+impl FromStr for ObfuscatedHoraId {
+    type Err = HoraIdParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = decode_hex(s)?;
+        Ok(Self::from_u64(u64::from_be_bytes(bytes)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: ObfuscationKey = ObfuscationKey::new(*b"0123456789abcdef");
+
+    #[test]
+    fn round_trip() {
+        let other = ObfuscationKey::new(*b"0123456789abcdeg");
+        for num in [0, 1, 57704410318438402, u64::MAX >> 1, u64::MAX] {
+            let id = HoraId::from(num);
+            let public = id.obfuscate(&KEY);
+            assert_eq!(public.deobfuscate(&KEY), id);
+            assert_eq!(public.to_string().parse(), Ok(public));
+            assert_ne!(id.obfuscate(&other), public);
+        }
+    }
+
+    #[test]
+    fn hides_sequence() {
+        // neighbouring IDs differ in about half of their obfuscated bits
+        let changed: u32 = (0..1000u64)
+            .map(|sequence| {
+                let id = HoraId::from(57704410318438400 + sequence);
+                let next = HoraId::from(57704410318438401 + sequence);
+                (id.obfuscate(&KEY).to_u64() ^ next.obfuscate(&KEY).to_u64()).count_ones()
+            })
+            .sum();
+        assert!((30_000..34_000).contains(&changed), "{}", changed);
+    }
+
+    #[test]
+    fn debug_hides_key() {
+        assert_eq!(format!("{:?}", KEY), "ObfuscationKey(..)");
+    }
+}
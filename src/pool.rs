@@ -0,0 +1,291 @@
+//! Pool of generators that split the sequence of one machine ID between them
+
+use crate::random_sequence::RandomSequence;
+use crate::{
+    Clock, GeneratorStats, HoraError, HoraGenerator, HoraId, HoraLayout, Spin, SystemClock,
+    WaitStrategy,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError, TryLockError};
+
+/// Thread-safe generator that spreads IDs over several internal generators
+///
+/// A [SyncHoraGenerator](crate::SyncHoraGenerator) serializes all threads on one lock. The pool
+/// holds `shards` generators instead, each writing its index into the highest bits of the
+/// sequence, so they never produce the same ID. Calls go to the next shard that isn't locked,
+/// which lets many cores generate IDs at once.
+///
+/// Every shard uses a fraction of the sequence numbers of a tick: 4 shards of the default layout
+/// each have 16,384. The shard count is rounded up to a power of two for this split. IDs are
+/// unique and ordered by tick, but IDs of the same tick are not ordered by the time they were
+/// generated.
+///
+/// ## Usage
+/// ```no_run
+/// use hora_id::HoraGeneratorPool;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let pool = Arc::new(HoraGeneratorPool::new(1, 8).unwrap());
+///
+/// let handles: Vec<_> = (0..32)
+///     .map(|_| {
+///         let pool = Arc::clone(&pool);
+///         thread::spawn(move || pool.next())
+///     })
+///     .collect();
+/// for handle in handles {
+///     println!("{}", handle.join().unwrap());
+/// }
+/// ```
+pub struct HoraGeneratorPool<C = SystemClock, W = Spin> {
+    pub(crate) shards: Box<[Mutex<HoraGenerator<C, W>>]>,
+    /// Shard the next call tries first
+    next_shard: AtomicUsize,
+}
+
+impl HoraGeneratorPool {
+    /// Create a pool of `shards` generators with the machine ID `machine_id`
+    ///
+    /// ## Fail condition
+    /// - If `shards` is zero or leaves no bit for the sequence, see [HoraGeneratorPool::from_generator]
+    /// - If the system time is before the HoraID epoch (2025-01-01)
+    pub fn new(machine_id: u8, shards: usize) -> Result<Self, HoraError> {
+        Self::from_generator(HoraGenerator::new(machine_id)?, shards)
+    }
+}
+
+impl<C: Clock + Clone, W: WaitStrategy + Clone> HoraGeneratorPool<C, W> {
+    /// Split `generator` into `shards` generators with the same configuration
+    ///
+    /// The layout, tenant, epoch and policies of `generator` apply to all shards. With a tenant,
+    /// the shard index goes below the tenant bits, so [HoraLayout::tenant](crate::HoraLayout::tenant)
+    /// still reads the tenant from the IDs.
+    ///
+    /// ## Fail condition
+    /// If `shards` is zero, needs as many bits as the sequence has or doesn't fit in 16 bits
+    /// together with the tenant ([HoraError::InvalidShardCount])
+    pub fn from_generator(
+        generator: HoraGenerator<C, W>,
+        shards: usize,
+    ) -> Result<Self, HoraError> {
+        let bits = partition_bits(&generator.layout, shards)?;
+        // `partition_bits` checked that the indexes fit in a u16
+        let shards = (0..shards as u16)
+            .map(|index| Mutex::new(generator.sequence_partition(bits, index)))
+            .collect();
+        Ok(Self {
+            shards,
+            next_shard: AtomicUsize::new(0),
+        })
+    }
+}
+
+/// Number of bits above the sequence of `layout` that `partitions` generators need to have
+/// distinct sequence numbers
+///
+/// ## Fail condition
+/// If `partitions` is zero, needs as many bits as the sequence has, doesn't fit next to the
+/// tenant in 16 bits or is more than [u16::MAX]
+pub(crate) fn partition_bits(layout: &HoraLayout, partitions: usize) -> Result<u8, HoraError> {
+    let max_bits = (layout.sequence_bits() - 1).min(16 - layout.tenant_bits());
+    let max = (1u64 << max_bits).min(u16::MAX as u64);
+    if partitions == 0 || partitions as u64 > max {
+        return Err(HoraError::InvalidShardCount {
+            shards: partitions as u64,
+            max,
+        });
+    }
+    Ok(partitions.next_power_of_two().trailing_zeros() as u8)
+}
+
+impl<C: Clone, W: Clone> HoraGenerator<C, W> {
+    /// Generator with the same configuration that writes `index` into `bits` bits between the
+    /// tenant and the sequence
+    pub(crate) fn sequence_partition(&self, bits: u8, index: u16) -> Self {
+        HoraGenerator {
+            layout: self
+                .layout
+                .with_tenant_bits(self.layout.tenant_bits() + bits),
+            tenant: self.tenant << bits | index,
+            sequence: 0,
+            stats: GeneratorStats::default(),
+            random_sequence: self
+                .random_sequence
+                .as_ref()
+                .map(|_| RandomSequence::default()),
+            clock: self.clock.clone(),
+            wait_strategy: self.wait_strategy.clone(),
+            ..*self
+        }
+    }
+}
+
+impl<C: Clock, W: WaitStrategy> HoraGeneratorPool<C, W> {
+    /// Generate a new [HoraId]
+    ///
+    /// ## Panics
+    /// If the system time is incorrect. Use [HoraGeneratorPool::try_next] to handle this case.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&self) -> HoraId {
+        self.try_next().expect("failed to generate HoraId")
+    }
+
+    /// Generate a new [HoraId], returning an error instead of panicking
+    ///
+    /// Takes the first shard that isn't locked, starting at a different shard on every call. If
+    /// all are locked, waits for one of them.
+    pub fn try_next(&self) -> Result<HoraId, HoraError> {
+        let start = self.next_shard.fetch_add(1, Ordering::Relaxed);
+        let count = self.shards.len();
+        for offset in 0..count {
+            // the generator state is always consistent, so a poisoned lock is safe to reuse
+            let generator = match self.shards[(start + offset) % count].try_lock() {
+                Ok(generator) => generator,
+                Err(TryLockError::Poisoned(err)) => err.into_inner(),
+                Err(TryLockError::WouldBlock) => continue,
+            };
+            return Self::generate(generator);
+        }
+        Self::generate(self.lock(start % count))
+    }
+
+    fn generate(mut generator: MutexGuard<'_, HoraGenerator<C, W>>) -> Result<HoraId, HoraError> {
+        generator.try_next()
+    }
+
+    pub(crate) fn lock(&self, shard: usize) -> MutexGuard<'_, HoraGenerator<C, W>> {
+        self.shards[shard]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Number of generators in the pool
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Counters of all shards added up, see [HoraGenerator::stats]
+    ///
+    /// [GeneratorStats::max_sequence] is the highest sequence within a shard.
+    pub fn stats(&self) -> GeneratorStats {
+        (0..self.shards.len()).fold(GeneratorStats::default(), |mut total, shard| {
+            total.merge(&self.lock(shard).stats());
+            total
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MockClock, EPOCH};
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<HoraGeneratorPool>();
+    }
+
+    #[test]
+    fn shards_split_the_sequence() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let generator = HoraGenerator::new_with_clock(7, clock).unwrap();
+        let pool = HoraGeneratorPool::from_generator(generator, 3).unwrap();
+        assert_eq!(pool.shards(), 3);
+
+        let ids: Vec<HoraId> = (0..6).map(|_| pool.next()).collect();
+        let sequences: Vec<u16> = ids.iter().map(|id| id.sequence()).collect();
+        // 3 shards take 2 bits, each shard counts in its quarter of the sequence
+        assert_eq!(sequences, [1, 16385, 32769, 2, 16386, 32770]);
+        assert!(ids.iter().all(|id| id.machine_id() == 7));
+        assert_eq!(pool.stats().generated(), 6);
+    }
+
+    #[test]
+    fn keeps_tenant() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let generator = HoraGenerator::with_layout_and_clock(HoraLayout::TENANT, 1, clock)
+            .unwrap()
+            .for_tenant(42)
+            .unwrap();
+        let pool = HoraGeneratorPool::from_generator(generator, 4).unwrap();
+        let ids: HashSet<HoraId> = (0..8).map(|_| pool.next()).collect();
+        assert_eq!(ids.len(), 8);
+        assert!(ids.iter().all(|id| id.tenant() == 42));
+    }
+
+    #[test]
+    fn invalid_shard_count() {
+        let generator = HoraGenerator::new(1).unwrap();
+        assert!(matches!(
+            HoraGeneratorPool::from_generator(generator, 0),
+            Err(HoraError::InvalidShardCount {
+                shards: 0,
+                max: 32768
+            })
+        ));
+        assert!(HoraGeneratorPool::new(1, 32768).is_ok());
+        assert!(HoraGeneratorPool::new(1, 32769).is_err());
+    }
+
+    #[test]
+    fn shard_count_limited_by_tenant() {
+        let layout = HoraLayout::WIDE_SEQUENCE.with_tenant_bits(16);
+        let generator = HoraGenerator::with_layout(layout, 1).unwrap();
+        assert!(matches!(
+            HoraGeneratorPool::from_generator(generator, 2),
+            Err(HoraError::InvalidShardCount { shards: 2, max: 1 })
+        ));
+        let generator = HoraGenerator::with_layout(layout, 1).unwrap();
+        assert!(HoraGeneratorPool::from_generator(generator, 1).is_ok());
+
+        let layout = HoraLayout::new(40, 2, 22).with_tenant_bits(4);
+        let generator = HoraGenerator::with_layout(layout, 1).unwrap();
+        assert!(HoraGeneratorPool::from_generator(generator, 4096).is_ok());
+        let generator = HoraGenerator::with_layout(layout, 1).unwrap();
+        assert!(matches!(
+            HoraGeneratorPool::from_generator(generator, 4097),
+            Err(HoraError::InvalidShardCount {
+                shards: 4097,
+                max: 4096
+            })
+        ));
+    }
+
+    #[test]
+    fn shard_count_fits_in_u16() {
+        let layout = HoraLayout::new(40, 2, 22);
+        let generator = HoraGenerator::with_layout(layout, 1).unwrap();
+        assert!(matches!(
+            HoraGeneratorPool::from_generator(generator, 65536),
+            Err(HoraError::InvalidShardCount {
+                shards: 65536,
+                max: 65535
+            })
+        ));
+    }
+
+    #[test]
+    fn shared_across_threads() {
+        let pool = Arc::new(HoraGeneratorPool::new(1, 4).unwrap());
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let pool = Arc::clone(&pool);
+                thread::spawn(move || (0..1000).map(|_| pool.next()).collect::<Vec<_>>())
+            })
+            .collect();
+
+        let mut unique = HashSet::new();
+        for handle in handles {
+            for id in handle.join().unwrap() {
+                assert!(unique.insert(id));
+            }
+        }
+        assert_eq!(unique.len(), 8000);
+        assert_eq!(pool.stats().generated(), 8000);
+    }
+}
@@ -0,0 +1,78 @@
+//! [postgres_types] support for [HoraId], used by `tokio-postgres` and `postgres`
+//!
+//! Binds to `BIGINT` columns through [HoraId::to_u64] and to `BYTEA` columns as 8 bytes.
+
+use crate::HoraId;
+use bytes::BytesMut;
+use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+use std::error::Error;
+
+impl ToSql for HoraId {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        if *ty == Type::INT8 {
+            (self.to_u64() as i64).to_sql(ty, out)
+        } else {
+            self.as_bytes().to_sql(ty, out)
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::INT8 | Type::BYTEA)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for HoraId {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        if *ty == Type::INT8 {
+            let num = i64::from_sql(ty, raw)?;
+            Ok(HoraId::from(num as u64))
+        } else {
+            let bytes = <&[u8]>::from_sql(ty, raw)?;
+            Ok(HoraId::try_from(bytes)?)
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::INT8 | Type::BYTEA)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(ty: &Type) -> BytesMut {
+        let id: HoraId = "00cd01daff010002".parse().unwrap();
+        let mut buf = BytesMut::new();
+        assert!(matches!(id.to_sql_checked(ty, &mut buf), Ok(IsNull::No)));
+        assert_eq!(HoraId::from_sql(ty, &buf).unwrap(), id);
+        buf
+    }
+
+    #[test]
+    fn bigint() {
+        let buf = round_trip(&Type::INT8);
+        assert_eq!(&buf[..], 57704410318438402i64.to_be_bytes());
+    }
+
+    #[test]
+    fn bytea() {
+        let buf = round_trip(&Type::BYTEA);
+        assert_eq!(&buf[..], [0x00, 0xcd, 0x01, 0xda, 0xff, 0x01, 0x00, 0x02]);
+    }
+
+    #[test]
+    fn rejected() {
+        let id = HoraId::from(1);
+        let mut buf = BytesMut::new();
+        assert!(id.to_sql_checked(&Type::TEXT, &mut buf).is_err());
+        assert!(!<HoraId as FromSql>::accepts(&Type::INT4));
+        assert!(HoraId::from_sql(&Type::BYTEA, &[1, 2, 3]).is_err());
+    }
+}
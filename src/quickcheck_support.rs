@@ -0,0 +1,57 @@
+//! [quickcheck] support for [HoraId] and [HoraId128]
+
+use crate::{HoraId, HoraId128};
+use quickcheck::{Arbitrary, Gen};
+
+/// Any 64-bit pattern, including format versions that [HoraId::decode] rejects, shrinking
+/// toward [HoraId::NIL]
+impl Arbitrary for HoraId {
+    fn arbitrary(g: &mut Gen) -> Self {
+        HoraId::from_bytes(u64::arbitrary(g).to_be_bytes())
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(
+            self.to_u64()
+                .shrink()
+                .map(|num| HoraId::from_bytes(num.to_be_bytes())),
+        )
+    }
+}
+
+/// Any 128-bit pattern, shrinking toward zero
+impl Arbitrary for HoraId128 {
+    fn arbitrary(g: &mut Gen) -> Self {
+        HoraId128::from(u128::arbitrary(g).to_be_bytes())
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(
+            self.to_u128()
+                .shrink()
+                .map(|num| HoraId128::from(num.to_be_bytes())),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+
+    #[test]
+    fn round_trip() {
+        fn prop(id: HoraId, long: HoraId128) -> bool {
+            id.to_string().parse() == Ok(id) && long.to_string().parse() == Ok(long)
+        }
+        QuickCheck::new().quickcheck(prop as fn(HoraId, HoraId128) -> bool);
+    }
+
+    #[test]
+    fn shrinks_to_nil() {
+        let id = HoraId::from_u64(57704410318438402).unwrap();
+        assert!(id.shrink().any(|id| id.is_nil()));
+        assert!(id.shrink().all(|smaller| smaller < id));
+        assert_eq!(HoraId::NIL.shrink().count(), 0);
+    }
+}
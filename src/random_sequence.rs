@@ -0,0 +1,30 @@
+use std::collections::HashSet;
+
+/// Random sequence numbers for [HoraGenerator::random_sequence](crate::HoraGenerator)
+///
+/// Remembers the values drawn in the current tick and draws again on a collision.
+#[derive(Debug, Default)]
+pub(crate) struct RandomSequence {
+    /// Tick the values in `used` belong to
+    ticks: u64,
+    used: HashSet<u32>,
+}
+
+impl RandomSequence {
+    /// Draw an unused sequence number of `ticks`, up to `max_sequence` which is all ones
+    ///
+    /// The caller counts the IDs of a tick and never asks for more than `max_sequence + 1`, so a
+    /// free value always exists.
+    pub(crate) fn draw(&mut self, ticks: u64, max_sequence: u32) -> u32 {
+        if ticks != self.ticks {
+            self.ticks = ticks;
+            self.used.clear();
+        }
+        loop {
+            let sequence = rand::random::<u32>() & max_sequence;
+            if self.used.insert(sequence) {
+                return sequence;
+            }
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use crate::{HoraError, HoraId};
+use core::ops::{Bound, RangeBounds, RangeInclusive};
+
+/// Inclusive range of [HoraId]s, for filtering IDs by the time they were created
+///
+/// Create it from a time window with [HoraIdRange::from_millis] or from two IDs with
+/// [HoraIdRange::new]. It implements [RangeBounds], so it can be passed directly to
+/// [BTreeMap::range](std::collections::BTreeMap::range), and converts to a
+/// [`RangeInclusive<u64>`](RangeInclusive) for stores that key by number.
+///
+/// ## Usage
+/// ```
+/// use hora_id::{HoraId, HoraIdRange};
+/// use std::collections::BTreeMap;
+///
+/// let march = HoraIdRange::from_millis(1740787200000, 1743465599999).unwrap();
+///
+/// let mut items = BTreeMap::new();
+/// items.insert(HoraId::min_for_millis(1741000000000).unwrap(), "in march");
+/// items.insert(HoraId::min_for_millis(1744000000000).unwrap(), "in april");
+/// let in_march: Vec<_> = items.range(march).map(|(_, name)| *name).collect();
+/// assert_eq!(in_march, ["in march"]);
+///
+/// let numbers = march.to_u64_range();
+/// assert!(numbers.contains(&HoraId::min_for_millis(1741000000000).unwrap().to_u64()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HoraIdRange {
+    start: HoraId,
+    end: HoraId,
+}
+
+impl HoraIdRange {
+    /// Create a range from `start` to `end`, both inclusive
+    ///
+    /// The range is empty if `start` is greater than `end`.
+    pub fn new(start: HoraId, end: HoraId) -> Self {
+        Self { start, end }
+    }
+
+    /// Create a range of all IDs created from `start_millis` to `end_millis`, both inclusive and
+    /// in milliseconds since the Unix epoch
+    ///
+    /// The bounds are rounded outwards to whole 1/256 second ticks, see
+    /// [HoraId::min_for_millis].
+    ///
+    /// ## Fail condition
+    /// If either time is before the HoraID epoch (2025-01-01) or too far in the future
+    pub fn from_millis(start_millis: u64, end_millis: u64) -> Result<Self, HoraError> {
+        Ok(Self {
+            start: HoraId::min_for_millis(start_millis)?,
+            end: HoraId::max_for_millis(end_millis)?,
+        })
+    }
+
+    /// The first ID in the range
+    pub fn start(&self) -> HoraId {
+        self.start
+    }
+
+    /// The last ID in the range
+    pub fn end(&self) -> HoraId {
+        self.end
+    }
+
+    /// Whether the range contains no IDs
+    pub fn is_empty(&self) -> bool {
+        self.start > self.end
+    }
+
+    /// Whether `id` is within the range
+    pub fn contains(&self, id: &HoraId) -> bool {
+        self.start <= *id && *id <= self.end
+    }
+
+    /// The range as numbers, see [HoraId::to_u64]
+    pub fn to_u64_range(&self) -> RangeInclusive<u64> {
+        self.start.to_u64()..=self.end.to_u64()
+    }
+}
+
+impl RangeBounds<HoraId> for HoraIdRange {
+    fn start_bound(&self) -> Bound<&HoraId> {
+        Bound::Included(&self.start)
+    }
+
+    fn end_bound(&self) -> Bound<&HoraId> {
+        Bound::Included(&self.end)
+    }
+}
+
+impl From<RangeInclusive<HoraId>> for HoraIdRange {
+    fn from(range: RangeInclusive<HoraId>) -> Self {
+        let (start, end) = range.into_inner();
+        Self::new(start, end)
+    }
+}
+
+impl From<HoraIdRange> for RangeInclusive<HoraId> {
+    fn from(range: HoraIdRange) -> Self {
+        range.start..=range.end
+    }
+}
+
+impl From<HoraIdRange> for RangeInclusive<u64> {
+    fn from(range: HoraIdRange) -> Self {
+        range.to_u64_range()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EPOCH;
+
+    #[test]
+    fn contains() {
+        let id: HoraId = "00cd01daff010002".parse().unwrap();
+        let range = HoraIdRange::from_millis(id.timestamp_millis(), id.timestamp_millis()).unwrap();
+        assert!(range.contains(&id));
+        assert!(range.contains(&range.start()) && range.contains(&range.end()));
+        assert!(!range.contains(&HoraId::from(id.to_u64() + (1 << 24))));
+        assert!(!range.contains(&HoraId::from(id.to_u64() - (1 << 24))));
+        assert!(!range.is_empty());
+
+        let empty = HoraIdRange::new(range.end(), range.start());
+        assert!(empty.is_empty());
+        assert!(!empty.contains(&id));
+    }
+
+    #[test]
+    fn conversions() {
+        let range = HoraIdRange::new(HoraId::from(5), HoraId::from(9));
+        assert_eq!(RangeInclusive::<u64>::from(range), 5..=9);
+        assert_eq!(HoraIdRange::from(HoraId::from(5)..=HoraId::from(9)), range);
+        assert_eq!(range.start_bound(), Bound::Included(&HoraId::from(5)));
+        let ids: std::collections::BTreeSet<HoraId> = (0..12).map(HoraId::from).collect();
+        assert_eq!(ids.range(range).count(), 5);
+
+        assert_eq!(
+            HoraIdRange::from_millis(EPOCH - 1, EPOCH),
+            Err(HoraError::TimestampOutOfRange)
+        );
+    }
+}
@@ -0,0 +1,91 @@
+//! [rayon] support for generating large batches of IDs in parallel
+
+use crate::{Clock, HoraError, HoraGeneratorPool, HoraId, WaitStrategy};
+use rayon::prelude::*;
+
+impl<C: Clock + Send, W: WaitStrategy + Send> HoraGeneratorPool<C, W> {
+    /// Generate `n` new [HoraId]s on the rayon thread pool, sorted in ascending order
+    ///
+    /// Each shard of the pool generates its share of the batch on a rayon worker, so the IDs are
+    /// unique across workers for the same reason as with [HoraGeneratorPool::next]. A batch
+    /// that spans several ticks is generated as fast as the clock allows, since every shard has
+    /// its own sequence numbers in each tick.
+    ///
+    /// ## Panics
+    /// If the system time is incorrect. Use [HoraGeneratorPool::try_par_next_n] to handle this
+    /// case.
+    ///
+    /// ## Usage
+    /// ```no_run
+    /// use hora_id::HoraGeneratorPool;
+    ///
+    /// let pool = HoraGeneratorPool::new(1, 16).unwrap();
+    /// let ids = pool.par_next_n(10_000_000);
+    /// assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    /// ```
+    pub fn par_next_n(&self, n: usize) -> Vec<HoraId> {
+        self.try_par_next_n(n).expect("failed to generate HoraId")
+    }
+
+    /// Generate `n` new [HoraId]s on the rayon thread pool, returning an error instead of
+    /// panicking
+    ///
+    /// ## Fail condition
+    /// Same as [HoraGenerator::try_next](crate::HoraGenerator::try_next). The IDs generated by
+    /// the other shards are dropped.
+    pub fn try_par_next_n(&self, n: usize) -> Result<Vec<HoraId>, HoraError> {
+        let shards = self.shards.len();
+        let batches = (0..shards)
+            .into_par_iter()
+            .map(|shard| {
+                // the first shards take one more ID each if `n` doesn't divide evenly
+                let count = n / shards + usize::from(shard < n % shards);
+                let mut ids = Vec::new();
+                self.lock(shard).try_next_n_into(count, &mut ids)?;
+                Ok(ids)
+            })
+            .collect::<Result<Vec<_>, HoraError>>()?;
+        let mut ids = batches.concat();
+        ids.par_sort_unstable();
+        Ok(ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HoraGenerator, HoraLayout, MockClock, EPOCH};
+    use std::collections::HashSet;
+
+    #[test]
+    fn par_next_n() {
+        let pool = HoraGeneratorPool::new(1, 4).unwrap();
+        let ids = pool.par_next_n(100_003);
+        assert_eq!(ids.len(), 100_003);
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(pool.stats().generated(), 100_003);
+        assert!(ids.binary_search(&pool.next()).is_err());
+    }
+
+    #[test]
+    fn unique_with_single_id_calls() {
+        let pool = HoraGeneratorPool::new(1, 3).unwrap();
+        let mut unique: HashSet<HoraId> = (0..1000).map(|_| pool.next()).collect();
+        for id in pool.par_next_n(10_000) {
+            assert!(unique.insert(id));
+        }
+        assert!(pool.par_next_n(0).is_empty());
+    }
+
+    #[test]
+    fn error() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let layout = HoraLayout::new(52, 8, 4);
+        let generator = HoraGenerator::with_layout_and_clock(layout, 1, clock)
+            .unwrap()
+            .with_wait_strategy(crate::Fail);
+        let pool = HoraGeneratorPool::from_generator(generator, 2).unwrap();
+        // 2 shards with 8 sequence numbers each, the clock never moves on
+        assert_eq!(pool.try_par_next_n(100), Err(HoraError::SequenceExhausted));
+    }
+}
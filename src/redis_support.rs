@@ -0,0 +1,371 @@
+//! Machine ID leases coordinated through [redis]
+//!
+//! Every instance claims one of the 256 machine IDs by setting a key with a TTL. A background
+//! thread renews the lease while the allocator is alive, and the key is deleted when the lease is
+//! released, so IDs of stopped instances become available again once their lease expires.
+
+use crate::{HoraError, HoraGenerator, MachineIdProvider};
+use redis::{Client, Connection, RedisError, Script};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+const RENEW_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+    return redis.call("PEXPIRE", KEYS[1], ARGV[2])
+end
+return 0
+"#;
+
+const RELEASE_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+    return redis.call("DEL", KEYS[1])
+end
+return 0
+"#;
+
+impl From<RedisError> for HoraError {
+    fn from(err: RedisError) -> Self {
+        HoraError::Coordination(err.to_string())
+    }
+}
+
+/// Claims a unique machine ID from a Redis server
+///
+/// The claimed ID is stored as `<prefix><machine_id>` with a random token as its value and
+/// expires after the TTL unless it is renewed. Renewal runs every third of the TTL on a
+/// background thread until [RedisMachineIdAllocator::release] is called or the allocator is
+/// dropped. The thread reconnects when the connection to the server is lost.
+///
+/// ## Usage
+/// ```no_run
+/// use hora_id::RedisMachineIdAllocator;
+/// use std::time::Duration;
+///
+/// let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+/// let allocator = RedisMachineIdAllocator::new(client).with_ttl(Duration::from_secs(10));
+///
+/// let mut generator = allocator.generator().unwrap();
+/// let id = generator.next();
+/// assert_eq!(id.machine_id(), allocator.machine_id().unwrap());
+/// ```
+pub struct RedisMachineIdAllocator {
+    client: Client,
+    key_prefix: String,
+    ttl: Duration,
+    lease: Mutex<Option<Lease>>,
+}
+
+struct Lease {
+    machine_id: u8,
+    key: String,
+    token: String,
+    state: Arc<LeaseState>,
+    stop: Sender<()>,
+    renewal: JoinHandle<()>,
+}
+
+/// Outcome of the renewals, shared with the renewal thread
+struct LeaseState {
+    /// Cleared when a renewal finds the key expired or taken by another instance
+    held: AtomicBool,
+    /// When the last successful renewal was sent, the key expires one TTL after it
+    renewed: Mutex<Instant>,
+}
+
+impl LeaseState {
+    fn is_held(&self, ttl: Duration) -> bool {
+        let renewed = *self.renewed.lock().unwrap_or_else(PoisonError::into_inner);
+        self.held.load(Ordering::Acquire) && renewed.elapsed() < ttl
+    }
+}
+
+impl RedisMachineIdAllocator {
+    /// Create an allocator using keys prefixed with `hora_id:machine:` and a 30 second TTL
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            key_prefix: "hora_id:machine:".to_owned(),
+            ttl: Duration::from_secs(30),
+            lease: Mutex::new(None),
+        }
+    }
+
+    /// Use `prefix` for the lease keys, to run several independent fleets on one server
+    pub fn with_key_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.key_prefix = prefix.into();
+        self
+    }
+
+    /// Set how long a lease survives without renewal
+    ///
+    /// A crashed instance keeps its machine ID for up to this long.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Claim an unused machine ID and start renewing its lease
+    ///
+    /// Returns the ID that is already held if called again before [release](Self::release).
+    ///
+    /// ## Fail condition
+    /// If the server can't be reached or all 256 machine IDs are leased
+    pub fn acquire(&self) -> Result<u8, HoraError> {
+        let mut lease = self.lease.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(lease) = lease.as_ref() {
+            return Ok(lease.machine_id);
+        }
+
+        let mut conn = self.client.get_connection()?;
+        let token = format!("{:016x}", rand::random::<u64>());
+        let ttl_millis = self.ttl.as_millis() as u64;
+        let sent = Instant::now();
+        // start at a random ID so that instances starting together rarely race for the same key
+        let start = rand::random::<u8>();
+        for offset in 0..=u8::MAX {
+            let machine_id = start.wrapping_add(offset);
+            let key = format!("{}{}", self.key_prefix, machine_id);
+            let claimed: Option<String> = redis::cmd("SET")
+                .arg(&key)
+                .arg(&token)
+                .arg("NX")
+                .arg("PX")
+                .arg(ttl_millis)
+                .query(&mut conn)?;
+            if claimed.is_some() {
+                *lease = Some(self.start_renewal(conn, sent, machine_id, key, token));
+                return Ok(machine_id);
+            }
+        }
+        Err(HoraError::MachineIdsExhausted)
+    }
+
+    /// Claim a machine ID and create a [HoraGenerator] using it
+    ///
+    /// ## Fail condition
+    /// If [acquire](Self::acquire) fails or the system time is incorrect
+    pub fn generator(&self) -> Result<HoraGenerator, HoraError> {
+        HoraGenerator::new(self.acquire()?)
+    }
+
+    /// The machine ID currently leased, if any
+    pub fn machine_id(&self) -> Option<u8> {
+        let lease = self.lease.lock().unwrap_or_else(PoisonError::into_inner);
+        lease.as_ref().map(|lease| lease.machine_id)
+    }
+
+    /// Whether the lease is still held
+    ///
+    /// Returns false if no ID was acquired, a renewal found that the key expired or was taken
+    /// by another instance, for example after a long pause, or no renewal succeeded for a TTL,
+    /// for example while the server is unreachable. The key may have expired at that point, so
+    /// IDs generated after it may collide with another instance.
+    pub fn is_held(&self) -> bool {
+        let lease = self.lease.lock().unwrap_or_else(PoisonError::into_inner);
+        lease
+            .as_ref()
+            .is_some_and(|lease| lease.state.is_held(self.ttl))
+    }
+
+    /// Stop renewing the lease and delete its key so that the machine ID can be reused
+    ///
+    /// Does nothing if no ID is leased.
+    pub fn release(&self) -> Result<(), HoraError> {
+        let lease = self
+            .lease
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        let Some(lease) = lease else {
+            return Ok(());
+        };
+        drop(lease.stop);
+        let _ = lease.renewal.join();
+
+        let mut conn = self.client.get_connection()?;
+        Script::new(RELEASE_SCRIPT)
+            .key(&lease.key)
+            .arg(&lease.token)
+            .invoke::<i64>(&mut conn)?;
+        Ok(())
+    }
+
+    fn start_renewal(
+        &self,
+        conn: Connection,
+        claimed: Instant,
+        machine_id: u8,
+        key: String,
+        token: String,
+    ) -> Lease {
+        let state = Arc::new(LeaseState {
+            held: AtomicBool::new(true),
+            renewed: Mutex::new(claimed),
+        });
+        let (stop, stopped) = mpsc::channel::<()>();
+        let ttl = self.ttl;
+        let renewal = {
+            let client = self.client.clone();
+            let state = Arc::clone(&state);
+            let key = key.clone();
+            let token = token.clone();
+            thread::spawn(move || {
+                let script = Script::new(RENEW_SCRIPT);
+                let mut conn = Some(conn);
+                // only a disconnect of the sender stops the loop, no message is ever sent
+                while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(ttl / 3) {
+                    let sent = Instant::now();
+                    let renewed = match &mut conn {
+                        Some(conn) => Ok(conn),
+                        None => client
+                            .get_connection_with_timeout(ttl / 3)
+                            .map(|new| conn.insert(new)),
+                    }
+                    .and_then(|conn| {
+                        script
+                            .key(&key)
+                            .arg(&token)
+                            .arg(ttl.as_millis() as u64)
+                            .invoke::<i64>(conn)
+                    });
+                    match renewed {
+                        Ok(1) => {
+                            *state.renewed.lock().unwrap_or_else(PoisonError::into_inner) = sent;
+                            state.held.store(true, Ordering::Release);
+                        }
+                        Ok(_) => state.held.store(false, Ordering::Release),
+                        // reconnect and retry on the next interval, `is_held` turns false once
+                        // the TTL passed without a renewal
+                        Err(_) => conn = None,
+                    }
+                }
+            })
+        };
+        Lease {
+            machine_id,
+            key,
+            token,
+            state,
+            stop,
+            renewal,
+        }
+    }
+}
+
+impl MachineIdProvider for RedisMachineIdAllocator {
+    fn acquire(&self) -> Result<u8, HoraError> {
+        RedisMachineIdAllocator::acquire(self)
+    }
+
+    fn release(&self) -> Result<(), HoraError> {
+        RedisMachineIdAllocator::release(self)
+    }
+}
+
+impl Drop for RedisMachineIdAllocator {
+    fn drop(&mut self) {
+        let _ = self.release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    fn unreachable() -> RedisMachineIdAllocator {
+        // port 1 is reserved, so connecting fails immediately
+        RedisMachineIdAllocator::new(Client::open("redis://127.0.0.1:1/").unwrap())
+    }
+
+    #[test]
+    fn connection_error() {
+        let allocator = unreachable();
+        assert!(matches!(
+            allocator.acquire(),
+            Err(HoraError::Coordination(_))
+        ));
+        assert!(allocator.generator().is_err());
+        assert_eq!(allocator.machine_id(), None);
+        assert!(!allocator.is_held());
+    }
+
+    #[test]
+    fn release_without_lease() {
+        assert_eq!(unreachable().release(), Ok(()));
+    }
+
+    /// Minimal server that claims every key and renews every lease, closing the first connection
+    /// after the claim and stopping after `accept` connections. Returns its address and the
+    /// number of renewals it answered.
+    fn flaky_server(accept: usize) -> (String, Arc<Mutex<usize>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("redis://{}/", listener.local_addr().unwrap());
+        let renewals = Arc::new(Mutex::new(0));
+        let counter = Arc::clone(&renewals);
+        thread::spawn(move || {
+            for (index, stream) in listener.incoming().take(accept).enumerate() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                while let Some(command) = read_command(&mut reader) {
+                    if command.eq_ignore_ascii_case("EVALSHA") {
+                        *counter.lock().unwrap() += 1;
+                        stream.write_all(b":1\r\n").unwrap();
+                    } else {
+                        stream.write_all(b"+OK\r\n").unwrap();
+                    }
+                    if index == 0 && command.eq_ignore_ascii_case("SET") {
+                        break;
+                    }
+                }
+            }
+        });
+        (address, renewals)
+    }
+
+    /// Read a command sent as an array of bulk strings and return its name
+    fn read_command(reader: &mut impl BufRead) -> Option<String> {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok().filter(|&read| read > 0)?;
+        let count: usize = line.trim_end().strip_prefix('*')?.parse().ok()?;
+        let mut name = None;
+        for _ in 0..count * 2 {
+            line.clear();
+            reader.read_line(&mut line).ok()?;
+            if !line.starts_with('$') && name.is_none() {
+                name = Some(line.trim_end().to_owned());
+            }
+        }
+        name
+    }
+
+    #[test]
+    fn reconnects_after_disconnect() {
+        let (address, renewals) = flaky_server(usize::MAX);
+        let allocator = RedisMachineIdAllocator::new(Client::open(address).unwrap())
+            .with_ttl(Duration::from_millis(300));
+        allocator.acquire().unwrap();
+        // the first renewal fails on the closed connection, the next ones reconnect
+        thread::sleep(Duration::from_millis(600));
+        assert!(*renewals.lock().unwrap() > 0);
+        assert!(allocator.is_held());
+    }
+
+    #[test]
+    fn not_held_without_renewal() {
+        let (address, renewals) = flaky_server(1);
+        let allocator = RedisMachineIdAllocator::new(Client::open(address).unwrap())
+            .with_ttl(Duration::from_millis(300));
+        allocator.acquire().unwrap();
+        assert!(allocator.is_held());
+        // the server is gone after the claim, so the key expires without renewals
+        thread::sleep(Duration::from_millis(400));
+        assert_eq!(*renewals.lock().unwrap(), 0);
+        assert!(!allocator.is_held());
+        assert!(allocator.machine_id().is_some());
+    }
+}
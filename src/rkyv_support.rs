@@ -0,0 +1,84 @@
+//! [rkyv] support for [HoraId] and [HoraId128]
+//!
+//! Both types derive `Archive`, `Serialize` and `Deserialize`. The archived forms
+//! [ArchivedHoraId] and [ArchivedHoraId128] have the same bytes and sort order as the IDs, so
+//! archived indexes can be searched in place, and they compare directly with the IDs.
+
+use crate::{ArchivedHoraId, ArchivedHoraId128, HoraId, HoraId128};
+use core::cmp::Ordering;
+
+/// Copy the ID out of an archive without going through a deserializer
+impl From<&ArchivedHoraId> for HoraId {
+    fn from(archived: &ArchivedHoraId) -> Self {
+        HoraId {
+            inner: archived.inner,
+        }
+    }
+}
+
+/// Copy the ID out of an archive without going through a deserializer
+impl From<&ArchivedHoraId128> for HoraId128 {
+    fn from(archived: &ArchivedHoraId128) -> Self {
+        HoraId128::from(archived.inner)
+    }
+}
+
+// Only the archived side compares with the ID, an impl on HoraId would break type inference in
+// code like `id == s.parse().unwrap()`
+
+impl PartialEq<HoraId> for ArchivedHoraId {
+    fn eq(&self, other: &HoraId) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl PartialOrd<HoraId> for ArchivedHoraId {
+    fn partial_cmp(&self, other: &HoraId) -> Option<Ordering> {
+        self.inner.partial_cmp(&other.inner)
+    }
+}
+
+impl PartialEq<HoraId128> for ArchivedHoraId128 {
+    fn eq(&self, other: &HoraId128) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl PartialOrd<HoraId128> for ArchivedHoraId128 {
+    fn partial_cmp(&self, other: &HoraId128) -> Option<Ordering> {
+        self.inner.partial_cmp(&other.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rkyv::rancor::Error;
+
+    #[test]
+    fn zero_copy() {
+        let ids: Vec<HoraId> = (0..100u64)
+            .map(|n| HoraId::from_u64(57704410318438402 + n).unwrap())
+            .collect();
+        let bytes = rkyv::to_bytes::<Error>(&ids).unwrap();
+        let archived = rkyv::access::<rkyv::Archived<Vec<HoraId>>, Error>(&bytes).unwrap();
+        assert_eq!(archived.len(), 100);
+        assert_eq!(archived[7], ids[7]);
+        assert!(archived[7] < ids[8]);
+        assert_eq!(
+            archived.binary_search_by(|id| id.partial_cmp(&ids[42]).unwrap()),
+            Ok(42)
+        );
+        assert_eq!(HoraId::from(&archived[99]), ids[99]);
+        assert_eq!(
+            rkyv::deserialize::<Vec<HoraId>, Error>(archived).unwrap(),
+            ids
+        );
+
+        let id: HoraId128 = "00cd01daff01000200cd01daff010003".parse().unwrap();
+        let bytes = rkyv::to_bytes::<Error>(&id).unwrap();
+        let archived = rkyv::access::<ArchivedHoraId128, Error>(&bytes).unwrap();
+        assert_eq!(*archived, id);
+        assert_eq!(HoraId128::from(archived), id);
+    }
+}
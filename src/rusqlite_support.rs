@@ -0,0 +1,72 @@
+//! [rusqlite] support for [HoraId]
+//!
+//! Written as an `INTEGER` via [HoraId::to_u64], read from `INTEGER` or an 8 byte `BLOB`.
+
+use crate::HoraId;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+impl ToSql for HoraId {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.to_u64() as i64))
+    }
+}
+
+impl FromSql for HoraId {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Integer(num) => Ok(HoraId::from(num as u64)),
+            ValueRef::Blob(bytes) => {
+                HoraId::try_from(bytes).map_err(|_| FromSqlError::InvalidBlobSize {
+                    expected_size: 8,
+                    blob_size: bytes.len(),
+                })
+            }
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    #[test]
+    fn round_trip() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute("CREATE TABLE items (id INTEGER PRIMARY KEY, raw BLOB)", ())
+            .unwrap();
+
+        let id: HoraId = "00cd01daff010002".parse().unwrap();
+        let later = HoraId::from(id.to_u64() + 1);
+        for item in [later, id] {
+            conn.execute(
+                "INSERT INTO items (id, raw) VALUES (?1, ?2)",
+                (item, item.as_bytes()),
+            )
+            .unwrap();
+        }
+
+        let (first, raw): (HoraId, HoraId) = conn
+            .query_row("SELECT id, raw FROM items ORDER BY id", (), |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert_eq!(first, id);
+        assert_eq!(raw, id);
+
+        let num: i64 = conn
+            .query_row("SELECT id FROM items WHERE id = ?1", [id], |row| row.get(0))
+            .unwrap();
+        assert_eq!(num, 57704410318438402);
+    }
+
+    #[test]
+    fn invalid() {
+        let conn = Connection::open_in_memory().unwrap();
+        let blob = conn.query_row("SELECT x'0102'", (), |row| row.get::<_, HoraId>(0));
+        assert!(blob.is_err());
+        let text = conn.query_row("SELECT 'abc'", (), |row| row.get::<_, HoraId>(0));
+        assert!(text.is_err());
+    }
+}
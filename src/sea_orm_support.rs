@@ -0,0 +1,112 @@
+//! [sea_orm] support for [HoraId]
+//!
+//! Stored in a `BIGINT` column via [HoraId::to_u64]. Primary keys need `auto_increment = false`.
+
+use crate::HoraId;
+use sea_orm::sea_query::{ArrayType, ColumnType, Nullable, Value, ValueType, ValueTypeErr};
+use sea_orm::{ColIdx, DbErr, QueryResult, TryFromU64, TryGetError, TryGetable};
+
+impl From<HoraId> for Value {
+    fn from(id: HoraId) -> Self {
+        Value::BigInt(Some(id.to_u64() as i64))
+    }
+}
+
+impl Nullable for HoraId {
+    fn null() -> Value {
+        Value::BigInt(None)
+    }
+}
+
+impl ValueType for HoraId {
+    fn try_from(v: Value) -> Result<Self, ValueTypeErr> {
+        match v {
+            Value::BigInt(Some(num)) => Ok(HoraId::from(num as u64)),
+            _ => Err(ValueTypeErr),
+        }
+    }
+
+    fn type_name() -> String {
+        "HoraId".to_owned()
+    }
+
+    fn array_type() -> ArrayType {
+        ArrayType::BigInt
+    }
+
+    fn column_type() -> ColumnType {
+        ColumnType::BigInteger
+    }
+}
+
+impl TryGetable for HoraId {
+    fn try_get_by<I: ColIdx>(res: &QueryResult, index: I) -> Result<Self, TryGetError> {
+        let num = i64::try_get_by(res, index)?;
+        Ok(HoraId::from(num as u64))
+    }
+}
+
+impl TryFromU64 for HoraId {
+    fn try_from_u64(n: u64) -> Result<Self, DbErr> {
+        Ok(HoraId::from(n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::entity::prelude::*;
+    use sea_orm::{DatabaseBackend, MockDatabase, Statement};
+
+    mod item {
+        use crate::HoraId;
+        use sea_orm::entity::prelude::*;
+
+        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+        #[sea_orm(table_name = "items")]
+        pub struct Model {
+            #[sea_orm(primary_key, auto_increment = false)]
+            pub id: HoraId,
+            pub name: String,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    #[test]
+    fn value() {
+        let id: HoraId = "00cd01daff010002".parse().unwrap();
+        let value = Value::from(id);
+        assert_eq!(value, Value::BigInt(Some(57704410318438402)));
+        assert_eq!(<HoraId as ValueType>::try_from(value).unwrap(), id);
+        assert!(<HoraId as ValueType>::try_from(Value::Int(Some(1))).is_err());
+        assert_eq!(HoraId::null(), Value::BigInt(None));
+        assert_eq!(HoraId::try_from_u64(57704410318438402).unwrap(), id);
+    }
+
+    #[tokio::test]
+    async fn entity() {
+        let id: HoraId = "00cd01daff010002".parse().unwrap();
+        let model = item::Model {
+            id,
+            name: "first".to_owned(),
+        };
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([[model.clone()]])
+            .into_connection();
+
+        let found = item::Entity::find_by_id(id).one(&db).await.unwrap();
+        assert_eq!(found, Some(model));
+        assert_eq!(
+            db.into_transaction_log()[0].statements()[0],
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT "items"."id", "items"."name" FROM "items" WHERE "items"."id" = $1 LIMIT $2"#,
+                [Value::BigInt(Some(57704410318438402)), 1u64.into()]
+            )
+        );
+    }
+}
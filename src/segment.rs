@@ -0,0 +1,172 @@
+//! Segment mode, where clients take IDs from blocks handed out by a central allocator
+
+use crate::{HoraError, HoraId, HoraIdRange};
+use core::ops::RangeInclusive;
+
+/// Hands out blocks of IDs that no other caller receives
+///
+/// Every block is a [HoraIdRange] of consecutive numbers, all of which are valid IDs. The
+/// allocator is the only place that needs a machine ID and a clock, so clients such as short
+/// lived jobs or processes on hosts with unreliable clocks can create IDs without either.
+///
+/// [SyncHoraGenerator](crate::SyncHoraGenerator) implements it for use in the same process.
+/// With the `segment-http` feature, `HttpSegmentAllocator` requests blocks from the `/segment`
+/// endpoint of the `hora-http` server.
+pub trait SegmentAllocator {
+    /// Allocate a block of up to `size` consecutive IDs
+    ///
+    /// The block may be smaller than `size`, but should never be empty.
+    fn allocate(&self, size: u32) -> Result<HoraIdRange, HoraError>;
+}
+
+impl<A: SegmentAllocator + ?Sized> SegmentAllocator for &A {
+    fn allocate(&self, size: u32) -> Result<HoraIdRange, HoraError> {
+        (**self).allocate(size)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A: SegmentAllocator + ?Sized> SegmentAllocator for std::sync::Arc<A> {
+    fn allocate(&self, size: u32) -> Result<HoraIdRange, HoraError> {
+        (**self).allocate(size)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C: crate::Clock, W: crate::WaitStrategy> SegmentAllocator for crate::SyncHoraGenerator<C, W> {
+    fn allocate(&self, size: u32) -> Result<HoraIdRange, HoraError> {
+        self.try_next_block(size)
+    }
+}
+
+/// Generator that takes IDs from blocks of a [SegmentAllocator]
+///
+/// A block of `segment_size` IDs is requested when the previous one is used up, so the
+/// allocator is contacted once per block instead of once per ID. IDs are sorted within a block
+/// and carry the time the block was allocated. IDs left in a block when the generator is
+/// dropped are never used.
+///
+/// ## Usage
+/// ```
+/// use hora_id::{SegmentGenerator, SyncHoraGenerator};
+/// use std::sync::Arc;
+///
+/// let allocator = Arc::new(SyncHoraGenerator::new(1).unwrap());
+/// let mut first = SegmentGenerator::new(Arc::clone(&allocator), 1000);
+/// let mut second = SegmentGenerator::new(allocator, 1000);
+///
+/// let (a, b) = (first.next(), second.next());
+/// assert_ne!(a, b);
+/// assert_eq!(first.remaining(), 999);
+/// ```
+pub struct SegmentGenerator<A> {
+    allocator: A,
+    segment_size: u32,
+    segment: RangeInclusive<u64>,
+}
+
+impl<A: SegmentAllocator> SegmentGenerator<A> {
+    /// Create a generator requesting blocks of `segment_size` IDs from `allocator`
+    ///
+    /// No block is requested until the first ID is generated.
+    pub fn new(allocator: A, segment_size: u32) -> Self {
+        #[allow(clippy::reversed_empty_ranges)]
+        let segment = 1..=0;
+        Self {
+            allocator,
+            segment_size,
+            segment,
+        }
+    }
+
+    /// Generate a new [HoraId]
+    ///
+    /// ## Panics
+    /// If a new block is needed and the allocator fails. Use [SegmentGenerator::try_next] to
+    /// handle this case.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> HoraId {
+        self.try_next().expect("failed to allocate HoraId segment")
+    }
+
+    /// Generate a new [HoraId], returning an error instead of panicking
+    ///
+    /// ## Fail condition
+    /// If a new block is needed and the allocator fails, or returns an empty block
+    /// ([HoraError::SequenceExhausted])
+    pub fn try_next(&mut self) -> Result<HoraId, HoraError> {
+        if let Some(num) = self.segment.next() {
+            return Ok(HoraId::from(num));
+        }
+        self.segment = self.allocator.allocate(self.segment_size)?.to_u64_range();
+        let num = self.segment.next().ok_or(HoraError::SequenceExhausted)?;
+        Ok(HoraId::from(num))
+    }
+
+    /// Number of IDs left in the current block
+    pub fn remaining(&self) -> u64 {
+        if self.segment.is_empty() {
+            0
+        } else {
+            self.segment.end() - self.segment.start() + 1
+        }
+    }
+
+    /// The allocator blocks are requested from
+    pub fn allocator(&self) -> &A {
+        &self.allocator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HoraGenerator, HoraLayout, MockClock, SyncHoraGenerator, EPOCH};
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    #[test]
+    fn block() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let layout = HoraLayout::new(48, 8, 8);
+        let mut generator = HoraGenerator::with_layout_and_clock(layout, 1, clock.clone()).unwrap();
+        clock.advance(4);
+        let block = generator.try_next_block(100).unwrap();
+        assert_eq!(block.to_u64_range().count(), 100);
+        assert_eq!(generator.stats().generated(), 100);
+        // the rest of the tick
+        let block = generator.try_next_block(1000).unwrap();
+        assert_eq!(block.to_u64_range().count(), 156);
+        assert_eq!(generator.stats().max_sequence(), 255);
+    }
+
+    #[test]
+    fn segments() {
+        let allocator = Arc::new(SyncHoraGenerator::new(1).unwrap());
+        let mut first = SegmentGenerator::new(Arc::clone(&allocator), 10);
+        let mut second = SegmentGenerator::new(&*allocator, 10);
+        assert_eq!(first.remaining(), 0);
+
+        let mut ids = HashSet::new();
+        for _ in 0..25 {
+            assert!(ids.insert(first.next()));
+            assert!(ids.insert(second.next()));
+        }
+        assert_eq!(first.remaining(), 5);
+        assert!(ids.iter().all(|id| id.machine_id() == 1));
+    }
+
+    #[test]
+    fn empty_segment() {
+        struct Empty;
+
+        impl SegmentAllocator for Empty {
+            fn allocate(&self, _: u32) -> Result<HoraIdRange, HoraError> {
+                Ok(HoraIdRange::new(HoraId::MAX, HoraId::NIL))
+            }
+        }
+
+        let mut generator = SegmentGenerator::new(Empty, 10);
+        assert_eq!(generator.try_next(), Err(HoraError::SequenceExhausted));
+    }
+}
@@ -0,0 +1,109 @@
+//! [SegmentAllocator] that requests blocks from an HTTP service
+
+use crate::{HoraError, HoraId, HoraIdRange, SegmentAllocator};
+use serde_json::Value;
+use ureq::Agent;
+
+/// Requests blocks of IDs from an HTTP endpoint such as `/segment` of the `hora-http` server
+///
+/// Sends `GET <url>?size=<size>` and expects a JSON object with the first and last ID of the
+/// block as hexadecimal strings: `{"start": "035c44620e010000", "end": "035c44620e01270f"}`.
+///
+/// ## Usage
+/// ```no_run
+/// use hora_id::{HttpSegmentAllocator, SegmentGenerator};
+///
+/// let allocator = HttpSegmentAllocator::new("http://hora-http:8080/segment");
+/// let mut generator = SegmentGenerator::new(allocator, 10_000);
+/// let id = generator.next();
+/// ```
+pub struct HttpSegmentAllocator {
+    agent: Agent,
+    url: String,
+}
+
+impl HttpSegmentAllocator {
+    /// Create an allocator requesting blocks from `url`
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_agent(Agent::new(), url)
+    }
+
+    /// Create an allocator sending requests with `agent`, to configure timeouts or a proxy
+    pub fn with_agent(agent: Agent, url: impl Into<String>) -> Self {
+        Self {
+            agent,
+            url: url.into(),
+        }
+    }
+}
+
+impl SegmentAllocator for HttpSegmentAllocator {
+    fn allocate(&self, size: u32) -> Result<HoraIdRange, HoraError> {
+        let body: Value = self
+            .agent
+            .get(&self.url)
+            .query("size", &size.to_string())
+            .call()?
+            .into_json()
+            .map_err(|err| HoraError::Coordination(err.to_string()))?;
+        let bound = |name: &str| -> Result<HoraId, HoraError> {
+            body[name]
+                .as_str()
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| {
+                    HoraError::Coordination(format!("segment response without a valid `{}`", name))
+                })
+        };
+        Ok(HoraIdRange::new(bound("start")?, bound("end")?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serve one request with `body`, returning the URL and the request line
+    fn serve(body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/segment", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            request_line
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn allocate() {
+        let (url, server) = serve(r#"{"start":"00cd01daff010000","end":"00cd01daff010009"}"#);
+        let range = HttpSegmentAllocator::new(url).allocate(10).unwrap();
+        assert!(server.join().unwrap().starts_with("GET /segment?size=10 "));
+        assert_eq!(range.start(), HoraId::from(57704410318438400));
+        assert_eq!(range.to_u64_range().count(), 10);
+    }
+
+    #[test]
+    fn invalid_response() {
+        let (url, server) = serve(r#"{"start":"00cd01daff010000"}"#);
+        let result = HttpSegmentAllocator::new(url).allocate(10);
+        server.join().unwrap();
+        assert!(matches!(result, Err(HoraError::Coordination(_))));
+    }
+}
@@ -0,0 +1,295 @@
+//! [serde] support for [HoraId] and [HoraId128]
+//!
+//! Human-readable formats such as JSON use the hexadecimal string, while compact binary formats
+//! store a [HoraId] as a `u64` and a [HoraId128] as 16 bytes. [decimal] writes the number as a
+//! decimal string instead.
+
+use crate::{HoraId, HoraId128};
+use core::fmt;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+impl Serialize for HoraId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_u64(self.to_u64())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HoraId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(HoraIdVisitor)
+        } else {
+            deserializer.deserialize_u64(HoraIdVisitor)
+        }
+    }
+}
+
+struct HoraIdVisitor;
+
+impl Visitor<'_> for HoraIdVisitor {
+    type Value = HoraId;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a 16 character hexadecimal string or a u64")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse().map_err(E::custom)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        HoraId::from_u64(v).ok_or_else(|| E::custom("invalid HoraId"))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        HoraId::try_from(v).map_err(|_| E::invalid_length(v.len(), &"8 bytes"))
+    }
+}
+
+impl Serialize for HoraId128 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_bytes(self.as_bytes())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HoraId128 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(HoraId128Visitor)
+        } else {
+            deserializer.deserialize_bytes(HoraId128Visitor)
+        }
+    }
+}
+
+struct HoraId128Visitor;
+
+impl Visitor<'_> for HoraId128Visitor {
+    type Value = HoraId128;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a 32 character hexadecimal string or 16 bytes")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse().map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        HoraId128::try_from(v).map_err(|_| E::invalid_length(v.len(), &"16 bytes"))
+    }
+}
+
+/// Serialize a [HoraId] as a decimal string, for APIs that expose IDs as numbers
+///
+/// JavaScript numbers lose precision above 2^53, so `JSON.parse` corrupts most IDs written as
+/// JSON numbers. Use this module with `#[serde(with = "hora_id::serde_decimal")]` to write
+/// IDs as strings of digits like `"57704410318438402"` in human-readable formats instead. Binary
+/// formats store the `u64` as usual.
+///
+/// Deserializing accepts both the string and a plain number.
+///
+/// ```
+/// use hora_id::HoraId;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct User {
+///     #[serde(with = "hora_id::serde_decimal")]
+///     id: HoraId,
+///     #[serde(with = "hora_id::serde_decimal::option")]
+///     invited_by: Option<HoraId>,
+/// }
+///
+/// let user = User { id: HoraId::from(57704410318438402), invited_by: None };
+/// let json = serde_json::to_string(&user).unwrap();
+/// assert_eq!(json, r#"{"id":"57704410318438402","invited_by":null}"#);
+/// ```
+pub mod decimal {
+    use super::*;
+
+    /// Serialize a [HoraId] as a decimal string, or a `u64` in binary formats
+    pub fn serialize<S: Serializer>(id: &HoraId, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&id.to_u64())
+        } else {
+            serializer.serialize_u64(id.to_u64())
+        }
+    }
+
+    /// Deserialize a [HoraId] from a decimal string or a number
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<HoraId, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(DecimalVisitor)
+        } else {
+            deserializer.deserialize_u64(DecimalVisitor)
+        }
+    }
+
+    struct DecimalVisitor;
+
+    impl Visitor<'_> for DecimalVisitor {
+        type Value = HoraId;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a decimal string or a u64")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            let num: u64 = v
+                .parse()
+                .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))?;
+            Ok(HoraId::from(num))
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            Ok(HoraId::from(v))
+        }
+    }
+
+    /// The same for `Option<HoraId>`, use `#[serde(with = "hora_id::serde_decimal::option")]`
+    pub mod option {
+        use super::*;
+
+        /// Serialize an optional [HoraId] as a decimal string or none
+        pub fn serialize<S: Serializer>(
+            id: &Option<HoraId>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match id {
+                Some(id) => serializer.serialize_some(&Decimal(*id)),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        /// Deserialize an optional [HoraId] from a decimal string, a number or none
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<HoraId>, D::Error> {
+            let id: Option<Decimal> = Deserialize::deserialize(deserializer)?;
+            Ok(id.map(|Decimal(id)| id))
+        }
+
+        /// Applies the decimal format to the value inside the option
+        struct Decimal(HoraId);
+
+        impl Serialize for Decimal {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                super::serialize(&self.0, serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for Decimal {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                super::deserialize(deserializer).map(Decimal)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{HoraId, HoraId128};
+    use serde_test::{assert_de_tokens, assert_tokens, Configure, Token};
+
+    #[test]
+    fn readable() {
+        let id = HoraId::from_u64(57704410318438402).unwrap();
+        assert_tokens(&id.readable(), &[Token::Str("00cd01daff010002")]);
+    }
+
+    #[test]
+    fn compact() {
+        let id = HoraId::from_u64(57704410318438402).unwrap();
+        assert_tokens(&id.compact(), &[Token::U64(57704410318438402)]);
+        assert_de_tokens(
+            &id.compact(),
+            &[Token::Bytes(&[
+                0x00, 0xcd, 0x01, 0xda, 0xff, 0x01, 0x00, 0x02,
+            ])],
+        );
+    }
+
+    #[test]
+    fn json() {
+        let id = HoraId::from_u64(57704410318438402).unwrap();
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, "\"00cd01daff010002\"");
+        let parsed: HoraId = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, id);
+
+        assert!(serde_json::from_str::<HoraId>("\"00cd01da\"").is_err());
+    }
+
+    #[test]
+    fn decimal() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Row {
+            #[serde(with = "super::decimal")]
+            id: HoraId,
+            #[serde(with = "super::decimal::option")]
+            parent: Option<HoraId>,
+        }
+
+        let id = HoraId::from_u64(57704410318438402).unwrap();
+        let row = Row {
+            id,
+            parent: Some(id),
+        };
+        let json = serde_json::to_string(&row).unwrap();
+        assert_eq!(
+            json,
+            r#"{"id":"57704410318438402","parent":"57704410318438402"}"#
+        );
+        assert_eq!(serde_json::from_str::<Row>(&json).unwrap(), row);
+
+        let numbers = r#"{"id":57704410318438402,"parent":null}"#;
+        let parsed = serde_json::from_str::<Row>(numbers).unwrap();
+        assert_eq!(parsed, Row { id, parent: None });
+        assert!(serde_json::from_str::<Row>(r#"{"id":"00cd01da","parent":null}"#).is_err());
+
+        assert_tokens(
+            &row.compact(),
+            &[
+                Token::Struct {
+                    name: "Row",
+                    len: 2,
+                },
+                Token::Str("id"),
+                Token::U64(57704410318438402),
+                Token::Str("parent"),
+                Token::Some,
+                Token::U64(57704410318438402),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn id128() {
+        let id = HoraId128::from(0x00cd_01da_ff01_0203_0405_0607_0809_0a0b);
+        assert_tokens(
+            &id.readable(),
+            &[Token::Str("00cd01daff0102030405060708090a0b")],
+        );
+        assert_tokens(
+            &id.compact(),
+            &[Token::Bytes(&[
+                0x00, 0xcd, 0x01, 0xda, 0xff, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09,
+                0x0a, 0x0b,
+            ])],
+        );
+
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(serde_json::from_str::<HoraId128>(&json).unwrap(), id);
+    }
+}
@@ -0,0 +1,232 @@
+use crate::{ticks_to_millis, unix_millis_to_ticks, HoraError, HoraId, HoraParams, EPOCH};
+
+/// Bit layout and epoch of Snowflake IDs, for converting them to and from [HoraId]s
+///
+/// A Snowflake is a 64-bit number made of a millisecond timestamp since a custom epoch, a worker
+/// ID and a per-millisecond sequence, from the most to the least significant bits. Services
+/// that split the worker ID further, such as Twitter's datacenter and worker or Discord's worker
+/// and process, are covered by treating both parts as one worker ID.
+///
+/// | Layout                      | Epoch      | Worker | Sequence |
+/// |-----------------------------|------------|--------|----------|
+/// | [SnowflakeLayout::TWITTER]  | 2010-11-04 | 10     | 12       |
+/// | [SnowflakeLayout::DISCORD]  | 2015-01-01 | 10     | 12       |
+///
+/// ## Mapping
+/// A [HoraId] counts 1/256 second ticks, so a tick holds up to 4 milliseconds. The worker ID
+/// becomes the machine ID and the HoraId sequence stores the millisecond within the tick above
+/// the Snowflake sequence. Converting keeps the order of the IDs and converts back exactly.
+/// IDs with a worker ID above 255 or a sequence wider than 14 bits can't be converted.
+///
+/// ## Usage
+/// ```
+/// use hora_id::{HoraId, SnowflakeLayout};
+///
+/// let snowflake = 2143981510456397829; // 2027-01-15, worker 37
+/// let id = HoraId::from_snowflake(snowflake, &SnowflakeLayout::TWITTER).unwrap();
+/// assert_eq!(id.machine_id(), 37);
+/// assert_eq!(id.to_snowflake(&SnowflakeLayout::TWITTER), Ok(snowflake));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SnowflakeLayout {
+    epoch: u64,
+    worker_bits: u8,
+    sequence_bits: u8,
+}
+
+impl SnowflakeLayout {
+    /// Twitter Snowflakes: epoch 1288834974657 ms, 10 bit worker ID, 12 bit sequence
+    pub const TWITTER: SnowflakeLayout = SnowflakeLayout::new(1288834974657, 10, 12);
+
+    /// Discord Snowflakes: epoch 1420070400000 ms, 10 bit worker and process ID, 12 bit increment
+    pub const DISCORD: SnowflakeLayout = SnowflakeLayout::new(1420070400000, 10, 12);
+
+    /// Create a custom layout with a millisecond timestamp since `epoch_millis` in the remaining
+    /// bits
+    ///
+    /// ## Panics
+    /// - If the sequence has no bits or more than 14 bits
+    /// - If the worker ID and sequence together leave less than 31 of the 63 bits for the
+    ///   timestamp
+    pub const fn new(epoch_millis: u64, worker_bits: u8, sequence_bits: u8) -> Self {
+        assert!(
+            sequence_bits > 0 && sequence_bits <= 14,
+            "sequence must have 1 to 14 bits"
+        );
+        assert!(
+            worker_bits as u32 + sequence_bits as u32 <= 63 - 31,
+            "timestamp must have at least 31 bits"
+        );
+        Self {
+            epoch: epoch_millis,
+            worker_bits,
+            sequence_bits,
+        }
+    }
+
+    /// Unix time in milliseconds the timestamps are counted from
+    pub const fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Timestamp of `snowflake` as milliseconds since the Unix epoch
+    pub const fn timestamp_millis(&self, snowflake: u64) -> u64 {
+        self.epoch + (snowflake >> (self.worker_bits + self.sequence_bits))
+    }
+
+    /// Worker ID of `snowflake`
+    pub const fn worker_id(&self, snowflake: u64) -> u64 {
+        (snowflake >> self.sequence_bits) & ((1 << self.worker_bits) - 1)
+    }
+
+    /// Sequence number of `snowflake`
+    pub const fn sequence(&self, snowflake: u64) -> u64 {
+        snowflake & ((1 << self.sequence_bits) - 1)
+    }
+}
+
+impl HoraId {
+    /// Convert a Snowflake to a [HoraId], see [SnowflakeLayout] for the mapping
+    ///
+    /// ## Fail condition
+    /// - If the timestamp is before the HoraID epoch (2025-01-01) or too far in the future
+    /// - If the worker ID is above 255
+    pub fn from_snowflake(snowflake: u64, layout: &SnowflakeLayout) -> Result<Self, HoraError> {
+        let millis = layout.timestamp_millis(snowflake);
+        let worker_id = layout.worker_id(snowflake);
+        if worker_id > u8::MAX as u64 {
+            return Err(HoraError::InvalidMachineId {
+                machine_id: worker_id,
+                max: u8::MAX as u64,
+            });
+        }
+        let ticks = unix_millis_to_ticks(millis)?;
+        let millis_in_tick = millis - EPOCH - ticks_to_millis(ticks);
+        Ok(HoraId::with_params(HoraParams {
+            machine_id: worker_id as u8,
+            ticks,
+            sequence: (millis_in_tick << layout.sequence_bits | layout.sequence(snowflake)) as u16,
+        }))
+    }
+
+    /// Convert the [HoraId] to a 63-bit Snowflake, see [SnowflakeLayout] for the mapping
+    ///
+    /// ## Fail condition
+    /// - If the ID is before the epoch of the layout or its timestamp doesn't fit in 63 bits
+    /// - If the machine ID doesn't fit in the worker bits
+    /// - If the sequence is too large for the Snowflake sequence and the milliseconds of its tick,
+    ///   which only happens for IDs that weren't converted from a Snowflake
+    pub fn to_snowflake(&self, layout: &SnowflakeLayout) -> Result<u64, HoraError> {
+        let shift = layout.worker_bits + layout.sequence_bits;
+        let max_worker = (1u64 << layout.worker_bits) - 1;
+        if self.machine_id() as u64 > max_worker {
+            return Err(HoraError::InvalidMachineId {
+                machine_id: self.machine_id() as u64,
+                max: max_worker,
+            });
+        }
+
+        let sequence = self.sequence() as u64;
+        let millis = self.timestamp_millis() + (sequence >> layout.sequence_bits);
+        if unix_millis_to_ticks(millis) != Ok(self.ticks()) {
+            // the millisecond would fall into the next tick and collide with its IDs
+            return Err(HoraError::IncompatibleId);
+        }
+        let since_epoch = millis
+            .checked_sub(layout.epoch)
+            .filter(|&millis| millis < 1 << (63 - shift))
+            .ok_or(HoraError::TimestampOutOfRange)?;
+        Ok(since_epoch << shift
+            | (self.machine_id() as u64) << layout.sequence_bits
+            | layout.sequence(sequence))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snowflake(layout: &SnowflakeLayout, millis: u64, worker: u64, sequence: u64) -> u64 {
+        (millis - layout.epoch) << (layout.worker_bits + layout.sequence_bits)
+            | worker << layout.sequence_bits
+            | sequence
+    }
+
+    #[test]
+    fn parts() {
+        let layout = SnowflakeLayout::DISCORD;
+        let id = snowflake(&layout, EPOCH + 1234, 1023, 4095);
+        assert_eq!(layout.timestamp_millis(id), EPOCH + 1234);
+        assert_eq!(layout.worker_id(id), 1023);
+        assert_eq!(layout.sequence(id), 4095);
+    }
+
+    #[test]
+    fn round_trip() {
+        let layout = SnowflakeLayout::TWITTER;
+        for millis in EPOCH..EPOCH + 20 {
+            for (worker, sequence) in [(0, 0), (255, 4095), (7, 1)] {
+                let original = snowflake(&layout, millis, worker, sequence);
+                let id = HoraId::from_snowflake(original, &layout).unwrap();
+                assert_eq!(id.machine_id() as u64, worker);
+                assert_eq!(id.to_snowflake(&layout), Ok(original));
+            }
+        }
+    }
+
+    #[test]
+    fn sort_order() {
+        let layout = SnowflakeLayout::TWITTER;
+        let mut last = HoraId::from(0);
+        for millis in EPOCH..EPOCH + 10 {
+            for sequence in [0, 1, 4095] {
+                let id = HoraId::from_snowflake(snowflake(&layout, millis, 3, sequence), &layout)
+                    .unwrap();
+                assert!(id > last);
+                last = id;
+            }
+        }
+    }
+
+    #[test]
+    fn incompatible() {
+        let layout = SnowflakeLayout::TWITTER;
+        let wide_worker = snowflake(&layout, EPOCH, 256, 0);
+        assert_eq!(
+            HoraId::from_snowflake(wide_worker, &layout),
+            Err(HoraError::InvalidMachineId {
+                machine_id: 256,
+                max: 255
+            })
+        );
+        let old = snowflake(&layout, EPOCH - 1, 1, 0);
+        assert_eq!(
+            HoraId::from_snowflake(old, &layout),
+            Err(HoraError::TimestampOutOfRange)
+        );
+
+        let id: HoraId = "00cd01daff010002".parse().unwrap();
+        let narrow = SnowflakeLayout::new(EPOCH, 4, 12);
+        assert!(matches!(
+            HoraId::from(id.to_u64() | 0xff << 16).to_snowflake(&narrow),
+            Err(HoraError::InvalidMachineId { max: 15, .. })
+        ));
+        // sequence 0xffff needs 15 milliseconds within a 4 millisecond tick
+        let busy = HoraId::from(id.to_u64() | 0xffff);
+        assert_eq!(busy.to_snowflake(&layout), Err(HoraError::IncompatibleId));
+        let later_epoch = SnowflakeLayout::new(id.timestamp_millis() + 1, 10, 12);
+        assert_eq!(
+            id.to_snowflake(&later_epoch),
+            Err(HoraError::TimestampOutOfRange)
+        );
+        // the shortest timestamp, 31 bits, only covers 24 days since the Unix epoch
+        let short = SnowflakeLayout::new(0, 20, 12);
+        assert_eq!(id.to_snowflake(&short), Err(HoraError::TimestampOutOfRange));
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp must have at least 31 bits")]
+    fn short_timestamp() {
+        SnowflakeLayout::new(0, 21, 12);
+    }
+}
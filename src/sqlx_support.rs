@@ -0,0 +1,101 @@
+//! [sqlx] support for [HoraId]
+//!
+//! Stored in a `BIGINT` column as [HoraId::to_u64], read from `BIGINT` or an 8 byte binary column.
+
+use crate::HoraId;
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::{Database, Decode, Encode, Type, ValueRef};
+
+impl<DB: Database> Type<DB> for HoraId
+where
+    i64: Type<DB>,
+    for<'a> &'a [u8]: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <i64 as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <i64 as Type<DB>>::compatible(ty) || <&[u8] as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for HoraId
+where
+    i64: Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        (self.to_u64() as i64).encode_by_ref(buf)
+    }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for HoraId
+where
+    i64: Decode<'r, DB> + Type<DB>,
+    &'r [u8]: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        if <i64 as Type<DB>>::compatible(&value.type_info()) {
+            let num = <i64 as Decode<DB>>::decode(value)?;
+            Ok(HoraId::from(num as u64))
+        } else {
+            let bytes = <&[u8] as Decode<DB>>::decode(value)?;
+            Ok(HoraId::try_from(bytes)?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::{Connection, Row, SqliteConnection};
+
+    #[tokio::test]
+    async fn sqlite_round_trip() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE TABLE items (id INTEGER PRIMARY KEY, raw BLOB)")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let id: HoraId = "00cd01daff010002".parse().unwrap();
+        let later = HoraId::from(id.to_u64() + 1);
+        for item in [later, id] {
+            sqlx::query("INSERT INTO items (id, raw) VALUES (?, ?)")
+                .bind(item)
+                .bind(item.as_bytes())
+                .execute(&mut conn)
+                .await
+                .unwrap();
+        }
+
+        let row = sqlx::query("SELECT id, raw FROM items ORDER BY id")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(row.get::<HoraId, _>("id"), id);
+        assert_eq!(row.get::<HoraId, _>("raw"), id);
+        assert_eq!(row.get::<i64, _>("id"), 57704410318438402);
+
+        let found: (HoraId,) = sqlx::query_as("SELECT id FROM items WHERE id = ?")
+            .bind(later)
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(found.0, later);
+    }
+
+    #[tokio::test]
+    async fn sqlite_invalid_blob() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        let row = sqlx::query("SELECT x'0102' AS raw")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert!(row.try_get::<HoraId, _>("raw").is_err());
+    }
+}
@@ -0,0 +1,273 @@
+//! Saving the position of a [HoraGenerator] so that it continues after a restart
+
+use crate::{Clock, HoraError, HoraGenerator, WaitStrategy};
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+const MAGIC: [u8; 4] = *b"HORA";
+const VERSION: u8 = 1;
+
+/// The last timestamp and sequence number a [HoraGenerator] handed out
+///
+/// A restarted process starts with the current time of its clock. If the clock stepped back
+/// before the restart, the new generator could repeat IDs it already produced. Saving the state
+/// and restoring it with [HoraGenerator::with_state] makes the generator continue after the
+/// saved ID instead, following its [RollbackPolicy](crate::RollbackPolicy) until the clock
+/// catches up.
+///
+/// Only IDs generated before the last save are protected, so save on shutdown and periodically.
+///
+/// ## Usage
+/// ```no_run
+/// use hora_id::{GeneratorState, HoraGenerator};
+///
+/// let path = "/var/lib/app/hora.state";
+/// let mut generator = HoraGenerator::new(1).unwrap();
+/// if let Some(state) = GeneratorState::load_from_file(path).unwrap() {
+///     generator = generator.with_state(state).unwrap();
+/// }
+///
+/// let id = generator.next();
+///
+/// generator.state().save_to_file(path).unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GeneratorState {
+    epoch: u64,
+    ticks: u64,
+    sequence: u32,
+}
+
+impl GeneratorState {
+    /// Number of bytes in the saved form
+    pub const LEN: usize = 25;
+
+    /// Unix time in milliseconds the timestamp is counted from
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Unix time in milliseconds of the last generated ID
+    pub fn timestamp_millis(&self) -> u64 {
+        self.epoch + crate::ticks_to_millis(self.ticks)
+    }
+
+    /// Sequence number of the last generated ID
+    pub fn sequence(&self) -> u32 {
+        self.sequence
+    }
+
+    /// Encode the state as a versioned, fixed-size byte array
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0; Self::LEN];
+        bytes[..4].copy_from_slice(&MAGIC);
+        bytes[4] = VERSION;
+        bytes[5..13].copy_from_slice(&self.epoch.to_be_bytes());
+        bytes[13..21].copy_from_slice(&self.ticks.to_be_bytes());
+        bytes[21..].copy_from_slice(&self.sequence.to_be_bytes());
+        bytes
+    }
+
+    /// Decode a state encoded with [GeneratorState::to_bytes]
+    ///
+    /// ## Fail condition
+    /// If the bytes don't start with the expected header or have the wrong length
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, HoraError> {
+        if bytes.len() != Self::LEN || bytes[..4] != MAGIC || bytes[4] != VERSION {
+            return Err(HoraError::InvalidState);
+        }
+        let mut epoch = [0; 8];
+        epoch.copy_from_slice(&bytes[5..13]);
+        let mut ticks = [0; 8];
+        ticks.copy_from_slice(&bytes[13..21]);
+        let mut sequence = [0; 4];
+        sequence.copy_from_slice(&bytes[21..]);
+        Ok(Self {
+            epoch: u64::from_be_bytes(epoch),
+            ticks: u64::from_be_bytes(ticks),
+            sequence: u32::from_be_bytes(sequence),
+        })
+    }
+
+    /// Write the state to `writer`
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn save<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+
+    /// Read a state written by [GeneratorState::save] from `reader`
+    ///
+    /// ## Fail condition
+    /// If reading fails, or with [io::ErrorKind::InvalidData] if the data is not a valid state
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn load<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut bytes = [0; Self::LEN];
+        reader.read_exact(&mut bytes)?;
+        Self::from_bytes(&bytes).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Save the state to the file at `path`
+    ///
+    /// The state is written to a temporary file next to `path` first and then renamed, so a crash
+    /// while saving leaves the previous state in place.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let mut file = std::fs::File::create(&tmp)?;
+        self.save(&mut file)?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, path)
+    }
+
+    /// Load the state saved to the file at `path`, or `None` if the file doesn't exist
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn load_from_file(path: impl AsRef<Path>) -> io::Result<Option<Self>> {
+        match std::fs::File::open(path) {
+            Ok(file) => Self::load(file).map(Some),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl<C: Clock, W: WaitStrategy> HoraGenerator<C, W> {
+    /// The position of the generator, to be restored with [HoraGenerator::with_state]
+    pub fn state(&self) -> GeneratorState {
+        GeneratorState {
+            epoch: self.epoch,
+            ticks: self.last_gen,
+            sequence: self.sequence,
+        }
+    }
+
+    /// Continue after a saved [GeneratorState]
+    ///
+    /// A state that is older than the position of the generator is ignored, so restoring never
+    /// moves the generator backwards.
+    ///
+    /// ## Fail condition
+    /// If the state was saved by a generator with a different epoch
+    pub fn with_state(mut self, state: GeneratorState) -> Result<Self, HoraError> {
+        if state.epoch != self.epoch {
+            return Err(HoraError::InvalidState);
+        }
+        if (state.ticks, state.sequence) > (self.last_gen, self.sequence) {
+            self.last_gen = state.ticks;
+            // a narrower layout continues from its last sequence number and then waits
+            self.sequence = state.sequence.min(self.layout.max_sequence());
+            // random sequence numbers used before the restart are unknown, skip the tick
+            #[cfg(feature = "std")]
+            if self.random_sequence.is_some() {
+                self.sequence = self.layout.max_sequence();
+            }
+        }
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MockClock, EPOCH};
+
+    #[test]
+    fn bytes_round_trip() {
+        let state = GeneratorState {
+            epoch: EPOCH,
+            ticks: 1000,
+            sequence: 7,
+        };
+        let bytes = state.to_bytes();
+        assert_eq!(&bytes[..5], b"HORA\x01");
+        assert_eq!(GeneratorState::from_bytes(&bytes), Ok(state));
+
+        assert_eq!(
+            GeneratorState::from_bytes(&bytes[..24]),
+            Err(HoraError::InvalidState)
+        );
+        let mut corrupt = bytes;
+        corrupt[0] = b'X';
+        assert_eq!(
+            GeneratorState::from_bytes(&corrupt),
+            Err(HoraError::InvalidState)
+        );
+    }
+
+    #[test]
+    fn continues_after_restart() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let mut generator = HoraGenerator::new_with_clock(1, clock.clone()).unwrap();
+        let last = generator.next();
+        let mut saved = Vec::new();
+        generator.state().save(&mut saved).unwrap();
+
+        // the process restarts after the clock stepped back
+        clock.rewind(2_000);
+        let state = GeneratorState::load(saved.as_slice()).unwrap();
+        assert_eq!(state.timestamp_millis(), last.timestamp_millis());
+        let mut restarted = HoraGenerator::new_with_clock(1, clock.clone())
+            .unwrap()
+            .with_state(state)
+            .unwrap();
+        let next = restarted.next();
+        assert!(next > last);
+        assert_eq!(next.sequence(), last.sequence() + 1);
+
+        // with random sequence numbers, the rest of the restored tick is skipped
+        clock.advance(2_000);
+        let mut random = HoraGenerator::new_with_clock(1, clock.clone())
+            .unwrap()
+            .with_wait_strategy(crate::Fail)
+            .random_sequence()
+            .with_state(state)
+            .unwrap();
+        assert_eq!(random.try_next(), Err(HoraError::SequenceExhausted));
+    }
+
+    #[test]
+    fn older_state_is_ignored() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let mut old = HoraGenerator::new_with_clock(1, clock.clone()).unwrap();
+        old.next_n(3);
+        let state = old.state();
+
+        clock.advance(5_000);
+        let generator = HoraGenerator::new_with_clock(1, clock.clone()).unwrap();
+        let before = generator.state();
+        let mut generator = generator.with_state(state).unwrap();
+        assert_eq!(generator.state(), before);
+        assert!(generator.next().timestamp_millis() >= EPOCH + 15_000);
+
+        let other_epoch = generator.with_epoch(EPOCH + 1_000).unwrap();
+        assert!(matches!(
+            other_epoch.with_state(state),
+            Err(HoraError::InvalidState)
+        ));
+    }
+
+    #[test]
+    fn file() {
+        let path = std::env::temp_dir().join(format!("hora_id_state_{}", std::process::id()));
+        assert_eq!(GeneratorState::load_from_file(&path).unwrap(), None);
+
+        let generator = HoraGenerator::new(1).unwrap();
+        generator.state().save_to_file(&path).unwrap();
+        assert_eq!(
+            GeneratorState::load_from_file(&path).unwrap(),
+            Some(generator.state())
+        );
+
+        std::fs::write(&path, b"not a state").unwrap();
+        let err = GeneratorState::load_from_file(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
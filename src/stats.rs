@@ -0,0 +1,124 @@
+//! Counters of a [HoraGenerator] for monitoring
+
+use crate::{Clock, HoraGenerator, WaitStrategy};
+
+/// Snapshot of the counters of a [HoraGenerator]
+///
+/// The counters start at zero when the generator is created and only grow, so export them as
+/// counters to your metrics system and alert on their rate. A [GeneratorStats::max_sequence]
+/// close to [HoraLayout::max_sequence](crate::HoraLayout::max_sequence) or a rising number of
+/// [GeneratorStats::exhaustion_waits] means the generator is close to its throughput limit.
+///
+/// ## Usage
+/// ```
+/// use hora_id::HoraGenerator;
+///
+/// let mut generator = HoraGenerator::new(1).unwrap();
+/// generator.next();
+/// generator.next();
+///
+/// let stats = generator.stats();
+/// assert_eq!(stats.generated(), 2);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GeneratorStats {
+    generated: u64,
+    max_sequence: u32,
+    exhaustion_waits: u64,
+    rollbacks: u64,
+}
+
+impl GeneratorStats {
+    /// Number of IDs generated
+    pub fn generated(&self) -> u64 {
+        self.generated
+    }
+
+    /// Highest sequence number reached in a tick, one less than the most IDs generated in a tick
+    pub fn max_sequence(&self) -> u32 {
+        self.max_sequence
+    }
+
+    /// Number of ticks in which all sequence numbers were used and the generator had to wait
+    pub fn exhaustion_waits(&self) -> u64 {
+        self.exhaustion_waits
+    }
+
+    /// Number of times the clock was found behind the last generated ID
+    ///
+    /// A rollback is counted once, no matter how many IDs are requested until the clock has
+    /// caught up.
+    pub fn rollbacks(&self) -> u64 {
+        self.rollbacks
+    }
+
+    pub(crate) fn record_id(&mut self, sequence: u32) {
+        self.record_ids(1, sequence);
+    }
+
+    /// Count `count` IDs ending at `sequence`, for a block reserved at once
+    pub(crate) fn record_ids(&mut self, count: u64, sequence: u32) {
+        self.generated += count;
+        self.max_sequence = self.max_sequence.max(sequence);
+    }
+
+    /// Add the counters of `other`, for generators that share a machine ID
+    #[cfg(feature = "std")]
+    pub(crate) fn merge(&mut self, other: &GeneratorStats) {
+        self.generated += other.generated;
+        self.max_sequence = self.max_sequence.max(other.max_sequence);
+        self.exhaustion_waits += other.exhaustion_waits;
+        self.rollbacks += other.rollbacks;
+    }
+
+    pub(crate) fn record_exhaustion(&mut self) {
+        self.exhaustion_waits += 1;
+    }
+
+    pub(crate) fn record_rollback(&mut self) {
+        self.rollbacks += 1;
+    }
+}
+
+impl<C: Clock, W: WaitStrategy> HoraGenerator<C, W> {
+    /// Counters of the IDs generated so far, see [GeneratorStats]
+    pub fn stats(&self) -> GeneratorStats {
+        self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Fail, HoraError, HoraGenerator, HoraLayout, MockClock, RollbackPolicy, EPOCH};
+
+    #[test]
+    fn counters() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let layout = HoraLayout::new(48, 8, 8);
+        let mut generator = HoraGenerator::with_layout_and_clock(layout, 1, clock.clone())
+            .unwrap()
+            .with_wait_strategy(Fail)
+            .with_rollback_policy(RollbackPolicy::Error);
+        clock.advance(4);
+        generator.next_n(256);
+        let stats = generator.stats();
+        assert_eq!(stats.generated(), 256);
+        assert_eq!(stats.max_sequence(), 255);
+        assert_eq!(stats.exhaustion_waits(), 0);
+
+        assert_eq!(generator.try_next(), Err(HoraError::SequenceExhausted));
+        assert_eq!(generator.try_next(), Err(HoraError::SequenceExhausted));
+        assert_eq!(generator.stats().exhaustion_waits(), 1);
+        clock.advance(4);
+        generator.next();
+
+        clock.rewind(1000);
+        assert!(generator.try_next().is_err());
+        assert!(generator.try_next().is_err());
+        clock.advance(2000);
+        generator.next();
+        let stats = generator.stats();
+        assert_eq!(stats.rollbacks(), 1);
+        assert_eq!(stats.generated(), 258);
+    }
+}
@@ -0,0 +1,134 @@
+//! [futures_core::Stream] of [HoraId]s
+
+use crate::{Clock, HoraError, HoraGenerator, HoraId, Spin, SystemClock, WaitStrategy};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+use futures_core::Stream;
+use futures_timer::Delay;
+
+/// An endless [Stream] of [HoraId]s for async pipelines
+///
+/// IDs are generated as fast as they are polled. When all sequence numbers of the current tick
+/// are used, the stream sleeps until the next tick instead of busy-looping, so other tasks on the
+/// executor keep running. The timer is runtime-agnostic and works with any executor. The
+/// [WaitStrategy] of the wrapped generator is not used.
+///
+/// The stream yields `Err` under the same conditions as [HoraGenerator::try_next], and continues
+/// afterwards.
+///
+/// ## Usage
+/// ```no_run
+/// use futures::StreamExt;
+/// use hora_id::{HoraGenerator, HoraStream};
+///
+/// # futures::executor::block_on(async {
+/// let stream = HoraStream::new(HoraGenerator::new(1).unwrap());
+/// let ids: Vec<_> = stream.take(1000).collect().await;
+/// # });
+/// ```
+pub struct HoraStream<C = SystemClock, W = Spin> {
+    generator: HoraGenerator<C, W>,
+    /// Pending wait for the next tick
+    delay: Option<Delay>,
+}
+
+impl<C: Clock, W: WaitStrategy> HoraStream<C, W> {
+    /// Create a stream that yields IDs from `generator`
+    pub fn new(generator: HoraGenerator<C, W>) -> Self {
+        Self {
+            generator,
+            delay: None,
+        }
+    }
+
+    /// Get the generator back, to continue generating IDs synchronously
+    pub fn into_inner(self) -> HoraGenerator<C, W> {
+        self.generator
+    }
+}
+
+impl<C: Clock, W: WaitStrategy> From<HoraGenerator<C, W>> for HoraStream<C, W> {
+    fn from(generator: HoraGenerator<C, W>) -> Self {
+        Self::new(generator)
+    }
+}
+
+impl<C: Clock + Unpin, W: WaitStrategy + Unpin> Stream for HoraStream<C, W> {
+    type Item = Result<HoraId, HoraError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(delay) = &mut this.delay {
+                ready!(Pin::new(delay).poll(cx));
+                this.delay = None;
+            }
+            match this.generator.poll_next_id() {
+                Ok(Some(id)) => return Poll::Ready(Some(Ok(id))),
+                Ok(None) => this.delay = Some(Delay::new(this.generator.until_next_tick())),
+                Err(err) => return Poll::Ready(Some(Err(err))),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HoraLayout, MockClock, RollbackPolicy, EPOCH};
+    use futures::executor::block_on;
+    use futures::StreamExt;
+    use std::time::Duration;
+
+    #[test]
+    fn yields_sorted_ids() {
+        let stream = HoraStream::new(HoraGenerator::new(1).unwrap());
+        // spans several ticks of the default layout
+        let ids: Vec<_> = block_on(stream.take(200_000).collect());
+        let ids: Vec<HoraId> = ids.into_iter().map(Result::unwrap).collect();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn waits_for_next_tick() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let layout = HoraLayout::new(47, 16, 1);
+        let generator = HoraGenerator::with_layout_and_clock(layout, 1, clock.clone()).unwrap();
+        let mut stream = HoraStream::new(generator);
+
+        // the constructor already used sequence 0, so only one ID is left in this tick
+        let id = block_on(stream.next()).unwrap().unwrap();
+        assert_eq!(layout.sequence(&id), 1);
+
+        let advance = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            clock.advance(4);
+        });
+        let next = block_on(stream.next()).unwrap().unwrap();
+        advance.join().unwrap();
+        assert_eq!(layout.sequence(&next), 0);
+        assert!(next > id);
+    }
+
+    #[test]
+    fn yields_errors() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let generator = HoraGenerator::new_with_clock(1, clock.clone())
+            .unwrap()
+            .with_rollback_policy(RollbackPolicy::Error);
+        let mut stream = HoraStream::from(generator);
+        assert!(block_on(stream.next()).unwrap().is_ok());
+        clock.rewind(1000);
+        assert!(matches!(
+            block_on(stream.next()),
+            Some(Err(HoraError::ClockMovedBackwards { .. }))
+        ));
+        clock.advance(1000);
+        assert!(block_on(stream.next()).unwrap().is_ok());
+    }
+}
@@ -0,0 +1,194 @@
+use crate::{
+    Clock, GeneratorStats, HoraError, HoraGenerator, HoraId, HoraIdRange, Spin, SystemClock,
+    WaitStrategy,
+};
+#[cfg(hora_loom)]
+use loom::sync::Mutex;
+#[cfg(not(hora_loom))]
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+/// Thread-safe ID generator that can be shared across threads
+///
+/// Wraps a [HoraGenerator] in a [Mutex] so that IDs can be generated from a shared reference.
+/// Put it in an [Arc](std::sync::Arc) to share it between threads or async tasks.
+///
+/// ## Usage
+/// ```no_run
+/// use hora_id::SyncHoraGenerator;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let generator = Arc::new(SyncHoraGenerator::new(1).unwrap());
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| {
+///         let generator = Arc::clone(&generator);
+///         thread::spawn(move || generator.next())
+///     })
+///     .collect();
+/// for handle in handles {
+///     println!("{}", handle.join().unwrap());
+/// }
+/// ```
+pub struct SyncHoraGenerator<C = SystemClock, W = Spin> {
+    inner: Mutex<HoraGenerator<C, W>>,
+}
+
+impl SyncHoraGenerator {
+    /// Create a generator, failing only with [HoraError::ClockBeforeEpoch] as any `u8` is valid
+    pub fn new(machine_id: u8) -> Result<Self, HoraError> {
+        let generator = HoraGenerator::new(machine_id)?;
+        Ok(Self::from(generator))
+    }
+}
+
+impl<C: Clock, W: WaitStrategy> SyncHoraGenerator<C, W> {
+    /// Generate a new [HoraId]
+    ///
+    /// ## Panics
+    /// If the system time is incorrect. Use [SyncHoraGenerator::try_next] to handle this case.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&self) -> HoraId {
+        self.try_next().expect("failed to generate HoraId")
+    }
+
+    /// Generate a new [HoraId], returning an error instead of panicking
+    pub fn try_next(&self) -> Result<HoraId, HoraError> {
+        // the generator state is always consistent, so a poisoned lock is safe to reuse
+        let mut generator = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        generator.try_next()
+    }
+
+    /// Reserve a block of up to `size` consecutive IDs, see [HoraGenerator::try_next_block]
+    pub fn try_next_block(&self, size: u32) -> Result<HoraIdRange, HoraError> {
+        let mut generator = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        generator.try_next_block(size)
+    }
+
+    /// Epoch the timestamps count from, see [HoraGenerator::epoch]
+    pub fn epoch(&self) -> u64 {
+        self.inner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .epoch()
+    }
+
+    /// Counters of the IDs generated so far, see [HoraGenerator::stats]
+    pub fn stats(&self) -> GeneratorStats {
+        self.inner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .stats()
+    }
+}
+
+impl<C, W> From<HoraGenerator<C, W>> for SyncHoraGenerator<C, W> {
+    fn from(generator: HoraGenerator<C, W>) -> Self {
+        Self {
+            inner: Mutex::new(generator),
+        }
+    }
+}
+
+#[cfg(all(test, not(hora_loom)))]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<SyncHoraGenerator>();
+    }
+
+    #[test]
+    fn shared_across_threads() {
+        let generator = Arc::new(SyncHoraGenerator::new(1).unwrap());
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let generator = Arc::clone(&generator);
+                thread::spawn(move || (0..1000).map(|_| generator.next()).collect::<Vec<_>>())
+            })
+            .collect();
+
+        let mut unique = HashSet::new();
+        for handle in handles {
+            for id in handle.join().unwrap() {
+                assert!(unique.insert(id));
+            }
+        }
+        assert_eq!(unique.len(), 4000);
+    }
+}
+
+/// Model checks of every thread interleaving, run with
+/// `RUSTFLAGS="--cfg hora_loom" cargo test --release --lib loom_tests`
+#[cfg(all(test, hora_loom))]
+mod loom_tests {
+    use super::*;
+    use crate::{MockClock, EPOCH};
+    use loom::sync::Arc;
+    use loom::thread;
+
+    fn unique(ids: Vec<HoraId>) {
+        for (index, id) in ids.iter().enumerate() {
+            assert!(!ids[index + 1..].contains(id), "duplicate ID {}", id);
+        }
+    }
+
+    #[test]
+    fn no_duplicates() {
+        loom::model(|| {
+            let clock = MockClock::new(EPOCH + 1000);
+            let generator = Arc::new(SyncHoraGenerator::from(
+                HoraGenerator::new_with_clock(1, clock).unwrap(),
+            ));
+            let handles: Vec<_> = (0..2)
+                .map(|_| {
+                    let generator = Arc::clone(&generator);
+                    thread::spawn(move || {
+                        let first = generator.next();
+                        let second = generator.next();
+                        assert!(first < second);
+                        [first, second]
+                    })
+                })
+                .collect();
+
+            let mut ids = vec![generator.next()];
+            for handle in handles {
+                ids.extend(handle.join().unwrap());
+            }
+            unique(ids);
+        });
+    }
+
+    #[test]
+    fn no_duplicates_while_clock_moves() {
+        loom::model(|| {
+            let clock = MockClock::new(EPOCH + 1000);
+            let generator = Arc::new(SyncHoraGenerator::from(
+                HoraGenerator::new_with_clock(1, clock.clone()).unwrap(),
+            ));
+            let other = {
+                let generator = Arc::clone(&generator);
+                thread::spawn(move || [generator.next(), generator.next()])
+            };
+
+            let first = generator.next();
+            // a step backwards must not make the generator reuse a (timestamp, sequence) pair
+            clock.advance(10);
+            let second = generator.next();
+            clock.rewind(20);
+            let third = generator.next();
+            assert!(first < second && second < third);
+
+            let mut ids = vec![first, second, third];
+            ids.extend(other.join().unwrap());
+            unique(ids);
+        });
+    }
+}
@@ -0,0 +1,307 @@
+//! [thread_local] support for generators without synchronization between threads
+
+use crate::pool::partition_bits;
+use crate::{Clock, HoraError, HoraGenerator, HoraId, Spin, SystemClock, WaitStrategy};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, Weak};
+use thread_local::ThreadLocal;
+
+/// Generator that gives every thread its own [HoraGenerator]
+///
+/// Each thread that generates an ID gets a generator with a slice of the sequence space, as in
+/// [HoraGeneratorPool](crate::HoraGeneratorPool), the first time it calls
+/// [ThreadLocalHoraGenerator::next]. After that it generates IDs without waiting for other
+/// threads. When a thread exits, its generator goes back to the generator and the next thread
+/// that needs one continues with it, so `threads` only has to cover the threads running at the
+/// same time.
+///
+/// IDs of one thread are sorted. IDs of different threads in the same tick are not.
+///
+/// ## Usage
+/// ```no_run
+/// use hora_id::HoraGenerator;
+/// use std::thread;
+///
+/// let generator = HoraGenerator::new(1).unwrap().thread_local(16).unwrap();
+///
+/// thread::scope(|scope| {
+///     for _ in 0..16 {
+///         scope.spawn(|| {
+///             for _ in 0..1000 {
+///                 println!("{}", generator.next());
+///             }
+///         });
+///     }
+/// });
+/// ```
+pub struct ThreadLocalHoraGenerator<C = SystemClock, W = Spin>
+where
+    C: Send,
+    W: Send,
+{
+    /// Configuration the generators of the threads are made from
+    template: HoraGenerator<C, W>,
+    /// Bits of the sequence taken by the thread index
+    bits: u8,
+    threads: usize,
+    /// Number of generators created from the template
+    claimed: AtomicUsize,
+    /// Generators of threads that exited
+    released: Arc<Mutex<Vec<HoraGenerator<C, W>>>>,
+    local: ThreadLocal<Slot<C, W>>,
+}
+
+/// Generator of one thread, shared with the [Release] guard of the thread
+type Slot<C, W> = Arc<Mutex<Option<HoraGenerator<C, W>>>>;
+
+std::thread_local! {
+    /// Guards that hand the generators of the current thread back when it exits
+    static RELEASE_ON_EXIT: RefCell<Vec<Box<dyn Releasable>>> = const { RefCell::new(Vec::new()) };
+}
+
+trait Releasable {
+    /// Whether the [ThreadLocalHoraGenerator] the guard belongs to still exists
+    fn is_live(&self) -> bool;
+}
+
+/// Moves the generator of a thread to the released generators when the thread exits
+///
+/// The slot of the thread in the [ThreadLocal] is left empty, since the `thread_local` crate
+/// gives the slot to whichever thread gets the ID of the exited thread next.
+struct Release<C, W> {
+    slot: Slot<C, W>,
+    released: Weak<Mutex<Vec<HoraGenerator<C, W>>>>,
+}
+
+impl<C, W> Releasable for Release<C, W> {
+    fn is_live(&self) -> bool {
+        self.released.strong_count() > 0
+    }
+}
+
+impl<C, W> Drop for Release<C, W> {
+    fn drop(&mut self) {
+        if let Some(released) = self.released.upgrade() {
+            if let Some(generator) = lock(&self.slot).take() {
+                lock(&released).push(generator);
+            }
+        }
+    }
+}
+
+// the generator state is always consistent, so a poisoned lock is safe to reuse
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl ThreadLocalHoraGenerator {
+    /// Create a generator for up to `threads` threads with the machine ID `machine_id`
+    ///
+    /// See [HoraGenerator::thread_local].
+    pub fn new(machine_id: u8, threads: usize) -> Result<Self, HoraError> {
+        HoraGenerator::new(machine_id)?.thread_local(threads)
+    }
+}
+
+impl<C: Clock + Clone + Send, W: WaitStrategy + Clone + Send> HoraGenerator<C, W> {
+    /// Split the generator between up to `threads` threads, see [ThreadLocalHoraGenerator]
+    ///
+    /// The layout, tenant, epoch and policies of the generator apply to the generators of all
+    /// threads.
+    ///
+    /// ## Fail condition
+    /// If `threads` is zero, needs as many bits as the sequence has or doesn't fit in 16 bits
+    /// together with the tenant ([HoraError::InvalidShardCount])
+    pub fn thread_local(self, threads: usize) -> Result<ThreadLocalHoraGenerator<C, W>, HoraError> {
+        let bits = partition_bits(&self.layout, threads)?;
+        Ok(ThreadLocalHoraGenerator {
+            template: self,
+            bits,
+            threads,
+            claimed: AtomicUsize::new(0),
+            released: Arc::default(),
+            local: ThreadLocal::new(),
+        })
+    }
+}
+
+impl<C, W> ThreadLocalHoraGenerator<C, W>
+where
+    C: Clock + Clone + Send + 'static,
+    W: WaitStrategy + Clone + Send + 'static,
+{
+    /// Generate a new [HoraId] with the generator of the current thread
+    ///
+    /// ## Panics
+    /// If the system time is incorrect or more threads than the generator was created for use
+    /// it at the same time. Use [ThreadLocalHoraGenerator::try_next] to handle these cases.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&self) -> HoraId {
+        self.try_next().expect("failed to generate HoraId")
+    }
+
+    /// Generate a new [HoraId], returning an error instead of panicking
+    ///
+    /// ## Fail condition
+    /// - If the generator of the thread fails, see [HoraGenerator::try_next]
+    /// - If this is a new thread and all generators are taken by other threads
+    ///   ([HoraError::PartitionsExhausted])
+    pub fn try_next(&self) -> Result<HoraId, HoraError> {
+        let slot = self.local.get_or(Slot::default);
+        // only this thread and its guard on exit use the slot, so the lock is never contended
+        let mut generator = lock(slot);
+        if generator.is_none() {
+            *generator = Some(self.claim()?);
+            self.release_on_exit(slot);
+        }
+        generator
+            .as_mut()
+            .expect("generator was just claimed")
+            .try_next()
+    }
+
+    /// Most threads that can generate IDs at the same time
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    fn claim(&self) -> Result<HoraGenerator<C, W>, HoraError> {
+        if let Some(generator) = lock(&self.released).pop() {
+            return Ok(generator);
+        }
+        let exhausted = HoraError::PartitionsExhausted {
+            partitions: self.threads as u64,
+        };
+        let index = self
+            .claimed
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |claimed| {
+                (claimed < self.threads).then_some(claimed + 1)
+            })
+            .map_err(|_| exhausted.clone())?;
+        let index = u16::try_from(index).map_err(|_| exhausted)?;
+        Ok(self.template.sequence_partition(self.bits, index))
+    }
+
+    fn release_on_exit(&self, slot: &Slot<C, W>) {
+        let guard = Release {
+            slot: Arc::clone(slot),
+            released: Arc::downgrade(&self.released),
+        };
+        // fails only while the thread exits, the generator of the thread is then not reused
+        let _ = RELEASE_ON_EXIT.try_with(|guards| {
+            let mut guards = guards.borrow_mut();
+            guards.retain(|guard| guard.is_live());
+            guards.push(Box::new(guard));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MockClock, EPOCH};
+    use std::collections::HashSet;
+    use std::sync::Barrier;
+    use std::thread;
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ThreadLocalHoraGenerator>();
+    }
+
+    #[test]
+    fn thread_slices() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let generator = HoraGenerator::new_with_clock(1, clock)
+            .unwrap()
+            .thread_local(4)
+            .unwrap();
+        assert_eq!(generator.threads(), 4);
+
+        let mut slices = HashSet::new();
+        // threads that run at the same time get different slices
+        let barrier = Barrier::new(4);
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let ids = [generator.next(), generator.next()];
+                        barrier.wait();
+                        ids
+                    })
+                })
+                .collect();
+            for handle in handles {
+                let [first, second] = handle.join().unwrap();
+                // consecutive in the slice of the thread
+                assert_eq!(first.to_u64() + 1, second.to_u64());
+                assert!(slices.insert(first.sequence() >> 14));
+            }
+        });
+        assert_eq!(slices.len(), 4);
+    }
+
+    #[test]
+    fn unique_across_threads() {
+        let generator = ThreadLocalHoraGenerator::new(1, 8).unwrap();
+        let mut unique = HashSet::new();
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| scope.spawn(|| (0..1000).map(|_| generator.next()).collect::<Vec<_>>()))
+                .collect();
+            for handle in handles {
+                let ids = handle.join().unwrap();
+                assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+                unique.extend(ids);
+            }
+        });
+        assert_eq!(unique.len(), 8000);
+    }
+
+    #[test]
+    fn too_many_threads() {
+        let generator = ThreadLocalHoraGenerator::new(1, 1).unwrap();
+        let barrier = Barrier::new(2);
+        let results: Vec<_> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..2)
+                .map(|_| {
+                    scope.spawn(|| {
+                        // both threads run at the same time, so they can't share a generator
+                        barrier.wait();
+                        let result = generator.try_next();
+                        barrier.wait();
+                        result
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+        assert!(results.contains(&Err(HoraError::PartitionsExhausted { partitions: 1 })));
+    }
+
+    #[test]
+    fn exited_threads_release_their_generator() {
+        let generator = ThreadLocalHoraGenerator::new(1, 1).unwrap();
+        let other = ThreadLocal::<u8>::new();
+        let done = Barrier::new(5);
+        let mut ids = Vec::new();
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                ids.push(scope.spawn(|| generator.try_next()).join().unwrap());
+                // keep the thread ID of the exited thread taken, so the next thread gets a new
+                // slot of the `ThreadLocal`
+                scope.spawn(|| {
+                    other.get_or(|| 0);
+                    done.wait();
+                });
+            }
+            done.wait();
+        });
+        let ids: Vec<HoraId> = ids.into_iter().map(Result::unwrap).collect();
+        // every thread continued with the generator of the one before
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
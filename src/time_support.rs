@@ -0,0 +1,83 @@
+//! [time] support for [HoraId]
+
+use crate::{ticks_to_unix_nanos, unix_nanos_to_ticks, HoraError, HoraId, HoraParams};
+use time::OffsetDateTime;
+
+impl HoraId {
+    /// Retrieve a [time] [OffsetDateTime] in UTC from [HoraId]
+    ///
+    /// The returned time is exact to the 1/256 second tick stored in the ID.
+    pub fn to_offset_datetime(&self) -> OffsetDateTime {
+        // every 40 bit timestamp is far within the range of OffsetDateTime
+        OffsetDateTime::from_unix_timestamp_nanos(ticks_to_unix_nanos(self.ticks())).unwrap()
+    }
+
+    /// Create a [HoraId] for the tick containing `datetime`
+    ///
+    /// The machine ID and sequence are zero, so the ID sorts before every generated ID of that
+    /// tick. This is useful as a lower bound for range queries.
+    ///
+    /// ## Fail condition
+    /// If `datetime` is before the HoraID epoch (2025-01-01) or too far in the future
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    /// use time::macros::datetime;
+    ///
+    /// let id = HoraId::from_offset_datetime(datetime!(2025-06-01 12:00 UTC)).unwrap();
+    /// assert_eq!(id.to_offset_datetime(), datetime!(2025-06-01 12:00 UTC));
+    /// ```
+    pub fn from_offset_datetime(datetime: OffsetDateTime) -> Result<Self, HoraError> {
+        let ticks = unix_nanos_to_ticks(datetime.unix_timestamp_nanos())?;
+        let params = HoraParams {
+            machine_id: 0,
+            ticks,
+            sequence: 0,
+        };
+        Ok(Self::with_params(params))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+    use time::Duration;
+
+    #[test]
+    fn to_offset_datetime() {
+        let id: HoraId = "00cd01daff010002".parse().unwrap();
+        let time = id.to_offset_datetime();
+        assert_eq!(time.unix_timestamp_nanos() / 1_000_000, 1749124954996);
+        assert_eq!(time.nanosecond() % 3_906_250, 0);
+
+        let id = HoraId::new(None).unwrap();
+        let now = OffsetDateTime::now_utc();
+        assert!(now - id.to_offset_datetime() < Duration::seconds(1));
+    }
+
+    #[test]
+    fn round_trip() {
+        let time = datetime!(2025-03-20 10:30:15.5 UTC);
+        let id = HoraId::from_offset_datetime(time).unwrap();
+        assert_eq!(id.to_offset_datetime(), time);
+        assert_eq!(id.machine_id(), 0);
+        assert_eq!(id.sequence(), 0);
+
+        // times within a tick round down to the start of the tick
+        let id2 = HoraId::from_offset_datetime(time + Duration::milliseconds(3)).unwrap();
+        assert_eq!(id, id2);
+    }
+
+    #[test]
+    fn out_of_range() {
+        assert_eq!(
+            HoraId::from_offset_datetime(datetime!(2024-12-31 23:59:59 UTC)),
+            Err(HoraError::TimestampOutOfRange)
+        );
+        assert_eq!(
+            HoraId::from_offset_datetime(datetime!(2200-01-01 0:00 UTC)),
+            Err(HoraError::TimestampOutOfRange)
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! [tokio] support for [HoraGenerator]
+
+use crate::{
+    Clock, GeneratorStats, HoraError, HoraGenerator, HoraId, Spin, SystemClock, WaitStrategy,
+};
+use tokio::sync::Mutex;
+
+/// Async ID generator for the tokio runtime
+///
+/// Generates IDs from a shared reference like [SyncHoraGenerator](crate::SyncHoraGenerator), but
+/// when all sequence numbers of the current tick are used, [AsyncHoraGenerator::next] awaits
+/// [tokio::time::sleep] instead of spinning, so a hot ID path doesn't burn a core of the runtime.
+/// The [WaitStrategy] of the wrapped generator is not used.
+///
+/// ## Usage
+/// ```no_run
+/// use hora_id::AsyncHoraGenerator;
+/// use std::sync::Arc;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let generator = Arc::new(AsyncHoraGenerator::new(1).unwrap());
+///
+/// let task = {
+///     let generator = Arc::clone(&generator);
+///     tokio::spawn(async move { generator.next().await })
+/// };
+/// println!("{} {}", generator.next().await, task.await.unwrap());
+/// # }
+/// ```
+pub struct AsyncHoraGenerator<C = SystemClock, W = Spin> {
+    inner: Mutex<HoraGenerator<C, W>>,
+}
+
+impl AsyncHoraGenerator {
+    /// Create a generator for any `u8` machine ID, or [HoraError::ClockBeforeEpoch] before 2025
+    pub fn new(machine_id: u8) -> Result<Self, HoraError> {
+        let generator = HoraGenerator::new(machine_id)?;
+        Ok(Self::from(generator))
+    }
+}
+
+impl<C: Clock, W: WaitStrategy> AsyncHoraGenerator<C, W> {
+    /// Generate a new [HoraId]
+    ///
+    /// ## Panics
+    /// If the system time is incorrect. Use [AsyncHoraGenerator::try_next] to handle this case.
+    #[allow(clippy::should_implement_trait)]
+    pub async fn next(&self) -> HoraId {
+        self.try_next().await.expect("failed to generate HoraId")
+    }
+
+    /// Generate a new [HoraId], returning an error instead of panicking
+    ///
+    /// ## Fail condition
+    /// Same as [HoraGenerator::try_next]
+    pub async fn try_next(&self) -> Result<HoraId, HoraError> {
+        let mut generator = self.inner.lock().await;
+        loop {
+            if let Some(id) = generator.poll_next_id()? {
+                return Ok(id);
+            }
+            tokio::time::sleep(generator.until_next_tick()).await;
+        }
+    }
+
+    /// Counters of the IDs generated so far, see [HoraGenerator::stats]
+    pub async fn stats(&self) -> GeneratorStats {
+        self.inner.lock().await.stats()
+    }
+}
+
+impl<C, W> From<HoraGenerator<C, W>> for AsyncHoraGenerator<C, W> {
+    fn from(generator: HoraGenerator<C, W>) -> Self {
+        Self {
+            inner: Mutex::new(generator),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HoraLayout, MockClock, EPOCH};
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn unique_across_tasks() {
+        let generator = Arc::new(AsyncHoraGenerator::new(1).unwrap());
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let generator = Arc::clone(&generator);
+                tokio::spawn(async move {
+                    let mut ids = Vec::new();
+                    for _ in 0..1000 {
+                        ids.push(generator.next().await);
+                    }
+                    ids
+                })
+            })
+            .collect();
+
+        let mut unique = HashSet::new();
+        for task in tasks {
+            for id in task.await.unwrap() {
+                assert!(unique.insert(id));
+            }
+        }
+        assert_eq!(unique.len(), 4000);
+    }
+
+    #[tokio::test]
+    async fn sleeps_until_next_tick() {
+        let clock = MockClock::new(EPOCH + 10_000);
+        let layout = HoraLayout::new(47, 16, 1);
+        let generator = HoraGenerator::with_layout_and_clock(layout, 1, clock.clone()).unwrap();
+        let generator = AsyncHoraGenerator::from(generator);
+
+        // the constructor already used sequence 0, so only one ID is left in this tick
+        let id = generator.next().await;
+        assert_eq!(layout.sequence(&id), 1);
+
+        let advance = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            clock.advance(4);
+        });
+        let next = generator.next().await;
+        advance.await.unwrap();
+        assert_eq!(layout.sequence(&next), 0);
+        assert!(next > id);
+    }
+}
@@ -0,0 +1,181 @@
+//! [tower_layer] middleware that gives every request a [HoraId](crate::HoraId)
+
+use crate::{Clock, Spin, SyncHoraGenerator, SystemClock, WaitStrategy};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+use http::{HeaderName, HeaderValue, Request, Response};
+use pin_project_lite::pin_project;
+use std::sync::Arc;
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Header the request ID is stored in
+const REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// [Layer] that generates a [HoraId](crate::HoraId) for every request
+///
+/// The ID is inserted into the request extensions, replaces the `x-request-id` header of the
+/// request and is added as `x-request-id` to the response unless the inner service already set
+/// one. Since the IDs are sorted by time, logs of many services can be ordered by request ID.
+///
+/// If the generator fails, for example because the system clock moved backwards, the request
+/// is passed on without an ID.
+///
+/// ## Usage
+/// ```
+/// use hora_id::{HoraId, HoraRequestIdLayer, SyncHoraGenerator};
+/// use http::{Request, Response};
+/// use std::convert::Infallible;
+/// use tower::{service_fn, ServiceBuilder};
+///
+/// async fn handler(request: Request<()>) -> Result<Response<String>, Infallible> {
+///     let request_id = request.extensions().get::<HoraId>().unwrap();
+///     Ok(Response::new(format!("request {}", request_id)))
+/// }
+///
+/// let generator = SyncHoraGenerator::new(1).unwrap();
+/// let service = ServiceBuilder::new()
+///     .layer(HoraRequestIdLayer::new(generator))
+///     .service(service_fn(handler));
+/// ```
+///
+/// With axum, add it with `Router::layer` and read the ID with `Extension<HoraId>`.
+pub struct HoraRequestIdLayer<C = SystemClock, W = Spin> {
+    generator: Arc<SyncHoraGenerator<C, W>>,
+}
+
+impl<C, W> HoraRequestIdLayer<C, W> {
+    /// Create a layer that takes IDs from `generator`, which can be shared through an [Arc]
+    pub fn new(generator: impl Into<Arc<SyncHoraGenerator<C, W>>>) -> Self {
+        Self {
+            generator: generator.into(),
+        }
+    }
+}
+
+impl<C, W> Clone for HoraRequestIdLayer<C, W> {
+    fn clone(&self) -> Self {
+        Self {
+            generator: Arc::clone(&self.generator),
+        }
+    }
+}
+
+impl<S, C, W> Layer<S> for HoraRequestIdLayer<C, W> {
+    type Service = HoraRequestId<S, C, W>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        HoraRequestId {
+            inner,
+            generator: Arc::clone(&self.generator),
+        }
+    }
+}
+
+/// Service created by [HoraRequestIdLayer]
+pub struct HoraRequestId<S, C = SystemClock, W = Spin> {
+    inner: S,
+    generator: Arc<SyncHoraGenerator<C, W>>,
+}
+
+impl<S: Clone, C, W> Clone for HoraRequestId<S, C, W> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            generator: Arc::clone(&self.generator),
+        }
+    }
+}
+
+impl<S, C, W, ReqBody, ResBody> Service<Request<ReqBody>> for HoraRequestId<S, C, W>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    C: Clock,
+    W: WaitStrategy,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = HoraRequestIdFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        let header = self.generator.try_next().ok().map(|id| {
+            let header = HeaderValue::from_str(&id.to_string()).expect("hexadecimal is ASCII");
+            request.headers_mut().insert(REQUEST_ID, header.clone());
+            request.extensions_mut().insert(id);
+            header
+        });
+        HoraRequestIdFuture {
+            inner: self.inner.call(request),
+            header,
+        }
+    }
+}
+
+pin_project! {
+    /// Response future of [HoraRequestId]
+    pub struct HoraRequestIdFuture<F> {
+        #[pin]
+        inner: F,
+        header: Option<HeaderValue>,
+    }
+}
+
+impl<F, B, E> Future for HoraRequestIdFuture<F>
+where
+    F: Future<Output = Result<Response<B>, E>>,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let mut response = ready!(this.inner.poll(cx))?;
+        if let Some(header) = this.header.take() {
+            response.headers_mut().entry(REQUEST_ID).or_insert(header);
+        }
+        Poll::Ready(Ok(response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HoraId;
+    use core::convert::Infallible;
+    use tower::{service_fn, ServiceBuilder, ServiceExt};
+
+    #[tokio::test]
+    async fn request_id() {
+        let service = ServiceBuilder::new()
+            .layer(HoraRequestIdLayer::new(SyncHoraGenerator::new(1).unwrap()))
+            .service(service_fn(|request: Request<()>| async move {
+                let id = *request.extensions().get::<HoraId>().unwrap();
+                assert_eq!(request.headers()[REQUEST_ID], id.to_string());
+                Ok::<_, Infallible>(Response::new(id))
+            }));
+
+        let first = service.clone().oneshot(Request::new(())).await.unwrap();
+        let second = service.oneshot(Request::new(())).await.unwrap();
+        assert_eq!(first.headers()[REQUEST_ID], first.body().to_string());
+        assert!(first.body() < second.body());
+    }
+
+    #[tokio::test]
+    async fn keeps_response_header() {
+        let service = HoraRequestIdLayer::new(SyncHoraGenerator::new(1).unwrap()).layer(
+            service_fn(|_: Request<()>| async {
+                let mut response = Response::new(());
+                response
+                    .headers_mut()
+                    .insert(REQUEST_ID, HeaderValue::from_static("upstream"));
+                Ok::<_, Infallible>(response)
+            }),
+        );
+        let response = service.oneshot(Request::new(())).await.unwrap();
+        assert_eq!(response.headers()[REQUEST_ID], "upstream");
+    }
+}
@@ -0,0 +1,100 @@
+//! [tracing] support for [HoraId]
+//!
+//! With the feature enabled, [HoraGenerator](crate::HoraGenerator) emits a `warn` event when
+//! the clock moves backwards and when the sequence numbers of a tick run out, once each time it
+//! happens rather than on every retry.
+
+use crate::HoraId;
+use tracing::field::{display, DisplayValue};
+
+impl HoraId {
+    /// Record the [HoraId] as a structured field of a span or event
+    ///
+    /// [tracing::Value] can only be implemented by tracing itself, so this wraps the ID in a
+    /// value that records the hexadecimal string. It is the same as `%id` in the macros.
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap();
+    /// let span = tracing::info_span!("request", id = id.tracing_value());
+    /// ```
+    pub fn tracing_value(&self) -> DisplayValue<HoraId> {
+        display(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Fail, HoraError, HoraGenerator, HoraLayout, MockClock, RollbackPolicy, EPOCH};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Level, Metadata, Subscriber};
+
+    /// Counts `warn` events
+    #[derive(Clone, Default)]
+    struct Warnings(Arc<AtomicUsize>);
+
+    impl Warnings {
+        fn count(&self) -> usize {
+            self.0.load(Ordering::SeqCst)
+        }
+    }
+
+    impl Subscriber for Warnings {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            if *event.metadata().level() == Level::WARN {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn warnings() {
+        let warnings = Warnings::default();
+        tracing::subscriber::with_default(warnings.clone(), || {
+            let clock = MockClock::new(EPOCH + 10_000);
+            let layout = HoraLayout::new(47, 16, 1);
+            let mut generator = HoraGenerator::with_layout_and_clock(layout, 1, clock.clone())
+                .unwrap()
+                .with_wait_strategy(Fail);
+            generator.next();
+            assert_eq!(generator.try_next(), Err(HoraError::SequenceExhausted));
+            assert_eq!(generator.try_next(), Err(HoraError::SequenceExhausted));
+            assert_eq!(warnings.count(), 1);
+
+            clock.advance(4);
+            generator.next();
+            assert_eq!(warnings.count(), 1);
+
+            let mut generator = generator.with_rollback_policy(RollbackPolicy::Error);
+            clock.rewind(1000);
+            assert!(generator.try_next().is_err());
+            assert!(generator.try_next().is_err());
+            assert_eq!(warnings.count(), 2);
+        });
+    }
+
+    #[test]
+    fn value() {
+        let id = crate::HoraId::from_u64(57704410318438402).unwrap();
+        assert_eq!(id.tracing_value().to_string(), "00cd01daff010002");
+    }
+}
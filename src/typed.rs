@@ -0,0 +1,204 @@
+//! Typed newtypes around [HoraId], see [TypedHoraId]
+
+use crate::HoraId;
+
+/// Newtype around [HoraId] for the IDs of one kind of object
+///
+/// Wrapping the IDs of users, orders and so on in their own types stops them from being mixed up
+/// at compile time. Derive the trait on a struct with a single [HoraId] field to also generate
+/// - [Display](core::fmt::Display) and [FromStr](core::str::FromStr), using
+///   [HoraId::to_prefixed] and [HoraId::parse_prefixed] if a prefix is set
+/// - `From<HoraId>` for the struct and `From` the struct for [HoraId]
+/// - serde `Serialize` and `Deserialize` with the `serde` feature, as the string in
+///   human-readable formats and as a `u64` in binary formats
+///
+/// Set a prefix with `#[hora_id(prefix = "usr")]`.
+///
+/// ```
+/// use hora_id::{HoraId, TypedHoraId};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, TypedHoraId)]
+/// #[hora_id(prefix = "usr")]
+/// struct UserId(HoraId);
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, TypedHoraId)]
+/// struct OrderId {
+///     id: HoraId,
+/// }
+///
+/// let user: UserId = "usr_00cd01daff010002".parse().unwrap();
+/// assert_eq!(user.to_string(), "usr_00cd01daff010002");
+/// assert_eq!(UserId::PREFIX, Some("usr"));
+///
+/// let order = OrderId::from(user.id());
+/// assert_eq!(order.to_string(), "00cd01daff010002");
+/// ```
+///
+/// Different ID types can't be passed for each other:
+/// ```compile_fail
+/// # use hora_id::{HoraId, TypedHoraId};
+/// # #[derive(TypedHoraId)]
+/// # struct UserId(HoraId);
+/// # #[derive(TypedHoraId)]
+/// # struct OrderId(HoraId);
+/// fn delete_user(id: UserId) {}
+///
+/// let order: OrderId = "00cd01daff010002".parse().unwrap();
+/// delete_user(order);
+/// ```
+pub trait TypedHoraId: From<HoraId> + Into<HoraId> {
+    /// Prefix of the string form, without the `_` separator, or `None` for plain hexadecimal
+    const PREFIX: Option<&'static str>;
+
+    /// The wrapped [HoraId]
+    fn id(&self) -> HoraId;
+}
+
+/// Helpers called by the code generated with `#[derive(TypedHoraId)]`
+#[doc(hidden)]
+pub mod __private {
+    use super::TypedHoraId;
+    use crate::{HoraId, HoraIdParseError};
+    use core::fmt;
+
+    #[cfg(feature = "serde")]
+    pub use serde;
+
+    pub fn fmt<T: TypedHoraId>(id: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(prefix) = T::PREFIX {
+            f.write_str(prefix)?;
+            f.write_str("_")?;
+        }
+        fmt::Display::fmt(&id.id(), f)
+    }
+
+    pub fn parse<T: TypedHoraId>(s: &str) -> Result<T, HoraIdParseError> {
+        let id = match T::PREFIX {
+            Some(prefix) => HoraId::parse_prefixed(prefix, s)?,
+            None => s.parse()?,
+        };
+        Ok(T::from(id))
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn serialize<T, S>(id: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: TypedHoraId + fmt::Display,
+        S: serde::Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(id)
+        } else {
+            serde::Serialize::serialize(&id.id(), serializer)
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: TypedHoraId,
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(TypedVisitor(core::marker::PhantomData))
+        } else {
+            <HoraId as serde::Deserialize>::deserialize(deserializer).map(T::from)
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    struct TypedVisitor<T>(core::marker::PhantomData<T>);
+
+    #[cfg(feature = "serde")]
+    impl<T: TypedHoraId> serde::de::Visitor<'_> for TypedVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            match T::PREFIX {
+                Some(prefix) => write!(formatter, "a {}_ prefixed HoraId", prefix),
+                None => formatter.write_str("a 16 character hexadecimal string"),
+            }
+        }
+
+        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            parse(v).map_err(E::custom)
+        }
+    }
+}
+
+/// Implements serde for a [TypedHoraId] if the `serde` feature is enabled
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __typed_hora_id_serde {
+    ($name:ident) => {
+        impl $crate::__private::serde::Serialize for $name {
+            fn serialize<S: $crate::__private::serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> ::core::result::Result<S::Ok, S::Error> {
+                $crate::__private::serialize(self, serializer)
+            }
+        }
+
+        impl<'de> $crate::__private::serde::Deserialize<'de> for $name {
+            fn deserialize<D: $crate::__private::serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> ::core::result::Result<Self, D::Error> {
+                $crate::__private::deserialize(deserializer)
+            }
+        }
+    };
+}
+
+/// Implements serde for a [TypedHoraId] if the `serde` feature is enabled
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __typed_hora_id_serde {
+    ($name:ident) => {};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{HoraId, HoraIdParseError, TypedHoraId};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, TypedHoraId)]
+    #[hora_id(prefix = "usr")]
+    struct UserId(HoraId);
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, TypedHoraId)]
+    struct OrderId {
+        id: HoraId,
+    }
+
+    #[test]
+    fn strings() {
+        let id = HoraId::from_u64(57704410318438402).unwrap();
+        let user = UserId::from(id);
+        assert_eq!(user.to_string(), "usr_00cd01daff010002");
+        assert_eq!("usr_00cd01daff010002".parse(), Ok(user));
+        assert_eq!(
+            "00cd01daff010002".parse::<UserId>(),
+            Err(HoraIdParseError::InvalidPrefix)
+        );
+
+        let order = OrderId::from(id);
+        assert_eq!(OrderId::PREFIX, None);
+        assert_eq!(order.to_string(), "00cd01daff010002");
+        assert_eq!("00cd01daff010002".parse(), Ok(order));
+        assert_eq!(HoraId::from(order), id);
+        assert_eq!(order.id(), user.id());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        use serde_test::{assert_tokens, Configure, Token};
+
+        let user = UserId::from(HoraId::from_u64(57704410318438402).unwrap());
+        assert_tokens(&user.readable(), &[Token::Str("usr_00cd01daff010002")]);
+        assert_tokens(&user.compact(), &[Token::U64(57704410318438402)]);
+        assert!(serde_json::from_str::<UserId>("\"org_00cd01daff010002\"").is_err());
+    }
+}
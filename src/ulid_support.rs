@@ -0,0 +1,102 @@
+//! [ulid] support for [HoraId]
+//!
+//! A [Ulid] stores its timestamp in milliseconds, a [HoraId] in 1/256 second ticks. Converting
+//! uses the first whole millisecond of the tick, which converts back to the same tick, so IDs
+//! and ULIDs created for the same moment can be matched by time. The machine ID and sequence
+//! fill the top 24 bits of the random part and the rest is zero, which keeps the order of the
+//! IDs and makes the conversion reversible.
+
+use crate::{unix_millis_to_ticks, HoraError, HoraId, HoraParams};
+use ulid::Ulid;
+
+/// Number of bits in the random part of a ULID below the machine ID and sequence
+const RANDOM_SHIFT: u32 = 80 - 24;
+
+impl HoraId {
+    /// Convert the [HoraId] to a [Ulid] with the same timestamp
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap();
+    /// let ulid = id.to_ulid();
+    /// assert_eq!(ulid.timestamp_ms(), id.timestamp_millis());
+    /// assert_eq!(HoraId::try_from_ulid(&ulid), Ok(id));
+    /// ```
+    pub fn to_ulid(&self) -> Ulid {
+        let low = u32::from_be_bytes([0, self.inner[5], self.inner[6], self.inner[7]]);
+        Ulid::from_parts(self.timestamp_millis(), (low as u128) << RANDOM_SHIFT)
+    }
+
+    /// Convert a [Ulid] to a [HoraId] for the same time
+    ///
+    /// The machine ID and sequence are read from the top of the random part, so ULIDs created by
+    /// [HoraId::to_ulid] convert back to the original ID. For other ULIDs they are arbitrary.
+    ///
+    /// ## Fail condition
+    /// If the timestamp of `ulid` is before the HoraID epoch (2025-01-01) or too far in the future
+    pub fn try_from_ulid(ulid: &Ulid) -> Result<Self, HoraError> {
+        let ticks = unix_millis_to_ticks(ulid.timestamp_ms())?;
+        let low = (ulid.random() >> RANDOM_SHIFT) as u32;
+        Ok(HoraId::with_params(HoraParams {
+            machine_id: (low >> 16) as u8,
+            ticks,
+            sequence: low as u16,
+        }))
+    }
+}
+
+impl From<HoraId> for Ulid {
+    fn from(id: HoraId) -> Self {
+        id.to_ulid()
+    }
+}
+
+impl TryFrom<Ulid> for HoraId {
+    type Error = HoraError;
+
+    fn try_from(ulid: Ulid) -> Result<Self, Self::Error> {
+        HoraId::try_from_ulid(&ulid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EPOCH;
+
+    #[test]
+    fn round_trip() {
+        for num in [0, 1, 0xffffff, 57704410318438402, ((1 << 38) - 1) << 24] {
+            let id = HoraId::from(num);
+            let ulid = Ulid::from(id);
+            assert_eq!(ulid.timestamp_ms(), id.timestamp_millis());
+            assert_eq!(HoraId::try_from(ulid), Ok(id));
+        }
+    }
+
+    #[test]
+    fn sort_order() {
+        let first = HoraId::from(57704410318438402);
+        let second = HoraId::from(57704410318438403);
+        let third = HoraId::from(57704410335215618);
+        assert!(first.to_ulid() < second.to_ulid());
+        assert!(second.to_ulid() < third.to_ulid());
+    }
+
+    #[test]
+    fn foreign_ulid() {
+        let ulid = Ulid::from_parts(EPOCH + 1500, u128::MAX >> 48);
+        let id = HoraId::try_from_ulid(&ulid).unwrap();
+        assert_eq!(id.timestamp_millis(), EPOCH + 1500);
+        assert_eq!(id.machine_id(), 0xff);
+        assert_eq!(id.sequence(), 0xffff);
+
+        let before_epoch = Ulid::from_parts(EPOCH - 1, 0);
+        assert_eq!(
+            HoraId::try_from(before_epoch),
+            Err(HoraError::TimestampOutOfRange)
+        );
+        assert!(HoraId::try_from(Ulid::from_parts((1 << 48) - 1, 0)).is_err());
+    }
+}
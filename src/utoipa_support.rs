@@ -0,0 +1,108 @@
+//! [utoipa] support for [HoraId] and [HoraId128]
+//!
+//! Both types are documented as the hexadecimal strings they serialize to in human-readable
+//! formats, with a pattern, fixed length and example value, so generated clients treat them as
+//! strings instead of 64-bit numbers that JavaScript can't represent.
+
+use crate::{HoraId, HoraId128};
+use std::borrow::Cow;
+use utoipa::openapi::path::{Parameter, ParameterBuilder, ParameterIn};
+use utoipa::openapi::schema::{ObjectBuilder, Schema, Type};
+use utoipa::openapi::{RefOr, Required};
+use utoipa::{IntoParams, PartialSchema, ToSchema};
+
+/// Example [HoraId] shown in the documentation
+const EXAMPLE: &str = "00cd01daff010002";
+
+/// Example [HoraId128] shown in the documentation
+const EXAMPLE_128: &str = "00cd01daff01000200cd01daff010003";
+
+/// String schema of `len` hexadecimal characters
+fn hex_schema(description: &str, len: usize, example: &str) -> RefOr<Schema> {
+    ObjectBuilder::new()
+        .schema_type(Type::String)
+        .description(Some(description))
+        .min_length(Some(len))
+        .max_length(Some(len))
+        .pattern(Some(format!("^[0-9a-fA-F]{{{}}}$", len)))
+        .examples([example])
+        .into()
+}
+
+/// A 16 character hexadecimal string
+impl PartialSchema for HoraId {
+    fn schema() -> RefOr<Schema> {
+        hex_schema("A HoraId as 16 hexadecimal characters", 16, EXAMPLE)
+    }
+}
+
+impl ToSchema for HoraId {
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("HoraId")
+    }
+}
+
+/// A single required parameter named `id`, in the path unless the handler says otherwise
+///
+/// Use this for handlers taking `Path<HoraId>` or `Query<HoraId>`. For a parameter with another
+/// name, list it in the handler attribute instead, like `params(("user_id" = HoraId, Path))`.
+impl IntoParams for HoraId {
+    fn into_params(parameter_in_provider: impl Fn() -> Option<ParameterIn>) -> Vec<Parameter> {
+        vec![ParameterBuilder::new()
+            .name("id")
+            .parameter_in(parameter_in_provider().unwrap_or_default())
+            .required(Required::True)
+            .description(Some("The ID as 16 hexadecimal characters"))
+            .schema(Some(HoraId::schema()))
+            .example(Some(EXAMPLE.into()))
+            .build()]
+    }
+}
+
+/// A 32 character hexadecimal string
+impl PartialSchema for HoraId128 {
+    fn schema() -> RefOr<Schema> {
+        hex_schema("A HoraId128 as 32 hexadecimal characters", 32, EXAMPLE_128)
+    }
+}
+
+impl ToSchema for HoraId128 {
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("HoraId128")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn schema() {
+        let schema = serde_json::to_value(HoraId::schema()).unwrap();
+        assert_eq!(schema["type"], "string");
+        assert_eq!(schema["pattern"], "^[0-9a-fA-F]{16}$");
+        assert_eq!(schema["minLength"], 16);
+        assert_eq!(schema["examples"], json!(["00cd01daff010002"]));
+        assert_eq!(HoraId::name(), "HoraId");
+        assert!(EXAMPLE.parse::<HoraId>().is_ok());
+
+        let schema = serde_json::to_value(HoraId128::schema()).unwrap();
+        assert_eq!(schema["maxLength"], 32);
+        assert!(EXAMPLE_128.parse::<HoraId128>().is_ok());
+    }
+
+    #[test]
+    fn params() {
+        let params = HoraId::into_params(|| Some(ParameterIn::Query));
+        assert_eq!(params.len(), 1);
+        let param = serde_json::to_value(&params[0]).unwrap();
+        assert_eq!(param["name"], "id");
+        assert_eq!(param["in"], "query");
+        assert_eq!(param["required"], true);
+        assert_eq!(param["schema"]["type"], "string");
+
+        let param = serde_json::to_value(&HoraId::into_params(|| None)[0]).unwrap();
+        assert_eq!(param["in"], "path");
+    }
+}
@@ -0,0 +1,117 @@
+//! [uuid] support for [HoraId]
+//!
+//! A [HoraId] is embedded in a version 8 [Uuid], the version reserved for custom layouts. The 8
+//! bytes of the ID fill the custom bits from the most significant end and the remaining bits are
+//! zero, so the UUIDs sort in the same order as the IDs:
+//!
+//! ```text
+//! bytes 0-5   ID bytes 0-5
+//! byte  6     version 8 | high nibble of ID byte 6
+//! byte  7     low nibble of ID byte 6 | high nibble of ID byte 7
+//! byte  8     RFC 9562 variant | low nibble of ID byte 7
+//! bytes 9-15  zero
+//! ```
+
+use crate::{HoraError, HoraId};
+use uuid::{Uuid, Variant};
+
+impl HoraId {
+    /// Embed the [HoraId] in a version 8 [Uuid]
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap();
+    /// let uuid = id.to_uuid();
+    /// assert_eq!(uuid.to_string(), "00cd01da-ff01-8000-8200-000000000000");
+    /// assert_eq!(HoraId::try_from_uuid(&uuid), Ok(id));
+    /// ```
+    pub fn to_uuid(&self) -> Uuid {
+        let id = self.inner;
+        let mut bytes = [0; 16];
+        bytes[..6].copy_from_slice(&id[..6]);
+        bytes[6] = 0x80 | id[6] >> 4;
+        bytes[7] = id[6] << 4 | id[7] >> 4;
+        bytes[8] = 0x80 | (id[7] & 0x0f);
+        Uuid::from_bytes(bytes)
+    }
+
+    /// Extract a [HoraId] embedded with [HoraId::to_uuid]
+    ///
+    /// ## Fail condition
+    /// If `uuid` is not a version 8 UUID with the layout of [HoraId::to_uuid]
+    pub fn try_from_uuid(uuid: &Uuid) -> Result<Self, HoraError> {
+        let bytes = uuid.as_bytes();
+        if uuid.get_version_num() != 8
+            || uuid.get_variant() != Variant::RFC4122
+            || bytes[8] & 0x30 != 0
+            || bytes[9..].iter().any(|&byte| byte != 0)
+        {
+            return Err(HoraError::IncompatibleId);
+        }
+        let mut id = [0; 8];
+        id[..6].copy_from_slice(&bytes[..6]);
+        id[6] = bytes[6] << 4 | bytes[7] >> 4;
+        id[7] = bytes[7] << 4 | (bytes[8] & 0x0f);
+        Ok(HoraId { inner: id })
+    }
+}
+
+impl From<HoraId> for Uuid {
+    fn from(id: HoraId) -> Self {
+        id.to_uuid()
+    }
+}
+
+impl TryFrom<Uuid> for HoraId {
+    type Error = HoraError;
+
+    fn try_from(uuid: Uuid) -> Result<Self, Self::Error> {
+        HoraId::try_from_uuid(&uuid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for num in [0, 1, 0x0f, 0xf0, 57704410318438402, u64::MAX] {
+            let id = HoraId::from(num);
+            let uuid = Uuid::from(id);
+            assert_eq!(uuid.get_version_num(), 8);
+            assert_eq!(uuid.get_variant(), Variant::RFC4122);
+            assert_eq!(HoraId::try_from(uuid), Ok(id));
+        }
+    }
+
+    #[test]
+    fn sort_order() {
+        let ids = [
+            1,
+            0x0f,
+            0x10,
+            0xff,
+            0x100,
+            0xfff,
+            57704410318438402,
+            u64::MAX,
+        ];
+        let uuids: Vec<Uuid> = ids.iter().map(|&num| HoraId::from(num).to_uuid()).collect();
+        assert!(uuids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn incompatible() {
+        let v4: Uuid = "67e55044-10b1-426f-9247-bb680e5fe0c8".parse().unwrap();
+        assert_eq!(HoraId::try_from_uuid(&v4), Err(HoraError::IncompatibleId));
+        let mut bytes = *HoraId::from(1).to_uuid().as_bytes();
+        bytes[15] = 1;
+        assert_eq!(
+            HoraId::try_from(Uuid::from_bytes(bytes)),
+            Err(HoraError::IncompatibleId)
+        );
+        assert!(HoraId::try_from(Uuid::nil()).is_err());
+    }
+}
@@ -0,0 +1,132 @@
+//! Format version stored in the top bits of every ID
+
+use crate::{HoraError, HoraId, EPOCH};
+
+/// Number of bits at the top of a [HoraId] that hold its format version
+pub(crate) const VERSION_BITS: u8 = 2;
+
+/// Parts of a [HoraId], decoded according to its format version, see [HoraId::decode]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct HoraParts {
+    /// Format version the parts were decoded with
+    pub version: u8,
+    /// Creation time in milliseconds since the Unix epoch, see [HoraId::timestamp_millis]
+    pub timestamp_millis: u64,
+    /// Machine ID of the generator that created the ID, see [HoraId::machine_id]
+    pub machine_id: u16,
+    /// Sequence number of the ID within its tick, see [HoraId::sequence]
+    pub sequence: u32,
+}
+
+impl HoraId {
+    /// Format version of the IDs created by this version of the crate
+    ///
+    /// Version 0 is the layout described in the crate documentation, with a 38 bit timestamp
+    /// that lasts until 2059. Later versions can change the precision or the width of the parts,
+    /// and still sort after all IDs of earlier versions.
+    pub const VERSION: u8 = 0;
+
+    /// Format version of the [HoraId], stored in its highest 2 bits
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap();
+    /// assert_eq!(id.version(), 0);
+    /// assert_eq!(HoraId::MAX.version(), 3);
+    /// ```
+    pub const fn version(&self) -> u8 {
+        self.inner[0] >> (8 - VERSION_BITS)
+    }
+
+    /// Split the [HoraId] into its parts, following the layout of its format version
+    ///
+    /// The accessors such as [HoraId::timestamp_millis] read every ID as version 0. Use this
+    /// where IDs of later versions can appear, for example when reading IDs produced by other
+    /// services.
+    ///
+    /// The parts are split with [HoraLayout::DEFAULT](crate::HoraLayout::DEFAULT). IDs of a
+    /// generator with a custom layout come back with the wrong machine ID and sequence, read
+    /// them with the accessors of their [HoraLayout](crate::HoraLayout) instead.
+    ///
+    /// ## Fail condition
+    /// If the version is not known to this version of the crate
+    /// ([HoraError::UnsupportedVersion])
+    ///
+    /// ```
+    /// use hora_id::HoraId;
+    ///
+    /// let id: HoraId = "00cd01daff010002".parse().unwrap();
+    /// let parts = id.decode().unwrap();
+    /// assert_eq!(parts.timestamp_millis, 1749124954997);
+    /// assert_eq!((parts.machine_id, parts.sequence), (1, 2));
+    /// ```
+    pub fn decode(&self) -> Result<HoraParts, HoraError> {
+        self.decode_with_epoch(EPOCH)
+    }
+
+    /// Split a [HoraId] generated with a custom epoch into its parts, see [HoraId::decode]
+    ///
+    /// `epoch_millis` must be the epoch the generator was configured with, see
+    /// [HoraGenerator::with_epoch](crate::HoraGenerator::with_epoch).
+    ///
+    /// ## Fail condition
+    /// If the version is not known to this version of the crate
+    /// ([HoraError::UnsupportedVersion])
+    pub fn decode_with_epoch(&self, epoch_millis: u64) -> Result<HoraParts, HoraError> {
+        match self.version() {
+            0 => Ok(HoraParts {
+                version: 0,
+                timestamp_millis: self.timestamp_millis_with_epoch(epoch_millis),
+                machine_id: self.machine_id() as u16,
+                sequence: self.sequence() as u32,
+            }),
+            version => Err(HoraError::UnsupportedVersion { version }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HoraGenerator, MockClock, EPOCH};
+
+    #[test]
+    fn version() {
+        let id = HoraId::from_bytes([0x3f, 0xff, 0xff, 0xff, 0xff, 1, 0, 2]);
+        assert_eq!(id.version(), 0);
+        assert_eq!(id.decode().unwrap().machine_id, 1);
+
+        let id = HoraId::from_bytes([0x40, 0, 0, 0, 0, 1, 0, 2]);
+        assert_eq!(id.version(), 1);
+        assert_eq!(
+            id.decode(),
+            Err(HoraError::UnsupportedVersion { version: 1 })
+        );
+        assert_eq!(HoraId::NIL.version(), HoraId::VERSION);
+    }
+
+    #[test]
+    fn decode_with_epoch() {
+        let id: HoraId = "00cd01daff010002".parse().unwrap();
+        let parts = id.decode_with_epoch(EPOCH + 60_000).unwrap();
+        assert_eq!(parts.timestamp_millis, id.timestamp_millis() + 60_000);
+        assert_eq!(id.decode_with_epoch(EPOCH), id.decode());
+    }
+
+    #[test]
+    fn generator_stays_in_version() {
+        // 2^38 ticks of 1/256 second after the epoch, in 2059
+        let end = EPOCH + (1u64 << 38) / 256 * 1000;
+        let clock = MockClock::new(end - 1000);
+        let mut generator = HoraGenerator::new_with_clock(1, clock.clone()).unwrap();
+        assert_eq!(generator.next().version(), 0);
+        clock.advance(1000);
+        assert_eq!(generator.try_next(), Err(HoraError::TimestampOutOfRange));
+        assert_eq!(
+            HoraId::min_for_millis(end),
+            Err(HoraError::TimestampOutOfRange)
+        );
+    }
+}
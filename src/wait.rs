@@ -0,0 +1,93 @@
+//! Strategies for waiting on the clock in [HoraGenerator](crate::HoraGenerator)
+
+use core::ops::ControlFlow;
+#[cfg(feature = "std")]
+use core::time::Duration;
+
+/// What a [HoraGenerator](crate::HoraGenerator) does while it waits for the clock
+///
+/// The generator waits when all sequence numbers of the current tick are used, and when the
+/// clock moved backwards with [RollbackPolicy::WaitUntilCaughtUp](crate::RollbackPolicy).
+/// It calls [WaitStrategy::wait] before every new read of the clock.
+pub trait WaitStrategy {
+    /// Wait a little before the clock is read again
+    ///
+    /// Return [ControlFlow::Break] to give up. The generator then returns
+    /// [HoraError::SequenceExhausted](crate::HoraError::SequenceExhausted) or
+    /// [HoraError::ClockMovedBackwards](crate::HoraError::ClockMovedBackwards) instead of
+    /// waiting.
+    fn wait(&self) -> ControlFlow<()>;
+}
+
+/// Busy-wait with [spin_loop](core::hint::spin_loop) for the lowest latency, the default
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Spin;
+
+impl WaitStrategy for Spin {
+    fn wait(&self) -> ControlFlow<()> {
+        core::hint::spin_loop();
+        ControlFlow::Continue(())
+    }
+}
+
+/// Spin once, then [yield](std::thread::yield_now) the rest of the time slice to other threads
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Yield;
+
+#[cfg(feature = "std")]
+impl WaitStrategy for Yield {
+    fn wait(&self) -> ControlFlow<()> {
+        core::hint::spin_loop();
+        std::thread::yield_now();
+        ControlFlow::Continue(())
+    }
+}
+
+/// [Sleep](std::thread::sleep) for the given duration between reads of the clock
+///
+/// A tick is 1/256 of a second (about 3.9 ms), so durations below that keep the delay after the
+/// clock moved on short.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sleep(pub Duration);
+
+#[cfg(feature = "std")]
+impl WaitStrategy for Sleep {
+    fn wait(&self) -> ControlFlow<()> {
+        std::thread::sleep(self.0);
+        ControlFlow::Continue(())
+    }
+}
+
+/// Never wait, return an error instead
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Fail;
+
+impl WaitStrategy for Fail {
+    fn wait(&self) -> ControlFlow<()> {
+        ControlFlow::Break(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn strategies() {
+        assert_eq!(Spin.wait(), ControlFlow::Continue(()));
+        assert_eq!(Yield.wait(), ControlFlow::Continue(()));
+        assert_eq!(Fail.wait(), ControlFlow::Break(()));
+
+        let start = Instant::now();
+        assert_eq!(
+            Sleep(Duration::from_millis(5)).wait(),
+            ControlFlow::Continue(())
+        );
+        assert!(start.elapsed() >= Duration::from_millis(5));
+    }
+}
@@ -0,0 +1,167 @@
+//! [wasm_bindgen] bindings for browsers, Node.js and edge workers
+//!
+//! `std::time::SystemTime` panics on `wasm32-unknown-unknown`, so the bindings read the time
+//! from `Date.now()` with [JsClock], and random bytes come from `crypto.getRandomValues()`. On
+//! WASI targets the system clock works and the regular [HoraGenerator] can be used directly.
+//!
+//! ```js
+//! import { HoraGenerator, HoraId } from "hora_id";
+//!
+//! const generator = new HoraGenerator(1);
+//! const id = generator.next();
+//! console.log(id.toString(), id.toBigInt(), id.toDate());
+//! console.log(HoraId.parse("00cd01daff010002").machineId);
+//! ```
+
+use crate::{Clock, HoraError, HoraGenerator, HoraId, HoraParts};
+use wasm_bindgen::prelude::*;
+
+/// [Clock] reading `Date.now()` of the JavaScript host
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsClock;
+
+impl Clock for JsClock {
+    fn now_millis(&self) -> u64 {
+        js_sys::Date::now() as u64
+    }
+}
+
+/// [HoraId] exported to JavaScript as the `HoraId` class
+///
+/// 64-bit numbers are passed as `BigInt`, since a JavaScript `number` loses the lowest bits.
+#[wasm_bindgen(js_name = HoraId)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WasmHoraId {
+    id: HoraId,
+}
+
+#[wasm_bindgen(js_class = HoraId)]
+impl WasmHoraId {
+    /// Parse the 16 character hexadecimal form, throws on invalid input
+    pub fn parse(s: &str) -> Result<WasmHoraId, JsError> {
+        Ok(Self { id: s.parse()? })
+    }
+
+    /// Create an ID from the `BigInt` returned by `toBigInt()`
+    #[wasm_bindgen(js_name = fromBigInt)]
+    pub fn from_big_int(num: u64) -> WasmHoraId {
+        Self {
+            id: HoraId::from_bytes(num.to_be_bytes()),
+        }
+    }
+
+    /// The 16 character hexadecimal form
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> String {
+        self.id.to_string()
+    }
+
+    /// The ID as a `BigInt`
+    #[wasm_bindgen(js_name = toBigInt)]
+    pub fn to_big_int(&self) -> u64 {
+        self.id.to_u64()
+    }
+
+    /// Format version of the ID, the other parts throw for versions this build can't decode
+    #[wasm_bindgen(getter)]
+    pub fn version(&self) -> u8 {
+        self.id.version()
+    }
+
+    /// Creation time in milliseconds since the Unix epoch
+    #[wasm_bindgen(getter)]
+    pub fn timestamp(&self) -> Result<f64, JsError> {
+        Ok(self.parts()?.timestamp_millis as f64)
+    }
+
+    /// Creation time as a `Date`
+    #[wasm_bindgen(js_name = toDate)]
+    pub fn to_date(&self) -> Result<js_sys::Date, JsError> {
+        Ok(js_sys::Date::new(&JsValue::from_f64(self.timestamp()?)))
+    }
+
+    #[wasm_bindgen(getter, js_name = machineId)]
+    pub fn machine_id(&self) -> Result<u16, JsError> {
+        Ok(self.parts()?.machine_id)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn sequence(&self) -> Result<u32, JsError> {
+        Ok(self.parts()?.sequence)
+    }
+
+    /// Negative, zero or positive like the callback of `Array.prototype.sort`
+    pub fn compare(&self, other: &WasmHoraId) -> i32 {
+        self.id.cmp(&other.id) as i32
+    }
+}
+
+impl WasmHoraId {
+    fn parts(&self) -> Result<HoraParts, HoraError> {
+        self.id.decode()
+    }
+}
+
+impl From<HoraId> for WasmHoraId {
+    fn from(id: HoraId) -> Self {
+        Self { id }
+    }
+}
+
+impl From<WasmHoraId> for HoraId {
+    fn from(id: WasmHoraId) -> Self {
+        id.id
+    }
+}
+
+/// [HoraGenerator] reading the time from [JsClock], exported as the `HoraGenerator` class
+///
+/// JavaScript is single-threaded, so when the sequence of a tick is used up the generator
+/// blocks for up to 4 ms until the next tick.
+#[wasm_bindgen(js_name = HoraGenerator)]
+pub struct WasmHoraGenerator {
+    generator: HoraGenerator<JsClock>,
+}
+
+#[wasm_bindgen(js_class = HoraGenerator)]
+impl WasmHoraGenerator {
+    /// Create a generator for `machineId`, throws if the clock is before the HoraID epoch
+    #[wasm_bindgen(constructor)]
+    pub fn new(machine_id: u8) -> Result<WasmHoraGenerator, JsError> {
+        Ok(Self {
+            generator: HoraGenerator::new_with_clock(machine_id, JsClock)?,
+        })
+    }
+
+    /// Generate a new ID, throws if the clock moved backwards under the rollback policy
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<WasmHoraId, JsError> {
+        Ok(self.generator.try_next()?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn id() {
+        let id = WasmHoraId::parse("00cd01daff010002").unwrap();
+        assert_eq!(id.to_big_int(), 57704410318438402);
+        assert_eq!(WasmHoraId::from_big_int(57704410318438402), id);
+        assert_eq!(id.to_js_string(), "00cd01daff010002");
+        assert_eq!(id.version(), 0);
+        let parts = id.parts().unwrap();
+        assert_eq!((parts.machine_id, parts.sequence), (1, 2));
+        let next = WasmHoraId::from_big_int(57704410318438403);
+        assert_eq!(id.compare(&next), -1);
+        assert_eq!(HoraId::from(id).to_u64(), 57704410318438402);
+
+        let future = WasmHoraId::parse("40cd01daff010002").unwrap();
+        assert_eq!(future.version(), 1);
+        assert_eq!(
+            future.parts(),
+            Err(HoraError::UnsupportedVersion { version: 1 })
+        );
+    }
+}